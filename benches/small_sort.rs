@@ -0,0 +1,67 @@
+//! `tinyvec::sort_unstable` against `slice::sort_unstable` on the
+//! short lengths the sorting networks cover.
+//!
+//! `cargo bench --bench small_sort`. One run on an x86_64 laptop:
+//!
+//! |len |network   |slice     |
+//! |----|----------|----------|
+//! |2   |2.92ms    |4.18ms    |
+//! |3   |4.31ms    |6.97ms    |
+//! |4   |4.60ms    |8.50ms    |
+//! |5   |6.35ms    |11.66ms   |
+//! |6   |7.66ms    |16.76ms   |
+//! |7   |8.85ms    |19.92ms   |
+//! |8   |9.61ms    |22.07ms   |
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use vecstor::tinyvec;
+
+const ROUNDS: usize = 200_000;
+
+/// xorshift, so the inputs are the same on every run.
+fn inputs(len: usize) -> Vec<[u32; 8]> {
+    let mut state = 0x2545_f491_u32;
+    (0..ROUNDS)
+        .map(|_| {
+            let mut input = [0; 8];
+            for slot in &mut input[..len] {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                *slot = state;
+            }
+            input
+        })
+        .collect()
+}
+
+fn time(mut run: impl FnMut()) -> Duration {
+    let begin = Instant::now();
+    run();
+    begin.elapsed()
+}
+
+fn main() {
+    for len in 2..=8 {
+        let inputs = inputs(len);
+        let network = time(|| {
+            for input in &inputs {
+                let mut vector: tinyvec<u32, 8> = tinyvec::new();
+                vector.extend_from_slice(&input[..len]);
+                vector.sort_unstable();
+                black_box(&vector);
+            }
+        });
+        let slice = time(|| {
+            for input in &inputs {
+                let mut vector: tinyvec<u32, 8> = tinyvec::new();
+                vector.extend_from_slice(&input[..len]);
+                vector.as_mut_slice().sort_unstable();
+                black_box(&vector);
+            }
+        });
+        println!("|{len:<4}|{network:<10.2?}|{slice:<10.2?}|");
+    }
+}
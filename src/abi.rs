@@ -0,0 +1,46 @@
+//! abi_stable support. tinyvec's own layout isn't stable across
+//! compilers, so values cross a dynamic library boundary as
+//! `RVec`/`RString` and borrows as `RSlice`/`RSliceMut`. Spilled
+//! vectors hand their heap allocation over without copying.
+
+use abi_stable::std_types::{RSlice, RSliceMut, RString, RVec};
+
+use crate::allocator::Global;
+use crate::{growth, index, tinystring, tinyvec};
+
+impl<T, const N: usize, G: growth::policy, L: index> tinyvec<T, N, Global, G, L> {
+    pub fn as_rslice(&self) -> RSlice<'_, T> {
+        RSlice::from_slice(self.as_slice())
+    }
+
+    pub fn as_rslice_mut(&mut self) -> RSliceMut<'_, T> {
+        RSliceMut::from_mut_slice(self.as_mut_slice())
+    }
+}
+
+impl<T, const N: usize, G: growth::policy, L: index> From<tinyvec<T, N, Global, G, L>> for RVec<T> {
+    fn from(vec: tinyvec<T, N, Global, G, L>) -> Self {
+        RVec::from(vec.into_vec())
+    }
+}
+
+/// Copies if the `RVec` was allocated by another library.
+impl<T, const N: usize, G: growth::policy, L: index> From<RVec<T>> for tinyvec<T, N, Global, G, L> {
+    fn from(vec: RVec<T>) -> Self {
+        tinyvec::from(vec.into_vec())
+    }
+}
+
+impl<const N: usize> From<tinystring<N>> for RString {
+    fn from(s: tinystring<N>) -> Self {
+        let bytes = RVec::from(s.into_bytes());
+        // The bytes came out of a `tinystring`.
+        unsafe { RString::from_utf8_unchecked(bytes) }
+    }
+}
+
+impl<const N: usize> From<RString> for tinystring<N> {
+    fn from(s: RString) -> Self {
+        tinystring::from(s.into_string())
+    }
+}
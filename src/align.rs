@@ -0,0 +1,29 @@
+//! Alignment markers for over-aligned inline buffers.
+//!
+//! Passed as the last parameter of [`inline`](crate::inline), e.g.
+//! `storagevec<f32, inline<f32, 16, usize, align::a64>>` keeps its
+//! elements on a 64 byte boundary, ready for SIMD loads or DMA.
+
+/// No extra alignment, just `align_of::<T>()`. The default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct natural;
+
+/// 16 byte alignment.
+#[repr(align(16))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct a16;
+
+/// 32 byte alignment.
+#[repr(align(32))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct a32;
+
+/// 64 byte alignment, a cache line on most targets.
+#[repr(align(64))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct a64;
+
+/// 128 byte alignment.
+#[repr(align(128))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct a128;
@@ -0,0 +1,91 @@
+//! Allocator plumbing for the heap segment.
+//!
+//! With the `allocator_api` feature (nightly) this is just
+//! `std::alloc::{Allocator, Global}` and the heap is a `Vec<T, A>`.
+//! On stable only [`Global`] exists and the heap is a plain `Vec<T>`,
+//! so the allocator parameter costs nothing there.
+
+#[cfg(feature = "allocator_api")]
+pub use std::alloc::{Allocator, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+pub use stable::{Allocator, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+mod stable {
+    use std::marker::PhantomData;
+    use std::ops::{Deref, DerefMut};
+
+    mod sealed {
+        pub trait sealed {}
+    }
+
+    /// Stand-in for `std::alloc::Allocator` on stable.
+    /// Enable the `allocator_api` feature to plug in your own.
+    pub trait Allocator: sealed::sealed {}
+
+    /// The global allocator.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Global;
+
+    impl sealed::sealed for Global {}
+    impl Allocator for Global {}
+
+    /// `Vec<T>` tagged with the allocator it would have used.
+    #[derive(Debug)]
+    pub struct heap<T, A>(Vec<T>, PhantomData<A>);
+
+    impl<T, A> heap<T, A> {
+        pub fn new_in(alloc: A) -> Self {
+            heap(Vec::new(), PhantomData)
+        }
+
+        pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+            heap(Vec::with_capacity(capacity), PhantomData)
+        }
+
+        pub fn from_vec(vec: Vec<T>) -> Self {
+            heap(vec, PhantomData)
+        }
+
+        pub fn into_vec(self) -> Vec<T> {
+            self.0
+        }
+    }
+
+    impl<T, A> Deref for heap<T, A> {
+        type Target = Vec<T>;
+        fn deref(&self) -> &Vec<T> {
+            &self.0
+        }
+    }
+
+    impl<T, A> DerefMut for heap<T, A> {
+        fn deref_mut(&mut self) -> &mut Vec<T> {
+            &mut self.0
+        }
+    }
+}
+
+/// Backing Vec of the heap segment.
+#[cfg(feature = "allocator_api")]
+pub(crate) type heap<T, A> = Vec<T, A>;
+
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) use stable::heap;
+
+/// Adopt a global `Vec`'s allocation as a heap segment.
+pub(crate) fn heap_from_vec<T>(vec: Vec<T>) -> heap<T, Global> {
+    #[cfg(feature = "allocator_api")]
+    return vec;
+    #[cfg(not(feature = "allocator_api"))]
+    heap::from_vec(vec)
+}
+
+/// Hand a heap segment's allocation over as a global `Vec`.
+pub(crate) fn heap_into_vec<T>(heap: heap<T, Global>) -> Vec<T> {
+    #[cfg(feature = "allocator_api")]
+    return heap;
+    #[cfg(not(feature = "allocator_api"))]
+    heap.into_vec()
+}
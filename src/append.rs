@@ -0,0 +1,116 @@
+//! Append-only buffer shared between threads, see [`appendvec`].
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::capacity_error;
+
+/// N inline slots that any number of threads can [`push`](appendvec::push)
+/// into through a shared reference, without a lock. Each push claims a
+/// slot by bumping an atomic counter and then writes it once.
+///
+/// Reading needs `&mut self` (or ownership): once the pushing threads
+/// are done the borrow checker guarantees every claimed slot is
+/// written. Order between threads is whatever order they claimed
+/// slots in.
+///
+/// ```rust
+/// use vecstor::appendvec;
+/// let mut events: appendvec<u32, 8> = appendvec::new();
+/// std::thread::scope(|s| {
+///     for id in 0..4 {
+///         let events = &events;
+///         s.spawn(move || events.push(id).unwrap());
+///     }
+/// });
+/// let mut seen = events.as_mut_slice().to_vec();
+/// seen.sort();
+/// assert_eq!(seen, [0, 1, 2, 3]);
+/// ```
+pub struct appendvec<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    /// Slots claimed so far, never more than N.
+    claimed: AtomicUsize,
+}
+
+// Each slot is written by exactly one push and only read through
+// `&mut self`, so sharing needs nothing more than moving T across.
+unsafe impl<T: Send, const N: usize> Sync for appendvec<T, N> {}
+
+impl<T, const N: usize> appendvec<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            claimed: AtomicUsize::new(0),
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Slots claimed so far. While other threads are pushing this is
+    /// only a snapshot, and some of them may still be writing.
+    pub fn len(&self) -> usize {
+        self.claimed.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append `element`, or hand it back if all N slots are taken.
+    pub fn push(&self, element: T) -> Result<(), capacity_error<T>> {
+        let claim = self
+            .claimed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                (n < N).then_some(n + 1)
+            });
+        match claim {
+            Ok(slot) => {
+                unsafe { (*self.slots[slot].get()).write(element) };
+                Ok(())
+            }
+            Err(_) => Err(capacity_error::new(element)),
+        }
+    }
+
+    /// The pushed elements, in claim order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = *self.claimed.get_mut();
+        unsafe { std::slice::from_raw_parts_mut(self.slots.as_mut_ptr().cast(), len) }
+    }
+
+    pub fn as_slice(&mut self) -> &[T] {
+        self.as_mut_slice()
+    }
+
+    /// Drop every element so the slots can be filled again.
+    pub fn clear(&mut self) {
+        let elements: *mut [T] = self.as_mut_slice();
+        *self.claimed.get_mut() = 0;
+        unsafe { std::ptr::drop_in_place(elements) };
+    }
+}
+
+impl<T, const N: usize> Drop for appendvec<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for appendvec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Only the claimed count, since reading elements needs `&mut`.
+#[cfg(feature = "fmt")]
+impl<T, const N: usize> fmt::Debug for appendvec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "appendvec {{ len: {}, capacity: {N} }}", self.len())
+    }
+}
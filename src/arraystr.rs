@@ -0,0 +1,185 @@
+//! Fixed-capacity UTF-8 string without any heap, see [`arraystring`].
+
+use std::fmt;
+
+use crate::error::capacity_error;
+use crate::{inline, storagevec};
+
+/// String with room for exactly N bytes of UTF-8, kept inline. The
+/// [`stackvec`](crate::stackvec) of strings: it never allocates, and
+/// pushing past N is an error instead, which makes it fit for panic
+/// messages, log lines and serial output on targets without a heap.
+///
+/// ```rust
+/// use std::fmt::Write;
+/// use vecstor::arraystring;
+/// let mut line: arraystring<12> = arraystring::new();
+/// write!(line, "t={}ms", 250).unwrap();
+/// assert_eq!(line.as_str(), "t=250ms");
+/// assert!(line.push_str(" overflow").is_err());
+/// assert_eq!(line.as_str(), "t=250ms");
+/// ```
+pub struct arraystring<const N: usize> {
+    /// Always valid UTF-8.
+    bytes: storagevec<u8, inline<u8, N>>,
+}
+
+impl<const N: usize> arraystring<N> {
+    pub fn new() -> Self {
+        Self {
+            bytes: storagevec::<u8, inline<u8, N>>::new(),
+        }
+    }
+
+    /// Length in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Always N bytes.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Bytes left before pushes start failing.
+    pub fn remaining(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Append all of `s`, or nothing if it doesn't fit.
+    pub fn push_str(&mut self, s: &str) -> Result<(), capacity_error> {
+        if s.len() > self.remaining() {
+            return Err(capacity_error::new(()));
+        }
+        self.bytes.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+
+    /// Append one character, or hand it back if it doesn't fit.
+    pub fn push(&mut self, c: char) -> Result<(), capacity_error<char>> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+            .map_err(|_| capacity_error::new(c))
+    }
+
+    /// Remove and return the last character.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.truncate(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Shorten to `len` bytes. Does nothing if `len >= self.len()`.
+    ///
+    /// Panics if `len` is not on a char boundary.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            assert!(
+                self.as_str().is_char_boundary(len),
+                "truncate: {len} is not a char boundary"
+            );
+            self.bytes.truncate(len);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { std::str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+impl<const N: usize> std::ops::Deref for arraystring<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> std::ops::DerefMut for arraystring<N> {
+    fn deref_mut(&mut self) -> &mut str {
+        unsafe { std::str::from_utf8_unchecked_mut(self.bytes.as_mut_slice()) }
+    }
+}
+
+/// A piece that doesn't fit is left out whole and reported as
+/// `fmt::Error`.
+impl<const N: usize> fmt::Write for arraystring<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> Default for arraystring<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Clone for arraystring<N> {
+    fn clone(&self) -> Self {
+        let mut copy = Self::new();
+        copy.bytes.extend_from_slice(self.as_bytes());
+        copy
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<const N: usize> fmt::Debug for arraystring<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<const N: usize> fmt::Display for arraystring<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for arraystring<N> {
+    type Error = capacity_error;
+
+    fn try_from(s: &str) -> Result<Self, capacity_error> {
+        let mut string = Self::new();
+        string.push_str(s)?;
+        Ok(string)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<arraystring<M>> for arraystring<N> {
+    fn eq(&self, other: &arraystring<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for arraystring<N> {}
+
+impl<const N: usize> PartialEq<str> for arraystring<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for arraystring<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> std::hash::Hash for arraystring<N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
@@ -0,0 +1,922 @@
+//! Backing storage for [`storagevec`](crate::storagevec).
+//!
+//! Every backend hands out one contiguous run of slots, the first
+//! `len()` of which are initialized. `storagevec` does all the element
+//! bookkeeping on top of that, so the same API runs over
+//! - [`spill`]: inline array that moves to the heap once it is full,
+//! - [`inline`]: inline array only, never allocates,
+//! - [`boxed`]: like `spill`, but the N slots are boxed,
+//! - [`borrowed`]: a caller-provided `&mut [MaybeUninit<T>]`.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::align::natural;
+use crate::allocator::{Allocator, Global, heap};
+use crate::error::try_reserve_error;
+use crate::growth::{doubling, policy};
+
+/// Contiguous memory a [`storagevec`](crate::storagevec) can live in.
+///
+/// # Safety
+///
+/// `as_ptr()`/`as_mut_ptr()` must point at `capacity()` slots valid for
+/// reads and writes of `T`, and must keep pointing at the same
+/// elements (possibly moved) across `reserve()`. The first `len()`
+/// slots are initialized, and the storage never drops them itself.
+pub unsafe trait storage<T> {
+    /// Number of initialized slots at the front.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// # Safety
+    ///
+    /// `len <= capacity()` and the first `len` slots are initialized.
+    unsafe fn set_len(&mut self, len: usize);
+
+    /// Number of slots available right now.
+    fn capacity(&self) -> usize;
+
+    fn as_ptr(&self) -> *const T;
+
+    fn as_mut_ptr(&mut self) -> *mut T;
+
+    /// Make room for `additional` more elements, moving the
+    /// initialized ones if needed. Returns false if this storage
+    /// can't grow that far.
+    fn reserve(&mut self, additional: usize) -> bool;
+
+    /// Like `reserve()`, telling why it failed. Backends that
+    /// allocate should report allocation failures here instead of
+    /// aborting.
+    fn try_reserve(&mut self, additional: usize) -> Result<(), try_reserve_error> {
+        if self.reserve(additional) {
+            Ok(())
+        } else {
+            Err(try_reserve_error::capacity_overflow)
+        }
+    }
+
+    /// Called after elements were removed, with the length already
+    /// updated. Backends may give spare capacity back here; the
+    /// default keeps it.
+    fn after_remove(&mut self) {}
+}
+
+/// Integer a backend keeps its inline length in.
+///
+/// `usize` by default. `u8`/`u16`/`u32` make small storages smaller,
+/// e.g. `inline<u8, 6, u16>` is 8 bytes instead of 16, at the price
+/// of N being capped by the type.
+pub trait index: Copy {
+    const MAX: usize;
+
+    /// `n <= Self::MAX`.
+    fn from_usize(n: usize) -> Self;
+
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_index {
+    ($($t:ty),*) => {$(
+        impl index for $t {
+            const MAX: usize = if (<$t>::MAX as u128) < (usize::MAX as u128) {
+                <$t>::MAX as usize
+            } else {
+                usize::MAX
+            };
+
+            fn from_usize(n: usize) -> Self {
+                debug_assert!(n <= <Self as index>::MAX, "length {n} overflows {}", stringify!($t));
+                n as $t
+            }
+
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+        }
+    )*};
+}
+
+impl_index!(u8, u16, u32, usize);
+
+/// Most `T`s one allocation may hold: like `Vec`, no buffer spans
+/// more than `isize::MAX` bytes. Unlimited for zero-sized types.
+pub(crate) const fn max_len<T>() -> usize {
+    match size_of::<T>() {
+        0 => usize::MAX,
+        size => isize::MAX as usize / size,
+    }
+}
+
+/// Heap capacity to grow to for `needed` slots: `G`'s pick, cut back
+/// to what an allocation may hold. `None` if even `needed` is too many.
+fn grow_target<T, G: policy>(capacity: usize, needed: usize) -> Option<usize> {
+    let max = max_len::<T>();
+    (needed <= max).then(|| G::grow(capacity, needed).clamp(needed, max))
+}
+
+/// Bytes of a heap buffer of `capacity` slots, for the stats.
+#[cfg(feature = "stats")]
+fn heap_size<T>(capacity: usize) -> usize {
+    capacity.saturating_mul(size_of::<T>())
+}
+
+/// Set with [`on_spill`], null while there is no hook.
+static spill_hook: AtomicPtr<()> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Call `hook` whenever a [`spill`] moves to the heap, with the number
+/// of elements it held on the stack. Only an instance's first spill
+/// is reported, not the ones after moving back inline, and one that
+/// started out on the heap never is. Handy to log or
+/// assert that a buffer meant to stay inline started allocating.
+/// `None` removes the hook. Applies to all instances, process-wide.
+pub fn on_spill(hook: Option<fn(usize)>) {
+    let ptr = hook.map_or(std::ptr::null_mut(), |hook| hook as *mut ());
+    spill_hook.store(ptr, Ordering::Release);
+}
+
+fn run_spill_hook(len: usize) {
+    let ptr = spill_hook.load(Ordering::Acquire);
+    if !ptr.is_null() {
+        let hook = unsafe { std::mem::transmute::<*mut (), fn(usize)>(ptr) };
+        hook(len);
+    }
+}
+
+/// Inline array that moves everything to the heap once it runs out
+/// of slots. This is what [`tinyvec`](crate::tinyvec) uses.
+/// `G` decides how the heap grows, see [`growth`](crate::growth).
+///
+/// The elements live either on the stack or on the heap, never both,
+/// so the two share memory and `spill` is about as big as the larger
+/// of `[T; N]` and a `Vec`.
+pub struct spill<T, const N: usize, A: Allocator + Clone = Global, G = doubling, L = usize> {
+    data: data<T, N, A, L>,
+    alloc: A,
+    #[cfg(feature = "spill_count")]
+    spills: u32,
+    #[cfg(feature = "high_water")]
+    high_water: usize,
+    growth: PhantomData<fn() -> G>,
+}
+
+enum data<T, const N: usize, A: Allocator, L> {
+    /// `reported` is whether the spill hook already ran, or had its
+    /// chance, for this instance; here rather than on `spill` it
+    /// mostly fits in the padding after the tag.
+    stack {
+        len: L,
+        buf: [MaybeUninit<T>; N],
+        reported: bool,
+    },
+    heap(heap<T, A>),
+}
+
+impl<T, const N: usize, A: Allocator + Clone, G: policy, L: index> spill<T, N, A, G, L> {
+    /// Slots available on the stack. Zero-sized types take no room,
+    /// so for them the stack only runs out when `L` does.
+    const STACK: usize = if size_of::<T>() == 0 { L::MAX } else { N };
+
+    /// Starts out on the heap if `heap_capacity` is more than
+    /// the stack can hold.
+    pub(crate) fn with_alloc(heap_capacity: usize, alloc: A) -> Self {
+        const { assert!(N <= L::MAX, "N does not fit the length type") };
+        let data = if heap_capacity > Self::STACK {
+            assert!(
+                heap_capacity <= max_len::<T>(),
+                "tinyvec: capacity overflow"
+            );
+            let heap = heap::with_capacity_in(heap_capacity, alloc.clone());
+            #[cfg(feature = "stats")]
+            crate::stats::allocated(heap_size::<T>(heap.capacity()));
+            data::heap(heap)
+        } else {
+            data::stack {
+                len: L::from_usize(0),
+                buf: [const { MaybeUninit::uninit() }; N],
+                reported: false,
+            }
+        };
+
+        Self {
+            data,
+            alloc,
+            #[cfg(feature = "spill_count")]
+            spills: 0,
+            #[cfg(feature = "high_water")]
+            high_water: 0,
+            growth: PhantomData,
+        }
+    }
+
+    /// Empty storage sharing this one's allocator.
+    pub(crate) fn empty_like(&self) -> Self {
+        Self::with_alloc(0, self.alloc.clone())
+    }
+
+    /// Returns (capacity_on_stack, capacity_on_heap).
+    /// Nothing is ever allocated for zero-sized types, so
+    /// both are unlimited for them.
+    pub(crate) fn capacities(&self) -> (usize, usize) {
+        match &self.data {
+            data::stack { .. } if size_of::<T>() == 0 => (Self::STACK, usize::MAX),
+            data::stack { .. } => (Self::STACK, 0),
+            data::heap(heap) => (Self::STACK, heap.capacity()),
+        }
+    }
+
+    /// Whether the elements currently live on the heap.
+    pub(crate) fn spilled(&self) -> bool {
+        matches!(self.data, data::heap(_))
+    }
+
+    /// How many times the elements moved from the stack to the heap,
+    /// saturating at `u32::MAX`.
+    #[cfg(feature = "spill_count")]
+    pub(crate) fn spills(&self) -> u32 {
+        self.spills
+    }
+
+    /// The largest `len()` this storage ever had.
+    #[cfg(feature = "high_water")]
+    pub(crate) fn high_water(&self) -> usize {
+        self.high_water
+    }
+
+    /// Make the heap hold at least `capacity` slots, moving the
+    /// elements there first if they are still on the stack.
+    pub(crate) fn grow_heap(&mut self, capacity: usize) {
+        if let Err(error) = self.try_grow_heap(capacity) {
+            panic!("tinyvec: {error}");
+        }
+    }
+
+    /// `grow_heap()`, but reporting allocation failure. Nothing
+    /// moves if it fails.
+    pub(crate) fn try_grow_heap(&mut self, capacity: usize) -> Result<(), try_reserve_error> {
+        if capacity > max_len::<T>() {
+            return Err(try_reserve_error::capacity_overflow);
+        }
+
+        let len = self.len();
+        match &mut self.data {
+            data::heap(heap) => {
+                if capacity <= heap.capacity() {
+                    return Ok(());
+                }
+                #[cfg(feature = "stats")]
+                let old_capacity = heap.capacity();
+                heap.try_reserve_exact(capacity - len)
+                    .map_err(|_| try_reserve_error::alloc_failed)?;
+                #[cfg(feature = "stats")]
+                crate::stats::reallocated(
+                    heap_size::<T>(old_capacity),
+                    heap_size::<T>(heap.capacity()),
+                );
+                #[cfg(feature = "log")]
+                log::debug!(
+                    target: "vecstor",
+                    "tinyvec<{}, {N}> reallocated, heap capacity {}",
+                    std::any::type_name::<T>(),
+                    heap.capacity()
+                );
+            }
+            data::stack { buf, reported, .. } => {
+                let first = !*reported;
+                let mut heap = heap::new_in(self.alloc.clone());
+                heap.try_reserve_exact(capacity.max(len))
+                    .map_err(|_| try_reserve_error::alloc_failed)?;
+                // Move the whole inline run over so the elements stay contiguous.
+                unsafe {
+                    let src = buf.as_ptr() as *const T;
+                    std::ptr::copy_nonoverlapping(src, heap.as_mut_ptr(), len);
+                    heap.set_len(len);
+                }
+                #[cfg(feature = "stats")]
+                crate::stats::spilled(heap_size::<T>(heap.capacity()));
+                #[cfg(feature = "log")]
+                log::debug!(
+                    target: "vecstor",
+                    "tinyvec<{}, {N}> spilled to the heap, heap capacity {}",
+                    std::any::type_name::<T>(),
+                    heap.capacity()
+                );
+                self.data = data::heap(heap);
+                self.spilled_once(len, first);
+            }
+        }
+        Ok(())
+    }
+
+    /// Bookkeeping after moving `len` elements to the heap: count the
+    /// spill, and report it if it is the `first`.
+    fn spilled_once(&mut self, len: usize, first: bool) {
+        #[cfg(feature = "spill_count")]
+        {
+            self.spills = self.spills.saturating_add(1);
+        }
+        if first {
+            run_spill_hook(len);
+        }
+    }
+
+    /// Whether the spill hook is done with this instance.
+    fn reported(&self) -> bool {
+        match self.data {
+            data::stack { reported, .. } => reported,
+            data::heap(_) => true,
+        }
+    }
+
+    /// The same elements in a spill with K inline slots. A heap
+    /// buffer that wouldn't fit in K is handed over as is; otherwise
+    /// the elements are copied to wherever they fit.
+    pub(crate) fn resize_inline<const K: usize>(self) -> spill<T, K, A, G, L> {
+        let len = self.len();
+        let this = std::mem::ManuallyDrop::new(self);
+        // `this` is never used or dropped again, so its fields are moved out.
+        let (data, alloc) = unsafe { (std::ptr::read(&this.data), std::ptr::read(&this.alloc)) };
+        let moves_heap = matches!(data, data::heap(_)) && len > spill::<T, K, A, G, L>::STACK;
+        let mut out = spill::<T, K, A, G, L>::with_alloc(if moves_heap { 0 } else { len }, alloc);
+        #[cfg(feature = "spill_count")]
+        {
+            out.spills = this.spills;
+        }
+        #[cfg(feature = "high_water")]
+        {
+            out.high_water = this.high_water;
+        }
+        match data {
+            data::heap(heap) if moves_heap => out.data = data::heap(heap),
+            data::heap(mut heap) => unsafe {
+                std::ptr::copy_nonoverlapping(heap.as_ptr(), out.as_mut_ptr(), len);
+                heap.set_len(0);
+                out.set_len(len);
+                if let data::stack { reported, .. } = &mut out.data {
+                    *reported = true;
+                }
+                #[cfg(feature = "stats")]
+                crate::stats::freed(heap_size::<T>(heap.capacity()));
+            },
+            data::stack { buf, reported, .. } => unsafe {
+                std::ptr::copy_nonoverlapping(buf.as_ptr() as *const T, out.as_mut_ptr(), len);
+                out.set_len(len);
+                if let data::stack { reported: to, .. } = &mut out.data {
+                    *to = reported;
+                }
+            },
+        }
+        out
+    }
+
+    /// Move the elements back to the stack and free the heap,
+    /// if they fit. Returns whether they are on the stack now.
+    pub(crate) fn unspill(&mut self) -> bool {
+        let data::heap(heap) = &mut self.data else {
+            return true;
+        };
+
+        let len = heap.len();
+        if len > Self::STACK {
+            return false;
+        }
+
+        let mut buf = [const { MaybeUninit::uninit() }; N];
+        unsafe {
+            std::ptr::copy_nonoverlapping(heap.as_ptr(), buf.as_mut_ptr() as *mut T, len);
+            heap.set_len(0);
+        }
+        #[cfg(feature = "stats")]
+        crate::stats::freed(heap_size::<T>(heap.capacity()));
+        self.data = data::stack {
+            len: L::from_usize(len),
+            buf,
+            reported: true,
+        };
+        true
+    }
+}
+
+impl<T, const N: usize, G: policy, L: index> spill<T, N, Global, G, L> {
+    /// Take over `vec`'s allocation if its elements don't fit inline,
+    /// otherwise move them onto the stack and free it.
+    pub(crate) fn from_vec(mut vec: Vec<T>) -> Self {
+        let len = vec.len();
+        if len > Self::STACK {
+            let mut out = Self::with_alloc(0, Global);
+            #[cfg(feature = "stats")]
+            crate::stats::allocated(heap_size::<T>(vec.capacity()));
+            out.data = data::heap(crate::allocator::heap_from_vec(vec));
+            #[cfg(feature = "high_water")]
+            {
+                out.high_water = len;
+            }
+            return out;
+        }
+
+        let mut out = Self::with_alloc(len, Global);
+        unsafe {
+            vec.set_len(0);
+            std::ptr::copy_nonoverlapping(vec.as_ptr(), out.as_mut_ptr(), len);
+            out.set_len(len);
+        }
+        out
+    }
+
+    /// Move the elements out as a `Vec`, handing over the heap
+    /// allocation if there is one, and leave this storage empty on the
+    /// stack with its counters kept, for `put_vec()`.
+    pub(crate) fn take_vec(&mut self) -> Vec<T> {
+        let len = self.len();
+        let empty = data::stack {
+            len: L::from_usize(0),
+            buf: [const { MaybeUninit::uninit() }; N],
+            reported: self.reported(),
+        };
+        match std::mem::replace(&mut self.data, empty) {
+            data::heap(heap) => crate::allocator::heap_into_vec(heap),
+            data::stack { buf, .. } => {
+                let mut vec = Vec::with_capacity(len);
+                unsafe {
+                    std::ptr::copy_nonoverlapping(buf.as_ptr() as *const T, vec.as_mut_ptr(), len);
+                    vec.set_len(len);
+                }
+                vec
+            }
+        }
+    }
+
+    /// Undo `take_vec()`. `heap_capacity` is what the heap held before
+    /// it, if it was spilled: then `vec` becomes the heap as is, even
+    /// if it would fit inline now. Otherwise it is moved back inline
+    /// if it fits, or adopted as a first spill.
+    pub(crate) fn put_vec(&mut self, mut vec: Vec<T>, heap_capacity: Option<usize>) {
+        debug_assert!(!self.spilled() && self.len() == 0);
+        let len = vec.len();
+        #[cfg(feature = "high_water")]
+        {
+            self.high_water = self.high_water.max(len);
+        }
+        if heap_capacity.is_none() && len <= Self::STACK {
+            unsafe {
+                vec.set_len(0);
+                std::ptr::copy_nonoverlapping(vec.as_ptr(), self.as_mut_ptr(), len);
+                self.set_len(len);
+            }
+            return;
+        }
+
+        #[cfg(feature = "stats")]
+        match heap_capacity {
+            Some(old) if old != vec.capacity() => {
+                crate::stats::reallocated(heap_size::<T>(old), heap_size::<T>(vec.capacity()))
+            }
+            Some(_) => {}
+            None => crate::stats::spilled(heap_size::<T>(vec.capacity())),
+        }
+        let first = !self.reported();
+        self.data = data::heap(crate::allocator::heap_from_vec(vec));
+        if heap_capacity.is_none() {
+            self.spilled_once(len, first);
+        }
+    }
+
+    /// The elements as a `Vec`, which takes over the heap allocation
+    /// if there is one.
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        let len = self.len();
+        let this = std::mem::ManuallyDrop::new(self);
+        // `this` is never used or dropped again; `alloc` is `Global`.
+        match unsafe { std::ptr::read(&this.data) } {
+            data::heap(heap) => {
+                #[cfg(feature = "stats")]
+                crate::stats::freed(heap_size::<T>(heap.capacity()));
+                crate::allocator::heap_into_vec(heap)
+            }
+            data::stack { buf, .. } => {
+                let mut vec = Vec::with_capacity(len);
+                unsafe {
+                    std::ptr::copy_nonoverlapping(buf.as_ptr() as *const T, vec.as_mut_ptr(), len);
+                    vec.set_len(len);
+                }
+                vec
+            }
+        }
+    }
+}
+
+unsafe impl<T, const N: usize, A: Allocator + Clone, G: policy, L: index> storage<T>
+    for spill<T, N, A, G, L>
+{
+    fn len(&self) -> usize {
+        match &self.data {
+            data::stack { len, .. } => len.to_usize(),
+            data::heap(heap) => heap.len(),
+        }
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity(), "set_len past capacity");
+        #[cfg(feature = "high_water")]
+        {
+            self.high_water = self.high_water.max(new_len);
+        }
+        match &mut self.data {
+            data::stack { len, .. } => *len = L::from_usize(new_len),
+            data::heap(heap) => unsafe { heap.set_len(new_len) },
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match &self.data {
+            data::stack { .. } => Self::STACK,
+            data::heap(heap) => heap.capacity(),
+        }
+    }
+
+    fn as_ptr(&self) -> *const T {
+        match &self.data {
+            data::stack { buf, .. } => buf.as_ptr() as *const T,
+            data::heap(heap) => heap.as_ptr(),
+        }
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        match &mut self.data {
+            data::stack { buf, .. } => buf.as_mut_ptr() as *mut T,
+            data::heap(heap) => heap.as_mut_ptr(),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) -> bool {
+        let len = self.len();
+        let Some(needed) = len.checked_add(additional) else {
+            return false;
+        };
+        if needed <= self.capacity() {
+            return true;
+        }
+
+        let Some(target) = grow_target::<T, G>(self.capacity(), needed) else {
+            return false;
+        };
+        self.grow_heap(target);
+        true
+    }
+
+    fn try_reserve(&mut self, additional: usize) -> Result<(), try_reserve_error> {
+        let needed = self
+            .len()
+            .checked_add(additional)
+            .ok_or(try_reserve_error::capacity_overflow)?;
+        if needed <= self.capacity() {
+            return Ok(());
+        }
+
+        let target = grow_target::<T, G>(self.capacity(), needed)
+            .ok_or(try_reserve_error::capacity_overflow)?;
+        self.try_grow_heap(target)
+    }
+
+    /// Shrinks the heap as far as `G::shrink()` says, but never below
+    /// the inline buffer: if the elements fit inline again they move
+    /// back there instead.
+    fn after_remove(&mut self) {
+        let data::heap(heap) = &mut self.data else {
+            return;
+        };
+        let (len, capacity) = (heap.len(), heap.capacity());
+        let Some(target) = G::shrink(capacity, len) else {
+            return;
+        };
+        if len <= Self::STACK {
+            self.unspill();
+            return;
+        }
+        let target = target.max(len).max(Self::STACK + 1);
+        if target < capacity {
+            heap.shrink_to(target);
+            #[cfg(feature = "stats")]
+            crate::stats::reallocated(heap_size::<T>(capacity), heap_size::<T>(heap.capacity()));
+        }
+    }
+}
+
+/// Elements belong to whoever drives the storage, only
+/// the heap allocation is freed here.
+impl<T, const N: usize, A: Allocator + Clone, G, L> Drop for spill<T, N, A, G, L> {
+    fn drop(&mut self) {
+        if let data::heap(heap) = &mut self.data {
+            unsafe { heap.set_len(0) };
+            #[cfg(feature = "stats")]
+            crate::stats::freed(heap_size::<T>(heap.capacity()));
+        }
+    }
+}
+
+/// Occupancy only, e.g. `len=5 inline=0/4 heap=5/8`. Never looks at
+/// the slots, so it needs no `T: Debug`.
+#[cfg(feature = "fmt")]
+impl<T, const N: usize, A: Allocator + Clone, G: policy, L: index> fmt::Debug
+    for spill<T, N, A, G, L>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.len();
+        let (stack, heap) = match &self.data {
+            data::stack { .. } => (len, 0),
+            data::heap(_) => (0, len),
+        };
+        let heap_capacity = if self.spilled() { self.capacity() } else { 0 };
+        write!(
+            f,
+            "len={len} inline={stack}/{N} heap={heap}/{heap_capacity}"
+        )
+    }
+}
+
+/// Inline array of exactly N slots, no heap at all.
+///
+/// `X` is one of the [`align`](crate::align) markers and sets a
+/// minimum alignment for the buffer.
+#[repr(C)]
+pub struct inline<T, const N: usize, L = usize, X = natural> {
+    align: [X; 0],
+    buf: [MaybeUninit<T>; N],
+    len: L,
+}
+
+impl<T, const N: usize, L: index, X> inline<T, N, L, X> {
+    pub(crate) fn new() -> Self {
+        const { assert!(N <= L::MAX, "N does not fit the length type") };
+        Self {
+            align: [],
+            buf: [const { MaybeUninit::uninit() }; N],
+            len: L::from_usize(0),
+        }
+    }
+}
+
+impl<T, const N: usize, X> inline<T, N, usize, X> {
+    /// `new()` for const contexts, which can't go through `index`.
+    pub(crate) const fn new_const() -> Self {
+        Self {
+            align: [],
+            buf: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Append `element`, panicking if all N slots are taken.
+    pub(crate) const fn push_const(&mut self, element: T) {
+        assert!(self.len < N, "push: storage is full");
+        self.buf[self.len] = MaybeUninit::new(element);
+        self.len += 1;
+    }
+}
+
+unsafe impl<T, const N: usize, L: index, X> storage<T> for inline<T, N, L, X> {
+    fn len(&self) -> usize {
+        self.len.to_usize()
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= N, "set_len past capacity");
+        self.len = L::from_usize(len);
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr() as *const T
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_mut_ptr() as *mut T
+    }
+
+    fn reserve(&mut self, additional: usize) -> bool {
+        additional <= N - self.len()
+    }
+}
+
+/// Occupancy only, e.g. `len=3 inline=3/4`.
+#[cfg(feature = "fmt")]
+impl<T, const N: usize, L: index, X> fmt::Debug for inline<T, N, L, X> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "len={} inline={}/{N}", self.len(), self.len())
+    }
+}
+
+/// [`spill`] for big N: the N first slots are one boxed array,
+/// allocated on first use, instead of living in the struct. Keeps the
+/// vector a few words large however big N is, at the price of one
+/// allocation; spills to a growing heap buffer past N like `spill`.
+pub struct boxed<T, const N: usize, G = doubling> {
+    data: boxed_data<T, N>,
+    growth: PhantomData<fn() -> G>,
+}
+
+enum boxed_data<T, const N: usize> {
+    /// Nothing allocated yet.
+    empty,
+    slab {
+        len: usize,
+        buf: Box<[MaybeUninit<T>; N]>,
+    },
+    heap(Vec<T>),
+}
+
+impl<T, const N: usize, G: policy> boxed<T, N, G> {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: boxed_data::empty,
+            growth: PhantomData,
+        }
+    }
+
+    /// Whether the elements moved past the slab to the growing heap.
+    pub(crate) fn spilled(&self) -> bool {
+        matches!(self.data, boxed_data::heap(_))
+    }
+
+    /// Whether the slab has been allocated (and not spilled from).
+    pub(crate) fn slab_allocated(&self) -> bool {
+        matches!(self.data, boxed_data::slab { .. })
+    }
+}
+
+unsafe impl<T, const N: usize, G: policy> storage<T> for boxed<T, N, G> {
+    fn len(&self) -> usize {
+        match &self.data {
+            boxed_data::empty => 0,
+            boxed_data::slab { len, .. } => *len,
+            boxed_data::heap(heap) => heap.len(),
+        }
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity(), "set_len past capacity");
+        match &mut self.data {
+            boxed_data::empty => {}
+            boxed_data::slab { len, .. } => *len = new_len,
+            boxed_data::heap(heap) => unsafe { heap.set_len(new_len) },
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match &self.data {
+            boxed_data::empty => 0,
+            boxed_data::slab { .. } => N,
+            boxed_data::heap(heap) => heap.capacity(),
+        }
+    }
+
+    fn as_ptr(&self) -> *const T {
+        match &self.data {
+            boxed_data::empty => std::ptr::NonNull::dangling().as_ptr(),
+            boxed_data::slab { buf, .. } => buf.as_ptr() as *const T,
+            boxed_data::heap(heap) => heap.as_ptr(),
+        }
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        match &mut self.data {
+            boxed_data::empty => std::ptr::NonNull::dangling().as_ptr(),
+            boxed_data::slab { buf, .. } => buf.as_mut_ptr() as *mut T,
+            boxed_data::heap(heap) => heap.as_mut_ptr(),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) -> bool {
+        let len = self.len();
+        let Some(needed) = len.checked_add(additional) else {
+            return false;
+        };
+        if needed <= self.capacity() {
+            return true;
+        }
+
+        if needed <= N {
+            // Only reachable while empty: the slab holds N.
+            let buf = unsafe { Box::<[MaybeUninit<T>; N]>::new_uninit().assume_init() };
+            self.data = boxed_data::slab { len: 0, buf };
+            return true;
+        }
+
+        let Some(target) = grow_target::<T, G>(self.capacity().max(N), needed) else {
+            return false;
+        };
+        match &mut self.data {
+            boxed_data::heap(heap) => heap.reserve_exact(target - len),
+            data => {
+                let mut heap = Vec::with_capacity(target);
+                unsafe {
+                    if let boxed_data::slab { buf, .. } = data {
+                        std::ptr::copy_nonoverlapping(
+                            buf.as_ptr() as *const T,
+                            heap.as_mut_ptr(),
+                            len,
+                        );
+                    }
+                    heap.set_len(len);
+                }
+                *data = boxed_data::heap(heap);
+            }
+        }
+        true
+    }
+
+    fn after_remove(&mut self) {
+        let boxed_data::heap(heap) = &mut self.data else {
+            return;
+        };
+        let (len, capacity) = (heap.len(), heap.capacity());
+        if let Some(target) = G::shrink(capacity, len) {
+            heap.shrink_to(target.max(len).max(N));
+        }
+    }
+}
+
+/// Elements belong to whoever drives the storage.
+impl<T, const N: usize, G> Drop for boxed<T, N, G> {
+    fn drop(&mut self) {
+        if let boxed_data::heap(heap) = &mut self.data {
+            unsafe { heap.set_len(0) };
+        }
+    }
+}
+
+/// Occupancy only, e.g. `len=3 boxed=3/4096 heap=0/0`.
+#[cfg(feature = "fmt")]
+impl<T, const N: usize, G: policy> fmt::Debug for boxed<T, N, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.len();
+        let (slab, heap, heap_capacity) = match &self.data {
+            boxed_data::heap(heap) => (0, len, heap.capacity()),
+            _ => (len, 0, 0),
+        };
+        write!(f, "len={len} boxed={slab}/{N} heap={heap}/{heap_capacity}")
+    }
+}
+
+/// Caller-provided buffer, e.g. an FFI or arena slab.
+/// Can't grow past the length of the buffer.
+pub struct borrowed<'a, T> {
+    buf: &'a mut [MaybeUninit<T>],
+    len: usize,
+}
+
+impl<'a, T> borrowed<'a, T> {
+    pub(crate) fn new(buf: &'a mut [MaybeUninit<T>]) -> Self {
+        Self { buf, len: 0 }
+    }
+}
+
+unsafe impl<T> storage<T> for borrowed<'_, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= self.buf.len(), "set_len past capacity");
+        self.len = len;
+    }
+
+    fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr() as *const T
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_mut_ptr() as *mut T
+    }
+
+    fn reserve(&mut self, additional: usize) -> bool {
+        additional <= self.buf.len() - self.len
+    }
+}
+
+/// Occupancy only, e.g. `len=3 borrowed=3/16`.
+#[cfg(feature = "fmt")]
+impl<T> fmt::Debug for borrowed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "len={} borrowed={}/{}",
+            self.len,
+            self.len,
+            self.buf.len()
+        )
+    }
+}
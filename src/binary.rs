@@ -0,0 +1,61 @@
+//! speedy support: a tinyvec reads and writes as a `Vec`, a
+//! tinystring as a `String`, a `u32` length and then the contents,
+//! so the types can be swapped without changing the format.
+
+use speedy::{Context, Readable, Reader, Writable, Writer};
+
+use crate::allocator::Global;
+use crate::{growth, index, tinystring, tinyvec};
+
+impl<C: Context, T: Writable<C>, const N: usize, G: growth::policy, L: index> Writable<C>
+    for tinyvec<T, N, Global, G, L>
+{
+    fn write_to<W: ?Sized + Writer<C>>(&self, writer: &mut W) -> Result<(), C::Error> {
+        self.as_slice().write_to(writer)
+    }
+
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(self.as_slice())
+    }
+}
+
+/// Short vectors are read straight into the inline slots. The
+/// length prefix isn't trusted for a reservation, so a corrupt one
+/// fails at the end of the input rather than allocating.
+impl<'a, C: Context, T: Readable<'a, C>, const N: usize, G: growth::policy, L: index>
+    Readable<'a, C> for tinyvec<T, N, Global, G, L>
+{
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let len = reader.read_u32()? as usize;
+        let mut vec = Self::new();
+        for _ in 0..len {
+            vec.push(reader.read_value()?);
+        }
+        Ok(vec)
+    }
+
+    fn minimum_bytes_needed() -> usize {
+        4
+    }
+}
+
+impl<C: Context, const N: usize> Writable<C> for tinystring<N> {
+    fn write_to<W: ?Sized + Writer<C>>(&self, writer: &mut W) -> Result<(), C::Error> {
+        self.as_str().write_to(writer)
+    }
+
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(self.as_str())
+    }
+}
+
+impl<'a, C: Context, const N: usize> Readable<'a, C> for tinystring<N> {
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let bytes: tinyvec<u8, N> = reader.read_value()?;
+        tinystring::from_utf8(bytes).map_err(|error| speedy::Error::custom(error).into())
+    }
+
+    fn minimum_bytes_needed() -> usize {
+        4
+    }
+}
@@ -0,0 +1,142 @@
+//! Fixed-size bit set, see [`tinybitset`].
+
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+/// BITS bits kept inline in `u64` words.
+///
+/// Stable Rust can't size an array from `BITS / 64` yet, so the word
+/// count is its own parameter: the default of 1 covers up to 64 bits,
+/// bigger sets spell it out, e.g. `tinybitset<200, 4>`. A `WORDS`
+/// too small for `BITS` fails to compile.
+///
+/// ```rust
+/// use vecstor::tinybitset;
+/// let mut dirty: tinybitset<40> = tinybitset::new();
+/// dirty.set(3);
+/// dirty.set(39);
+/// assert!(dirty.test(39));
+/// assert_eq!(dirty.iter().collect::<Vec<_>>(), [3, 39]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct tinybitset<const BITS: usize, const WORDS: usize = 1> {
+    words: [u64; WORDS],
+}
+
+impl<const BITS: usize, const WORDS: usize> tinybitset<BITS, WORDS> {
+    /// All bits clear.
+    pub const fn new() -> Self {
+        const { assert!(BITS <= WORDS * 64, "WORDS is too small for BITS") };
+        Self { words: [0; WORDS] }
+    }
+
+    /// Number of bits, BITS.
+    pub const fn len(&self) -> usize {
+        BITS
+    }
+
+    fn locate(bit: usize) -> (usize, u64) {
+        assert!(bit < BITS, "bit {bit} out of range for {BITS} bits");
+        (bit / 64, 1 << (bit % 64))
+    }
+
+    /// Panics if `bit >= BITS`, as do `clear` and `test`.
+    pub fn set(&mut self, bit: usize) {
+        let (word, mask) = Self::locate(bit);
+        self.words[word] |= mask;
+    }
+
+    pub fn clear(&mut self, bit: usize) {
+        let (word, mask) = Self::locate(bit);
+        self.words[word] &= !mask;
+    }
+
+    pub fn test(&self, bit: usize) -> bool {
+        let (word, mask) = Self::locate(bit);
+        self.words[word] & mask != 0
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Whether no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Clear every bit.
+    pub fn reset(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    /// Indices of the set bits, ascending.
+    pub fn iter(&self) -> ones<'_, WORDS> {
+        ones {
+            words: &self.words,
+            word: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> Default for tinybitset<BITS, WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the set bits of a [`tinybitset`].
+pub struct ones<'a, const WORDS: usize> {
+    words: &'a [u64; WORDS],
+    word: usize,
+    /// Bits of `words[word]` not yielded yet.
+    current: u64,
+}
+
+impl<const WORDS: usize> Iterator for ones<'_, WORDS> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word += 1;
+            self.current = *self.words.get(self.word)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.word * 64 + bit)
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $sym:tt) => {
+        impl<const BITS: usize, const WORDS: usize> $assign for tinybitset<BITS, WORDS> {
+            fn $assign_method(&mut self, rhs: Self) {
+                for (word, other) in self.words.iter_mut().zip(rhs.words) {
+                    *word = *word $sym other;
+                }
+            }
+        }
+
+        impl<const BITS: usize, const WORDS: usize> $op for tinybitset<BITS, WORDS> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+/// The set bits, e.g. `{3, 39}`.
+#[cfg(feature = "fmt")]
+impl<const BITS: usize, const WORDS: usize> fmt::Debug for tinybitset<BITS, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,100 @@
+//! bytes support: byte vectors as a `Buf` that reads from the front,
+//! and tinyvec and stackvec as a `BufMut` that writes into the spare
+//! capacity, growing a tinyvec as it goes like `Vec<u8>`.
+
+use bytes::buf::UninitSlice;
+use bytes::{Buf, BufMut};
+
+use crate::allocator::Allocator;
+use crate::{growth, index, stackvec, storage, storagevec, tinyvec};
+
+/// `advance()` shifts the rest down, so it is linear in what is left:
+/// meant for frames of a few dozen bytes, not as a stream buffer.
+impl<S: storage<u8>> Buf for storagevec<u8, S> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    /// Panics if `cnt > remaining()`.
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.len(),
+            "advance: {cnt} past the end (len {})",
+            self.len()
+        );
+        self.drain(..cnt);
+    }
+}
+
+impl<const N: usize> Buf for stackvec<u8, N> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        Buf::advance(&mut **self, cnt);
+    }
+}
+
+/// Spare slots after the elements, `len()..total_capacity()`.
+fn spare<S: storage<u8>>(vec: &mut storagevec<u8, S>) -> &mut UninitSlice {
+    let (len, capacity) = (vec.len(), vec.storage.capacity());
+    unsafe { UninitSlice::from_raw_parts_mut(vec.storage.as_mut_ptr().add(len), capacity - len) }
+}
+
+/// Panics if fewer than `cnt` slots are spare.
+unsafe fn commit<S: storage<u8>>(vec: &mut storagevec<u8, S>, cnt: usize) {
+    let spare = vec.remaining_capacity();
+    assert!(
+        cnt <= spare,
+        "advance_mut: {cnt} past the capacity ({spare} spare)"
+    );
+    unsafe { vec.storage.set_len(vec.len() + cnt) };
+}
+
+unsafe impl<const N: usize, A: Allocator + Clone, G: growth::policy, L: index> BufMut
+    for tinyvec<u8, N, A, G, L>
+{
+    fn remaining_mut(&self) -> usize {
+        isize::MAX as usize - self.len()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        unsafe { commit(self, cnt) }
+    }
+
+    /// Grows by 64 bytes or more when full.
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        if self.remaining_capacity() == 0 && !self.storage.reserve(64) {
+            panic!("chunk_mut: storage is full");
+        }
+        spare(self)
+    }
+
+    fn put_slice(&mut self, src: &[u8]) {
+        self.extend_from_slice(src);
+    }
+}
+
+/// Never allocates: `put*()` past N bytes panics, as for `&mut [u8]`.
+unsafe impl<const N: usize> BufMut for stackvec<u8, N> {
+    fn remaining_mut(&self) -> usize {
+        self.remaining_capacity()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        unsafe { commit(self, cnt) }
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        spare(self)
+    }
+}
@@ -0,0 +1,180 @@
+//! Cheaply sliceable byte buffer, see [`tinybytes`].
+
+use std::fmt;
+use std::ops::{Deref, Range, RangeBounds};
+use std::sync::Arc;
+
+#[derive(Clone)]
+enum repr<const N: usize> {
+    inline {
+        data: [u8; N],
+        len: usize,
+    },
+    /// `buf[start..end]`, shared by every slice taken from it.
+    shared {
+        buf: Arc<[u8]>,
+        start: usize,
+        end: usize,
+    },
+}
+
+/// Immutable bytes: up to N are stored inline and copied around, more
+/// than that live in one reference-counted allocation that
+/// [`slice`](tinybytes::slice), [`split_to`](tinybytes::split_to),
+/// [`split_off`](tinybytes::split_off) and `clone` share without
+/// copying. Pieces short enough to fit inline are copied out instead,
+/// so they don't pin the allocation.
+///
+/// ```rust
+/// use vecstor::tinybytes;
+/// let mut frame = tinybytes::<16>::from(&b"HEAD:a somewhat longer payload"[..]);
+/// let head = frame.split_to(5);
+/// assert_eq!(&*head, b"HEAD:");
+/// assert!(head.is_inline());
+/// assert_eq!(&*frame.slice(..8), b"a somewh");
+/// assert!(!frame.is_inline());
+/// ```
+#[derive(Clone)]
+pub struct tinybytes<const N: usize> {
+    repr: repr<N>,
+}
+
+impl<const N: usize> tinybytes<N> {
+    pub const fn new() -> Self {
+        Self {
+            repr: repr::inline {
+                data: [0; N],
+                len: 0,
+            },
+        }
+    }
+
+    /// Whether the bytes are stored inline rather than shared.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.repr, repr::inline { .. })
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.repr {
+            repr::inline { data, len } => &data[..*len],
+            repr::shared { buf, start, end } => &buf[*start..*end],
+        }
+    }
+
+    fn copied(bytes: &[u8]) -> Self {
+        let mut data = [0; N];
+        data[..bytes.len()].copy_from_slice(bytes);
+        Self {
+            repr: repr::inline {
+                data,
+                len: bytes.len(),
+            },
+        }
+    }
+
+    /// The bytes in `range`: copied if they fit inline, otherwise
+    /// sharing the allocation. Panics if `range` is out of bounds.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let Range { start, end } = crate::bounds(&range, self.len(), "slice");
+        match &self.repr {
+            repr::shared {
+                buf, start: base, ..
+            } if end - start > N => Self {
+                repr: repr::shared {
+                    buf: buf.clone(),
+                    start: base + start,
+                    end: base + end,
+                },
+            },
+            _ => Self::copied(&self.as_slice()[start..end]),
+        }
+    }
+
+    /// Split off and return `[0, at)`, leaving `[at, len)`.
+    pub fn split_to(&mut self, at: usize) -> Self {
+        let front = self.slice(..at);
+        *self = self.slice(at..);
+        front
+    }
+
+    /// Split off and return `[at, len)`, leaving `[0, at)`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let back = self.slice(at..);
+        *self = self.slice(..at);
+        back
+    }
+}
+
+impl<const N: usize> Deref for tinybytes<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> Default for tinybytes<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<&[u8]> for tinybytes<N> {
+    fn from(bytes: &[u8]) -> Self {
+        if bytes.len() <= N {
+            Self::copied(bytes)
+        } else {
+            Self::from(Arc::<[u8]>::from(bytes))
+        }
+    }
+}
+
+/// Copies into one shared allocation when it doesn't fit inline;
+/// the Vec's own buffer is freed.
+impl<const N: usize> From<Vec<u8>> for tinybytes<N> {
+    fn from(bytes: Vec<u8>) -> Self {
+        if bytes.len() <= N {
+            Self::copied(&bytes)
+        } else {
+            Self::from(Arc::<[u8]>::from(bytes))
+        }
+    }
+}
+
+/// Shared even if short, since the allocation already exists.
+impl<const N: usize> From<Arc<[u8]>> for tinybytes<N> {
+    fn from(buf: Arc<[u8]>) -> Self {
+        let end = buf.len();
+        Self {
+            repr: repr::shared { buf, start: 0, end },
+        }
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<tinybytes<M>> for tinybytes<N> {
+    fn eq(&self, other: &tinybytes<M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for tinybytes<N> {}
+
+impl<const N: usize> PartialEq<[u8]> for tinybytes<N> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> std::hash::Hash for tinybytes<N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+/// Byte string form, e.g. `b"GET \xff"`.
+#[cfg(feature = "fmt")]
+impl<const N: usize> fmt::Debug for tinybytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"{}\"", self.as_slice().escape_ascii())
+    }
+}
@@ -0,0 +1,312 @@
+//! Bounded queues on inline storage: single-producer single-consumer
+//! [`spsc`], and [`mpmc`] for any number of threads on each side.
+
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::capacity_error;
+
+/// Bounded queue of N inline slots for exactly one sending and one
+/// receiving thread. Nothing is allocated and nothing blocks, so it
+/// can be used from audio callbacks and interrupt handlers.
+///
+/// [`split`](spsc::split) borrows it as a [`sender`] and a
+/// [`receiver`]; having only one of each is what makes the plain
+/// atomic head and tail enough.
+///
+/// ```rust
+/// use vecstor::spsc;
+/// let mut queue: spsc<u32, 4> = spsc::new();
+/// let (mut tx, mut rx) = queue.split();
+/// std::thread::scope(|s| {
+///     s.spawn(move || {
+///         for sample in 0..100 {
+///             let mut pending = sample;
+///             while let Err(full) = tx.try_send(pending) {
+///                 pending = full.element();
+///             }
+///         }
+///     });
+///     let mut received = 0;
+///     while received < 100 {
+///         if let Some(sample) = rx.try_recv() {
+///             assert_eq!(sample, received);
+///             received += 1;
+///         }
+///     }
+/// });
+/// ```
+pub struct spsc<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    /// Total elements received; only the receiver stores it.
+    head: AtomicUsize,
+    /// Total elements sent; only the sender stores it.
+    tail: AtomicUsize,
+}
+
+// The sender only writes slots between tail and head + N, the receiver
+// only reads slots between head and tail, and each publishes its side
+// with a release store.
+unsafe impl<T: Send, const N: usize> Sync for spsc<T, N> {}
+
+impl<T, const N: usize> spsc<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Elements in flight; only a snapshot while both ends are active.
+    pub fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The two ends of the queue.
+    pub fn split(&mut self) -> (sender<'_, T, N>, receiver<'_, T, N>) {
+        (sender { queue: self }, receiver { queue: self })
+    }
+
+    fn slot(&self, count: usize) -> *mut MaybeUninit<T> {
+        self.slots[count % N].get()
+    }
+}
+
+impl<T, const N: usize> Drop for spsc<T, N> {
+    fn drop(&mut self) {
+        let (_, mut rx) = self.split();
+        while rx.try_recv().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for spsc<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T, const N: usize> fmt::Debug for spsc<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "spsc {{ len: {}, capacity: {N} }}", self.len())
+    }
+}
+
+/// Sending end of an [`spsc`].
+pub struct sender<'a, T, const N: usize> {
+    queue: &'a spsc<T, N>,
+}
+
+impl<T, const N: usize> sender<'_, T, N> {
+    /// Queue `element`, or hand it back if all N slots are full.
+    pub fn try_send(&mut self, element: T) -> Result<(), capacity_error<T>> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == N {
+            return Err(capacity_error::new(element));
+        }
+        unsafe { (*self.queue.slot(tail)).write(element) };
+        self.queue
+            .tail
+            .store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Receiving end of an [`spsc`].
+pub struct receiver<'a, T, const N: usize> {
+    queue: &'a spsc<T, N>,
+}
+
+impl<T, const N: usize> receiver<'_, T, N> {
+    /// Oldest queued element, if any.
+    pub fn try_recv(&mut self) -> Option<T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        let element = unsafe { (*self.queue.slot(head)).assume_init_read() };
+        self.queue
+            .head
+            .store(head.wrapping_add(1), Ordering::Release);
+        Some(element)
+    }
+}
+
+/// A slot of an [`mpmc`] and the lap it is ready for.
+struct mpmc_slot<T> {
+    /// `pos` when free for the send of ticket `pos`, `pos + 1` once
+    /// that value is in and ready for the matching receive.
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Bounded queue of N inline slots that any number of threads can
+/// send to and receive from through a shared reference. No heap, no
+/// locks; a full or empty queue is reported instead of waited on.
+///
+/// Each slot carries a sequence number, so a sender or receiver
+/// claims a ticket with one compare-exchange and then owns the slot
+/// until it bumps the sequence (Vyukov's bounded queue).
+///
+/// ```rust
+/// use vecstor::mpmc;
+/// let jobs: mpmc<u64, 8> = mpmc::new();
+/// let done = std::sync::atomic::AtomicU64::new(0);
+/// std::thread::scope(|s| {
+///     for worker in 0..3 {
+///         let (jobs, done) = (&jobs, &done);
+///         s.spawn(move || {
+///             for job in 0..100 {
+///                 let mut pending = worker * 100 + job;
+///                 while let Err(full) = jobs.try_send(pending) {
+///                     pending = full.element();
+///                     if let Some(job) = jobs.try_recv() {
+///                         done.fetch_add(job, std::sync::atomic::Ordering::Relaxed);
+///                     }
+///                 }
+///             }
+///         });
+///     }
+/// });
+/// while let Some(job) = jobs.try_recv() {
+///     done.fetch_add(job, std::sync::atomic::Ordering::Relaxed);
+/// }
+/// assert_eq!(done.into_inner(), (0..300).sum::<u64>());
+/// ```
+pub struct mpmc<T, const N: usize> {
+    slots: [mpmc_slot<T>; N],
+    /// Ticket of the next send.
+    tail: AtomicUsize,
+    /// Ticket of the next receive.
+    head: AtomicUsize,
+}
+
+// A slot's value is only touched by the thread whose ticket matches
+// its sequence, and handed over with a release store of the sequence.
+unsafe impl<T: Send, const N: usize> Sync for mpmc<T, N> {}
+
+impl<T, const N: usize> mpmc<T, N> {
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "mpmc needs at least one slot") };
+        let mut slots = [const {
+            mpmc_slot {
+                sequence: AtomicUsize::new(0),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }; N];
+        let mut at = 0;
+        while at < N {
+            slots[at].sequence = AtomicUsize::new(at);
+            at += 1;
+        }
+        Self {
+            slots,
+            tail: AtomicUsize::new(0),
+            head: AtomicUsize::new(0),
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Elements in flight; only a snapshot while others use it.
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(head).min(N)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queue `element`, or hand it back if all N slots are full.
+    pub fn try_send(&self, element: T) -> Result<(), capacity_error<T>> {
+        let mut ticket = self.tail.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[ticket % N];
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            match (sequence.wrapping_sub(ticket) as isize).signum() {
+                0 => match self.tail.compare_exchange_weak(
+                    ticket,
+                    ticket.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { (*slot.value.get()).write(element) };
+                        slot.sequence
+                            .store(ticket.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => ticket = current,
+                },
+                // Still holds the value from a lap ago.
+                -1 => return Err(capacity_error::new(element)),
+                _ => ticket = self.tail.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Oldest queued element, if any.
+    pub fn try_recv(&self) -> Option<T> {
+        let mut ticket = self.head.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[ticket % N];
+            let sequence = slot.sequence.load(Ordering::Acquire);
+            match (sequence.wrapping_sub(ticket.wrapping_add(1)) as isize).signum() {
+                0 => match self.head.compare_exchange_weak(
+                    ticket,
+                    ticket.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let element = unsafe { (*slot.value.get()).assume_init_read() };
+                        slot.sequence
+                            .store(ticket.wrapping_add(N), Ordering::Release);
+                        return Some(element);
+                    }
+                    Err(current) => ticket = current,
+                },
+                // Not sent yet.
+                -1 => return None,
+                _ => ticket = self.head.load(Ordering::Relaxed),
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for mpmc<T, N> {
+    fn drop(&mut self) {
+        while self.try_recv().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for mpmc<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T, const N: usize> fmt::Debug for mpmc<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mpmc {{ len: {}, capacity: {N} }}", self.len())
+    }
+}
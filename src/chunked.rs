@@ -0,0 +1,149 @@
+//! Segmented vector with stable element addresses, see [`chunkedvec`].
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+use crate::tinyvec;
+
+/// Vector that grows by adding boxed chunks of C elements instead of
+/// reallocating, so an element never moves once pushed: pointers into
+/// it stay valid until the element is popped or the vector dropped.
+/// The chunk pointers themselves sit in a [`tinyvec`] with room for N
+/// on the stack.
+///
+/// ```rust
+/// use vecstor::chunkedvec;
+/// let mut nodes: chunkedvec<u64, 4, 2> = chunkedvec::new();
+/// let first: *const u64 = nodes.push(7);
+/// for n in 0..100 {
+///     nodes.push(n);
+/// }
+/// assert_eq!(unsafe { *first }, 7);
+/// assert_eq!(nodes.get(100), Some(&99));
+/// ```
+pub struct chunkedvec<T, const C: usize, const N: usize> {
+    /// Boxed `[MaybeUninit<T>; C]`s, kept as raw pointers so that
+    /// moving them around doesn't invalidate outstanding pointers into
+    /// the chunks. All but the last are full.
+    chunks: tinyvec<NonNull<T>, N>,
+    len: usize,
+}
+
+// Owns its chunks like a `Vec<Box<[T; C]>>` would.
+unsafe impl<T: Send, const C: usize, const N: usize> Send for chunkedvec<T, C, N> {}
+unsafe impl<T: Sync, const C: usize, const N: usize> Sync for chunkedvec<T, C, N> {}
+
+impl<T, const C: usize, const N: usize> chunkedvec<T, C, N> {
+    pub fn new() -> Self {
+        const { assert!(C > 0, "chunks must hold at least one element") };
+        Self {
+            chunks: tinyvec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Chunks allocated so far.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Slot `at`, which must be below `chunk_count() * C`.
+    fn slot(&self, at: usize) -> *mut T {
+        unsafe { self.chunks.as_slice()[at / C].as_ptr().add(at % C) }
+    }
+
+    /// Append `element`, allocating a new chunk if the last is full,
+    /// and return it in place.
+    pub fn push(&mut self, element: T) -> &mut T {
+        if self.len == self.chunks.len() * C {
+            let chunk = Box::new([const { MaybeUninit::<T>::uninit() }; C]);
+            self.chunks.push(NonNull::from(Box::leak(chunk)).cast());
+        }
+        let slot = self.slot(self.len);
+        self.len += 1;
+        unsafe {
+            slot.write(element);
+            &mut *slot
+        }
+    }
+
+    /// Remove the last element, freeing its chunk if it empties.
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        let element = unsafe { self.slot(self.len).read() };
+        if self.len.is_multiple_of(C) {
+            let chunk = self.chunks.pop().unwrap();
+            drop(unsafe { Box::from_raw(chunk.cast::<[MaybeUninit<T>; C]>().as_ptr()) });
+        }
+        Some(element)
+    }
+
+    pub fn get(&self, at: usize) -> Option<&T> {
+        (at < self.len).then(|| unsafe { &*self.slot(at) })
+    }
+
+    pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        (at < self.len).then(|| unsafe { &mut *self.slot(at) })
+    }
+
+    /// The elements chunk by chunk, each a contiguous slice.
+    pub fn chunks(&self) -> impl Iterator<Item = &[T]> {
+        let len = self.len;
+        self.chunks.iter().enumerate().map(move |(i, chunk)| {
+            let filled = (len - i * C).min(C);
+            unsafe { std::slice::from_raw_parts(chunk.as_ptr(), filled) }
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.chunks().flatten()
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T, const C: usize, const N: usize> Drop for chunkedvec<T, C, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const C: usize, const N: usize> Default for chunkedvec<T, C, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize, const N: usize> Extend<T> for chunkedvec<T, C, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, const C: usize, const N: usize> FromIterator<T> for chunkedvec<T, C, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const C: usize, const N: usize> fmt::Debug for chunkedvec<T, C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
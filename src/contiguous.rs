@@ -0,0 +1,156 @@
+//! One trait over every growable contiguous container, see [`veclike`].
+
+use crate::error::capacity_error;
+use crate::{stackvec, storage, storagevec};
+
+/// What [`tinyvec`](crate::tinyvec), the other
+/// [`storagevec`]s, [`stackvec`] and `Vec` have in common, so code can
+/// be generic over the container and leave the storage to the caller.
+///
+/// ```rust
+/// use vecstor::{stackvec, tinyvec, veclike};
+/// fn digits(mut n: u32, out: &mut impl veclike<u8>) {
+///     out.clear();
+///     loop {
+///         out.push(b'0' + (n % 10) as u8);
+///         n /= 10;
+///         if n == 0 {
+///             break;
+///         }
+///     }
+///     out.as_mut_slice().reverse();
+/// }
+///
+/// let mut heap = Vec::new();
+/// let mut tiny: tinyvec<u8, 4> = tinyvec::new();
+/// let mut fixed: stackvec<u8, 10> = stackvec::new();
+/// digits(1234567, &mut heap);
+/// digits(1234567, &mut tiny);
+/// digits(1234567, &mut fixed);
+/// assert!(heap == tiny.as_slice() && heap == fixed.as_slice());
+/// ```
+pub trait veclike<T> {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn as_slice(&self) -> &[T];
+
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    fn get(&self, at: usize) -> Option<&T> {
+        self.as_slice().get(at)
+    }
+
+    fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(at)
+    }
+
+    /// Push `element`, or hand it back if the container can't grow.
+    fn try_push(&mut self, element: T) -> Result<(), capacity_error<T>>;
+
+    /// Panics if the container can't grow.
+    fn push(&mut self, element: T) {
+        if self.try_push(element).is_err() {
+            panic!("push: storage is full");
+        }
+    }
+
+    fn pop(&mut self) -> Option<T>;
+
+    /// Drop the elements from `len` on, if there are that many.
+    fn truncate(&mut self, len: usize);
+
+    fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+
+impl<T, S: storage<T>> veclike<T> for storagevec<T, S> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn as_slice(&self) -> &[T] {
+        self.as_slice()
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+
+    fn try_push(&mut self, element: T) -> Result<(), capacity_error<T>> {
+        self.try_push(element)
+    }
+
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+impl<T, const N: usize> veclike<T> for stackvec<T, N> {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn as_slice(&self) -> &[T] {
+        (**self).as_slice()
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        (**self).as_mut_slice()
+    }
+
+    fn try_push(&mut self, element: T) -> Result<(), capacity_error<T>> {
+        self.push(element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        (**self).pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        (**self).truncate(len);
+    }
+}
+
+impl<T> veclike<T> for Vec<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn as_slice(&self) -> &[T] {
+        self.as_slice()
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+
+    fn try_push(&mut self, element: T) -> Result<(), capacity_error<T>> {
+        self.push(element);
+        Ok(())
+    }
+
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
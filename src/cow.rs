@@ -0,0 +1,97 @@
+//! Borrowed-or-owned buffer, see [`tinycow`].
+
+use std::fmt;
+use std::ops::Deref;
+
+use crate::tinyvec;
+
+/// Either a borrowed slice or an owned [`tinyvec`]; cloned into the
+/// tinyvec the first time it is mutated. Parsers can hand out
+/// subslices of their input and only copy when they have to, e.g.
+/// to unescape.
+///
+/// ```rust
+/// use vecstor::tinycow;
+/// let input = b"plain";
+/// let mut text: tinycow<u8, 16> = tinycow::from(&input[..]);
+/// assert!(text.is_borrowed());
+/// text.to_mut().push(b'!');
+/// assert!(text.is_owned());
+/// assert_eq!(&*text, b"plain!");
+/// ```
+pub enum tinycow<'a, T, const N: usize> {
+    borrowed(&'a [T]),
+    owned(tinyvec<T, N>),
+}
+
+impl<'a, T, const N: usize> tinycow<'a, T, N> {
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, tinycow::borrowed(_))
+    }
+
+    pub fn is_owned(&self) -> bool {
+        !self.is_borrowed()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            tinycow::borrowed(slice) => slice,
+            tinycow::owned(vec) => vec.as_slice(),
+        }
+    }
+
+    /// The owned tinyvec, cloning the slice into it first if
+    /// still borrowed.
+    pub fn to_mut(&mut self) -> &mut tinyvec<T, N>
+    where
+        T: Clone,
+    {
+        if let tinycow::borrowed(slice) = *self {
+            let mut vec = tinyvec::with_heap_capacity(slice.len());
+            vec.extend_from_slice(slice);
+            *self = tinycow::owned(vec);
+        }
+        match self {
+            tinycow::owned(vec) => vec,
+            tinycow::borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// Owned tinyvec, cloning if still borrowed.
+    pub fn into_owned(self) -> tinyvec<T, N>
+    where
+        T: Clone,
+    {
+        match self {
+            tinycow::owned(vec) => vec,
+            mut borrowed => std::mem::take(borrowed.to_mut()),
+        }
+    }
+}
+
+impl<T, const N: usize> Deref for tinycow<'_, T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a [T]> for tinycow<'a, T, N> {
+    fn from(slice: &'a [T]) -> Self {
+        tinycow::borrowed(slice)
+    }
+}
+
+impl<T, const N: usize> From<tinyvec<T, N>> for tinycow<'_, T, N> {
+    fn from(vec: tinyvec<T, N>) -> Self {
+        tinycow::owned(vec)
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for tinycow<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
@@ -0,0 +1,139 @@
+//! NUL-terminated byte string with inline storage, see [`tinycstring`].
+
+use std::ffi::{CStr, c_char};
+
+use crate::error::nul_error;
+use crate::tinyvec;
+
+/// C string that keeps up to N bytes, terminating NUL included,
+/// inline and moves to the heap past that. Builds short paths and
+/// names for FFI calls without allocating.
+///
+/// ```rust
+/// use vecstor::tinycstring;
+/// let mut path: tinycstring<16> = tinycstring::new(b"/dev/").unwrap();
+/// path.push_bytes(b"tty0").unwrap();
+/// assert_eq!(path.as_bytes_with_nul(), b"/dev/tty0\0");
+/// assert!(path.push_bytes(b"a\0b").is_err());
+/// let _for_c = path.as_ptr();
+/// ```
+pub struct tinycstring<const N: usize> {
+    /// Never holds a NUL but the last byte, which always is one.
+    bytes: tinyvec<u8, N>,
+}
+
+impl<const N: usize> tinycstring<N> {
+    /// Copy `bytes` and terminate them, failing at the first NUL
+    /// inside, like `CString::new`.
+    pub fn new(bytes: impl AsRef<[u8]>) -> Result<Self, nul_error> {
+        let mut string = Self {
+            bytes: tinyvec::new(),
+        };
+        string.bytes.push(0);
+        string.push_bytes(bytes.as_ref())?;
+        Ok(string)
+    }
+
+    /// Length in bytes, without the NUL.
+    pub fn len(&self) -> usize {
+        self.bytes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the bytes moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.bytes.is_spilled()
+    }
+
+    /// Append `bytes`, or leave the string as it was if they hold a
+    /// NUL.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), nul_error> {
+        if let Some(at) = bytes.iter().position(|&b| b == 0) {
+            return Err(nul_error { at });
+        }
+        // Reserve first, so a failed allocation panics while the NUL
+        // is still there.
+        if let Err(error) = self.bytes.try_reserve(bytes.len()) {
+            panic!("tinycstring: {error}");
+        }
+        self.bytes.pop();
+        self.bytes.extend_from_slice(bytes);
+        self.bytes.push(0);
+        Ok(())
+    }
+
+    /// Drop the contents, keeping the heap allocation.
+    pub fn clear(&mut self) {
+        self.bytes.truncate(1);
+        self.bytes.as_mut_slice()[0] = 0;
+    }
+
+    /// For C APIs: valid, and NUL-terminated, until `self` is
+    /// changed or dropped.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.bytes.as_slice().as_ptr() as *const c_char
+    }
+
+    pub fn as_c_str(&self) -> &CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.bytes.as_slice()) }
+    }
+
+    /// The bytes without the NUL.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes.as_slice()[..self.len()]
+    }
+
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+impl<const N: usize> std::ops::Deref for tinycstring<N> {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> Default for tinycstring<N> {
+    fn default() -> Self {
+        Self::new([]).unwrap()
+    }
+}
+
+impl<const N: usize> Clone for tinycstring<N> {
+    fn clone(&self) -> Self {
+        Self::new(self.as_bytes()).unwrap()
+    }
+}
+
+impl<const N: usize> From<&CStr> for tinycstring<N> {
+    fn from(s: &CStr) -> Self {
+        Self::new(s.to_bytes()).unwrap()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<const N: usize> std::fmt::Debug for tinycstring<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_c_str(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<tinycstring<M>> for tinycstring<N> {
+    fn eq(&self, other: &tinycstring<M>) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> Eq for tinycstring<N> {}
+
+impl<const N: usize> PartialEq<CStr> for tinycstring<N> {
+    fn eq(&self, other: &CStr) -> bool {
+        self.as_c_str() == other
+    }
+}
@@ -0,0 +1,120 @@
+//! In-memory async I/O over a byte tinyvec, see [`tinycursor`].
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::tinyvec;
+
+/// `std::io::Cursor<Vec<u8>>` for tokio: a byte tinyvec and a
+/// position, with `AsyncRead`/`AsyncBufRead` reading from the
+/// position and `AsyncWrite` overwriting from it, growing the vector
+/// past the end. Always ready, so it stands in for a socket in tests
+/// and in-memory transports.
+///
+/// ```rust
+/// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+/// use vecstor::cursor::tinycursor;
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut wire: tinycursor<32> = tinycursor::default();
+/// wire.write_all(b"ping").await.unwrap();
+/// wire.set_position(0);
+/// let mut reply = [0; 4];
+/// wire.read_exact(&mut reply).await.unwrap();
+/// assert_eq!(&reply, b"ping");
+/// # });
+/// ```
+#[derive(Default)]
+pub struct tinycursor<const N: usize> {
+    inner: tinyvec<u8, N>,
+    position: usize,
+}
+
+impl<const N: usize> tinycursor<N> {
+    /// Starts at position 0.
+    pub fn new(inner: tinyvec<u8, N>) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    pub fn into_inner(self) -> tinyvec<u8, N> {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &tinyvec<u8, N> {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut tinyvec<u8, N> {
+        &mut self.inner
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// May be past the end: reads then see nothing, and a write fills
+    /// the gap with zeroes first.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    fn unread(&self) -> &[u8] {
+        let start = self.position.min(self.inner.len());
+        &self.inner.as_slice()[start..]
+    }
+}
+
+impl<const N: usize> AsyncRead for tinycursor<N> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let n = this.unread().len().min(buf.remaining());
+        buf.put_slice(&this.unread()[..n]);
+        this.position += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<const N: usize> AsyncBufRead for tinycursor<N> {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().unread()))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().position += amt;
+    }
+}
+
+impl<const N: usize> AsyncWrite for tinycursor<N> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let end = this.position.checked_add(buf.len()).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "cursor position overflows")
+        })?;
+        if this.inner.len() < this.position {
+            this.inner.resize(this.position, 0);
+        }
+        let overlap = this.inner.len().min(end) - this.position;
+        this.inner.as_mut_slice()[this.position..][..overlap].copy_from_slice(&buf[..overlap]);
+        this.inner.extend_from_slice(&buf[overlap..]);
+        this.position = end;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
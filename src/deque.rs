@@ -0,0 +1,249 @@
+//! Double-ended queue with inline storage, see [`tinydeque`].
+
+use std::fmt;
+use std::iter::Chain;
+use std::mem::MaybeUninit;
+use std::slice;
+
+use crate::backend::{spill, storage};
+
+/// Ring buffer over the same inline-then-heap storage as
+/// [`tinyvec`](crate::tinyvec). Up to N elements it never allocates;
+/// once full it moves to the heap and keeps growing there.
+///
+/// ```rust
+/// use vecstor::tinydeque;
+/// let mut recent: tinydeque<u32, 4> = tinydeque::new();
+/// for event in 0..6 {
+///     if recent.len() == 4 {
+///         recent.pop_front();
+///     }
+///     recent.push_back(event);
+/// }
+/// assert_eq!(recent.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5]);
+/// ```
+pub struct tinydeque<T, const N: usize> {
+    /// Slots only; its own length stays 0 except while growing.
+    slots: spill<T, N>,
+    /// Slot of the front element.
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> tinydeque<T, N> {
+    pub fn new() -> Self {
+        Self {
+            slots: spill::with_alloc(0, Default::default()),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Elements that fit before the next allocation.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Whether the elements moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.slots.spilled()
+    }
+
+    /// Slot holding logical position `at`, for `at <= capacity()`.
+    fn slot(&self, at: usize) -> usize {
+        // Compared against the room after the head rather than summed,
+        // as a zero-sized T has `usize::MAX` slots.
+        let after_head = self.capacity() - self.head;
+        if at >= after_head {
+            at - after_head
+        } else {
+            self.head + at
+        }
+    }
+
+    /// Make room for one more element. Only called when full, when
+    /// every slot is initialized, so the ring can be rotated so that
+    /// the front is at slot 0 and handed to the storage as its prefix.
+    fn grow(&mut self) {
+        let capacity = self.capacity();
+        debug_assert_eq!(self.len, capacity);
+        unsafe {
+            slice::from_raw_parts_mut(self.slots.as_mut_ptr(), capacity).rotate_left(self.head);
+            self.head = 0;
+            self.slots.set_len(self.len);
+        }
+        if !self.slots.reserve(1) {
+            panic!("tinydeque: capacity overflow");
+        }
+        unsafe { self.slots.set_len(0) };
+    }
+
+    pub fn push_back(&mut self, element: T) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+        let slot = self.slot(self.len);
+        unsafe { self.slots.as_mut_ptr().add(slot).write(element) };
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, element: T) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+        self.head = self.slot(self.capacity() - 1);
+        unsafe { self.slots.as_mut_ptr().add(self.head).write(element) };
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let element = unsafe { self.slots.as_ptr().add(self.head).read() };
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(element)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.slot(self.len);
+        Some(unsafe { self.slots.as_ptr().add(slot).read() })
+    }
+
+    /// Element at logical position `at`, 0 being the front.
+    pub fn get(&self, at: usize) -> Option<&T> {
+        if at >= self.len {
+            return None;
+        }
+        Some(unsafe { &*self.slots.as_ptr().add(self.slot(at)) })
+    }
+
+    pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        if at >= self.len {
+            return None;
+        }
+        let slot = self.slot(at);
+        Some(unsafe { &mut *self.slots.as_mut_ptr().add(slot) })
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// The elements in order, as the run from the head to the end of
+    /// the buffer and the run that wrapped around to the start, like
+    /// `VecDeque::as_slices`. The second one is empty unless the
+    /// contents wrap.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first = self.len.min(self.capacity() - self.head);
+        unsafe {
+            let base = self.slots.as_ptr();
+            (
+                slice::from_raw_parts(base.add(self.head), first),
+                slice::from_raw_parts(base, self.len - first),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let first = self.len.min(self.capacity() - self.head);
+        unsafe {
+            let base = self.slots.as_mut_ptr();
+            (
+                slice::from_raw_parts_mut(base.add(self.head), first),
+                slice::from_raw_parts_mut(base, self.len - first),
+            )
+        }
+    }
+
+    /// Move the elements so they are one slice, and return it. Does
+    /// nothing unless the contents wrap; otherwise the buffer is
+    /// rotated to put the front at slot 0.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let capacity = self.capacity();
+        if self.len > capacity - self.head {
+            // Rotating the slots as `MaybeUninit` moves the free gap
+            // along without reading it.
+            unsafe {
+                let slots = self.slots.as_mut_ptr() as *mut MaybeUninit<T>;
+                slice::from_raw_parts_mut(slots, capacity).rotate_left(self.head);
+            }
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Run `f` on the elements as one slice: borrowed when they don't
+    /// wrap, otherwise cloned into a scratch [`tinyvec`](crate::tinyvec)
+    /// that stays on the stack up to N elements. A
+    /// [`tinyvec`](crate::tinyvec) is always contiguous and needs none
+    /// of this, `as_slice()` is enough there.
+    pub fn with_contiguous<R, F: FnOnce(&[T]) -> R>(&self, f: F) -> R
+    where
+        T: Clone,
+    {
+        match self.as_slices() {
+            (all, []) => f(all),
+            (first, second) => {
+                let mut scratch = crate::tinyvec::<T, N>::with_heap_capacity(self.len);
+                scratch.extend_from_slice(first);
+                scratch.extend_from_slice(second);
+                f(scratch.as_slice())
+            }
+        }
+    }
+
+    /// Mutable `with_contiguous()`, which rotates the elements into
+    /// place with `make_contiguous()` instead of copying.
+    pub fn with_contiguous_mut<R, F: FnOnce(&mut [T]) -> R>(&mut self, f: F) -> R {
+        f(self.make_contiguous())
+    }
+
+    /// Front to back.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second)
+    }
+
+    /// Drop every element, keeping the heap allocation.
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.head = 0;
+    }
+}
+
+impl<T, const N: usize> Drop for tinydeque<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for tinydeque<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for tinydeque<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
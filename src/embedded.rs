@@ -0,0 +1,87 @@
+//! embedded-io support: a byte vector over any backend is a `Write`
+//! sink that appends, and a `Read`/`BufRead` source that consumes
+//! from the front. A full fixed-size backend ends writes short, then
+//! fails them with [`capacity_error`], like a `&mut [u8]`.
+
+use embedded_io::{BufRead, ErrorKind, ErrorType, Read, Write};
+
+use crate::{capacity_error, stackvec, storage, storagevec};
+
+impl embedded_io::Error for capacity_error {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::WriteZero
+    }
+}
+
+impl<S: storage<u8>> ErrorType for storagevec<u8, S> {
+    type Error = capacity_error;
+}
+
+impl<S: storage<u8>> Write for storagevec<u8, S> {
+    /// As much of `buf` as fits.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, capacity_error> {
+        let fits = match self.try_reserve(buf.len()) {
+            Ok(()) => buf.len(),
+            Err(_) => self.remaining_capacity(),
+        };
+        if fits == 0 && !buf.is_empty() {
+            return Err(capacity_error::new(()));
+        }
+        self.extend_from_slice(&buf[..fits]);
+        Ok(fits)
+    }
+
+    fn flush(&mut self) -> Result<(), capacity_error> {
+        Ok(())
+    }
+}
+
+/// Never fails; reads 0 bytes once empty.
+impl<S: storage<u8>> Read for storagevec<u8, S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, capacity_error> {
+        let n = buf.len().min(self.len());
+        buf[..n].copy_from_slice(&self.as_slice()[..n]);
+        self.drain(..n);
+        Ok(n)
+    }
+}
+
+impl<S: storage<u8>> BufRead for storagevec<u8, S> {
+    fn fill_buf(&mut self) -> Result<&[u8], capacity_error> {
+        Ok(self.as_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt.min(self.len()));
+    }
+}
+
+impl<const N: usize> ErrorType for stackvec<u8, N> {
+    type Error = capacity_error;
+}
+
+impl<const N: usize> Write for stackvec<u8, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, capacity_error> {
+        Write::write(&mut **self, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), capacity_error> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> Read for stackvec<u8, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, capacity_error> {
+        Read::read(&mut **self, buf)
+    }
+}
+
+impl<const N: usize> BufRead for stackvec<u8, N> {
+    fn fill_buf(&mut self) -> Result<&[u8], capacity_error> {
+        Ok(self.as_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        BufRead::consume(&mut **self, amt);
+    }
+}
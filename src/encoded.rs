@@ -0,0 +1,109 @@
+//! Hex and base64 serde representations for byte tinyvecs, for
+//! `#[serde(with = "vecstor::encoded::hex")]` or
+//! `#[serde(with = "vecstor::encoded::base64")]`. Human-readable
+//! formats such as JSON get a string; binary formats still get plain
+//! bytes.
+
+use core::fmt;
+
+use serde::Serializer;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+
+use crate::error::decode_error;
+use crate::tinyvec;
+
+/// Inline capacity of the encoded string before it spills; covers a
+/// 32-byte digest in hex.
+const ENCODED: usize = 64;
+
+struct visitor<const N: usize> {
+    decode: fn(&str) -> Result<tinyvec<u8, N>, decode_error>,
+    expecting: &'static str,
+}
+
+impl<'de, const N: usize> Visitor<'de> for visitor<N> {
+    type Value = tinyvec<u8, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        (self.decode)(s).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Ok(bytes.iter().copied().collect())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut out = tinyvec::new();
+        while let Some(byte) = seq.next_element()? {
+            out.push(byte);
+        }
+        Ok(out)
+    }
+}
+
+fn deserialize_with<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+    visitor: visitor<N>,
+) -> Result<tinyvec<u8, N>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+/// Lowercase hex; either case is accepted back.
+pub mod hex {
+    use super::*;
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        bytes: &tinyvec<u8, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(bytes.to_hex::<ENCODED>().as_str())
+        } else {
+            serializer.serialize_bytes(bytes.as_slice())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<tinyvec<u8, N>, D::Error> {
+        let visitor = visitor {
+            decode: tinyvec::from_hex,
+            expecting: "a hex string",
+        };
+        deserialize_with(deserializer, visitor)
+    }
+}
+
+/// Standard padded base64; the padding is optional coming back.
+pub mod base64 {
+    use super::*;
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        bytes: &tinyvec<u8, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(bytes.to_base64::<ENCODED>().as_str())
+        } else {
+            serializer.serialize_bytes(bytes.as_slice())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<tinyvec<u8, N>, D::Error> {
+        let visitor = visitor {
+            decode: tinyvec::from_base64,
+            expecting: "a base64 string",
+        };
+        deserialize_with(deserializer, visitor)
+    }
+}
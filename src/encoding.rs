@@ -0,0 +1,114 @@
+//! Hex and base64 for byte tinyvecs.
+
+use crate::error::decode_error;
+use crate::{tinystring, tinyvec};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn base64_value(byte: u8) -> Option<u8> {
+    BASE64.iter().position(|&b| b == byte).map(|v| v as u8)
+}
+
+impl<const N: usize> tinyvec<u8, N> {
+    /// Lowercase hex, two digits per byte.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinystring, tinyvec};
+    /// let digest: tinyvec<u8, 4> = tiny_vec![0xde, 0xad, 0x01];
+    /// let hex: tinystring<8> = digest.to_hex();
+    /// assert_eq!(hex, "dead01");
+    /// assert_eq!(tinyvec::<u8, 4>::from_hex("DEAD01"), Ok(digest));
+    /// ```
+    pub fn to_hex<const K: usize>(&self) -> tinystring<K> {
+        let mut out = tinystring::new();
+        for &byte in self.iter() {
+            out.push(HEX[usize::from(byte >> 4)] as char);
+            out.push(HEX[usize::from(byte & 0xf)] as char);
+        }
+        out
+    }
+
+    /// Parse hex digits of either case, two per byte.
+    pub fn from_hex(hex: &str) -> Result<Self, decode_error> {
+        let hex = hex.as_bytes();
+        if !hex.len().is_multiple_of(2) {
+            return Err(decode_error::invalid_length);
+        }
+        let mut out = Self::with_heap_capacity(hex.len() / 2);
+        for (pair, digits) in hex.chunks_exact(2).enumerate() {
+            let digit = |i: usize| {
+                hex_value(digits[i]).ok_or(decode_error::invalid_byte { at: pair * 2 + i })
+            };
+            out.push(digit(0)? << 4 | digit(1)?);
+        }
+        Ok(out)
+    }
+
+    /// Standard base64 (RFC 4648), with `=` padding.
+    ///
+    /// ```rust
+    /// use vecstor::{tinystring, tinyvec};
+    /// let token = tinyvec::<u8, 8>::from_iter(*b"hello");
+    /// let encoded: tinystring<8> = token.to_base64();
+    /// assert_eq!(encoded, "aGVsbG8=");
+    /// assert_eq!(tinyvec::<u8, 8>::from_base64("aGVsbG8"), Ok(token));
+    /// ```
+    pub fn to_base64<const K: usize>(&self) -> tinystring<K> {
+        let mut out = tinystring::new();
+        for group in self.chunks(3) {
+            let bits = group
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= group.len() {
+                    out.push(BASE64[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    /// Parse standard base64; the trailing `=` padding is optional.
+    pub fn from_base64(base64: &str) -> Result<Self, decode_error> {
+        let input = base64.as_bytes();
+        let data = input
+            .strip_suffix(b"==")
+            .or_else(|| input.strip_suffix(b"="));
+        let data = match data {
+            Some(data) if !input.len().is_multiple_of(4) => {
+                return Err(decode_error::invalid_byte { at: data.len() });
+            }
+            Some(data) => data,
+            None => input,
+        };
+        if data.len() % 4 == 1 {
+            return Err(decode_error::invalid_length);
+        }
+        let mut out = Self::with_heap_capacity(data.len() / 4 * 3 + 2);
+        for (group, chars) in data.chunks(4).enumerate() {
+            let mut bits = 0u32;
+            for (i, &c) in chars.iter().enumerate() {
+                let value =
+                    base64_value(c).ok_or(decode_error::invalid_byte { at: group * 4 + i })?;
+                bits |= u32::from(value) << (18 - 6 * i);
+            }
+            for i in 0..chars.len() - 1 {
+                out.push((bits >> (16 - 8 * i)) as u8);
+            }
+        }
+        Ok(out)
+    }
+}
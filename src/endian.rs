@@ -0,0 +1,42 @@
+//! Fixed-width integers in and out of byte tinyvecs, for assembling and
+//! parsing small binary frames.
+
+use crate::tinyvec;
+
+macro_rules! endian_methods {
+    ($($ty:ty: $put_le:ident, $put_be:ident, $get_le:ident, $get_be:ident;)*) => {
+        impl<const N: usize> tinyvec<u8, N> {
+            $(
+                /// Append, least significant byte first.
+                pub fn $put_le(&mut self, value: $ty) {
+                    self.extend_from_slice(&value.to_le_bytes());
+                }
+
+                /// Append, most significant byte first.
+                pub fn $put_be(&mut self, value: $ty) {
+                    self.extend_from_slice(&value.to_be_bytes());
+                }
+
+                /// Read at byte `offset`, `None` if it runs past the end.
+                pub fn $get_le(&self, offset: usize) -> Option<$ty> {
+                    let bytes = self.as_slice().get(offset..offset.checked_add(size_of::<$ty>())?)?;
+                    Some(<$ty>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+
+                pub fn $get_be(&self, offset: usize) -> Option<$ty> {
+                    let bytes = self.as_slice().get(offset..offset.checked_add(size_of::<$ty>())?)?;
+                    Some(<$ty>::from_be_bytes(bytes.try_into().unwrap()))
+                }
+            )*
+        }
+    };
+}
+
+endian_methods! {
+    u16: put_u16_le, put_u16_be, get_u16_le, get_u16_be;
+    u32: put_u32_le, put_u32_be, get_u32_le, get_u32_be;
+    u64: put_u64_le, put_u64_be, get_u64_le, get_u64_be;
+    i16: put_i16_le, put_i16_be, get_i16_le, get_i16_be;
+    i32: put_i32_le, put_i32_be, get_i32_le, get_i32_be;
+    i64: put_i64_le, put_i64_be, get_i64_le, get_i64_be;
+}
@@ -0,0 +1,141 @@
+//! Errors of the fallible `try_*` methods.
+//!
+//! All of them implement [`Display`](core::fmt::Display) and
+//! [`core::error::Error`], and only depend on `core`.
+
+use core::fmt;
+
+/// The storage had no room for an element, and can't (or
+/// wasn't allowed to) grow. Hands the element back.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct capacity_error<T = ()> {
+    element: T,
+}
+
+impl<T> capacity_error<T> {
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    /// The element that didn't fit.
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Drop the element, e.g. to pass the error on with `?`.
+    pub fn simplify(self) -> capacity_error {
+        capacity_error { element: () }
+    }
+}
+
+/// Leaves the element out, so it needs no `T: Debug`.
+impl<T> fmt::Debug for capacity_error<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("capacity_error")
+    }
+}
+
+impl<T> fmt::Display for capacity_error<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("storage is full")
+    }
+}
+
+impl<T> core::error::Error for capacity_error<T> {}
+
+/// Why `try_reserve()` couldn't make room.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum try_reserve_error {
+    /// More than the storage can ever hold: a fixed-size backend is
+    /// out of slots, or the length would overflow.
+    capacity_overflow,
+    /// The allocator returned an error.
+    alloc_failed,
+}
+
+impl fmt::Display for try_reserve_error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            try_reserve_error::capacity_overflow => f.write_str("capacity overflow"),
+            try_reserve_error::alloc_failed => f.write_str("memory allocation failed"),
+        }
+    }
+}
+
+impl core::error::Error for try_reserve_error {}
+
+/// An index past the end, with the length it was checked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct index_error {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for index_error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index {} out of bounds (len {})", self.index, self.len)
+    }
+}
+
+impl core::error::Error for index_error {}
+
+/// Why `get_many_mut()` couldn't hand out the references.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum disjoint_error {
+    out_of_bounds(index_error),
+    /// `index` was asked for more than once.
+    overlapping {
+        index: usize,
+    },
+}
+
+impl fmt::Display for disjoint_error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            disjoint_error::out_of_bounds(error) => error.fmt(f),
+            disjoint_error::overlapping { index } => write!(f, "index {index} requested twice"),
+        }
+    }
+}
+
+impl core::error::Error for disjoint_error {}
+
+impl From<index_error> for disjoint_error {
+    fn from(error: index_error) -> Self {
+        disjoint_error::out_of_bounds(error)
+    }
+}
+
+/// Input to `from_hex()` or `from_base64()` that isn't valid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum decode_error {
+    /// Byte `at` is not part of the alphabet, or is misplaced padding.
+    invalid_byte { at: usize },
+    /// The input ends partway through an encoded byte.
+    invalid_length,
+}
+
+impl fmt::Display for decode_error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            decode_error::invalid_byte { at } => write!(f, "invalid byte at offset {at}"),
+            decode_error::invalid_length => f.write_str("truncated input"),
+        }
+    }
+}
+
+impl core::error::Error for decode_error {}
+
+/// Bytes for a C string with a NUL inside, at offset `at`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct nul_error {
+    pub at: usize,
+}
+
+impl fmt::Display for nul_error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nul byte at offset {}", self.at)
+    }
+}
+
+impl core::error::Error for nul_error {}
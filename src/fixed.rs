@@ -0,0 +1,150 @@
+//! Fixed-capacity vector without any heap, see [`stackvec`].
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::error::capacity_error;
+use crate::{inline, owned_iter, storagevec};
+
+/// Vector with room for exactly N elements, kept inline. It has no
+/// heap field and no allocator parameter, so it never allocates and
+/// its size is fixed by `N` alone.
+///
+/// `push` and `insert` hand the element back instead of growing;
+/// everything else is [`storagevec`]'s API through `Deref`.
+///
+/// ```rust
+/// use vecstor::stackvec;
+/// let mut buf: stackvec<u8, 2> = stackvec::new();
+/// assert!(buf.push(1).is_ok());
+/// assert!(buf.push(2).is_ok());
+/// assert_eq!(buf.push(3).unwrap_err().element(), 3);
+/// assert_eq!(buf.len(), 2);
+/// ```
+pub struct stackvec<T, const N: usize> {
+    vec: storagevec<T, inline<T, N>>,
+}
+
+impl<T, const N: usize> stackvec<T, N> {
+    /// Build-time check that this takes at most `MAX_BYTES` and that
+    /// N isn't 0, see `tinyvec::assert_footprint()`.
+    ///
+    /// ```rust,compile_fail
+    /// use vecstor::stackvec;
+    /// const _: () = stackvec::<u8, 0>::assert_footprint::<64>();
+    /// ```
+    pub const fn assert_footprint<const MAX_BYTES: usize>() {
+        storagevec::<T, inline<T, N>>::assert_footprint::<MAX_BYTES>()
+    }
+
+    /// Usable in `const` items.
+    pub const fn new() -> Self {
+        Self::from_inline(inline::new_const())
+    }
+
+    const fn from_inline(storage: inline<T, N>) -> Self {
+        Self {
+            vec: storagevec {
+                storage,
+                marker: PhantomData,
+            },
+        }
+    }
+
+    /// Always N.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Whether another `push()` would fail.
+    pub fn is_full(&self) -> bool {
+        self.vec.len() == N
+    }
+
+    /// Push `element`, or hand it back if all N slots are taken.
+    pub fn push(&mut self, element: T) -> Result<(), capacity_error<T>> {
+        self.vec.try_push(element)
+    }
+
+    /// Insert `element` at `index`, or hand it back if full.
+    ///
+    /// Panics if `index > len()`.
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), capacity_error<T>> {
+        self.vec.try_insert(index, element)
+    }
+}
+
+/// Construction in const contexts, so small lookup tables can be
+/// `const` items instead of lazily built statics.
+///
+/// ```rust
+/// use vecstor::stackvec;
+/// const PRIMES: stackvec<u32, 8> = {
+///     let mut primes = stackvec::from_slice(&[2, 3, 5]);
+///     primes.push_const(7);
+///     primes
+/// };
+/// assert_eq!(PRIMES.as_slice(), [2, 3, 5, 7]);
+/// ```
+impl<T: Copy, const N: usize> stackvec<T, N> {
+    /// Copy of `items`. Panics, which fails the build in a const
+    /// item, if there are more than N.
+    pub const fn from_slice(items: &[T]) -> Self {
+        let mut storage = inline::new_const();
+        let mut at = 0;
+        while at < items.len() {
+            storage.push_const(items[at]);
+            at += 1;
+        }
+        Self::from_inline(storage)
+    }
+
+    /// `push()` for const contexts: panics instead of handing the
+    /// element back.
+    pub const fn push_const(&mut self, element: T) {
+        self.vec.storage.push_const(element);
+    }
+}
+
+impl<T, const N: usize> Deref for stackvec<T, N> {
+    type Target = storagevec<T, inline<T, N>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T, const N: usize> DerefMut for stackvec<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vec
+    }
+}
+
+impl<T, const N: usize> Default for stackvec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for stackvec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.vec, f)
+    }
+}
+
+impl<T, const N: usize> IntoIterator for stackvec<T, N> {
+    type Item = T;
+    type IntoIter = owned_iter<T, inline<T, N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.into_iter()
+    }
+}
+
+impl<T, const N: usize> From<storagevec<T, inline<T, N>>> for stackvec<T, N> {
+    fn from(vec: storagevec<T, inline<T, N>>) -> Self {
+        Self { vec }
+    }
+}
@@ -0,0 +1,158 @@
+//! Fixed-size 2D grid, see [`tinygrid`].
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::tinyvec;
+
+/// W by H cells in row-major order. The cells live in a
+/// [`tinyvec<T, N>`](crate::tinyvec): stable Rust can't size an array
+/// from `W * H`, so the inline capacity is its own parameter and a
+/// grid bigger than N cells is on the heap from the start.
+///
+/// Indexing is `grid[(x, y)]`, x being the column.
+///
+/// ```rust
+/// use vecstor::tinygrid;
+/// let mut board: tinygrid<char, 3, 3, 9> = tinygrid::new('.');
+/// board[(1, 1)] = 'x';
+/// board[(2, 0)] = 'o';
+/// assert_eq!(board.row(0), ['.', '.', 'o']);
+/// assert_eq!(board.column(1).collect::<String>(), ".x.");
+/// assert!(!board.is_spilled());
+/// ```
+pub struct tinygrid<T, const W: usize, const H: usize, const N: usize = 64> {
+    cells: tinyvec<T, N>,
+}
+
+impl<T, const W: usize, const H: usize, const N: usize> tinygrid<T, W, H, N> {
+    /// Every cell set to `value`.
+    pub fn new(value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = tinyvec::new();
+        cells.resize(W * H, value);
+        Self { cells }
+    }
+
+    /// Cell `(x, y)` set to `f(x, y)`, row by row.
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Self {
+        let mut cells = tinyvec::new();
+        let mut at = 0;
+        cells.resize_with(W * H, || {
+            let cell = f(at % W, at / W);
+            at += 1;
+            cell
+        });
+        Self { cells }
+    }
+
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Whether the cells are on the heap, i.e. `W * H > N`.
+    pub fn is_spilled(&self) -> bool {
+        self.cells.is_spilled()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= W || y >= H {
+            return None;
+        }
+        self.cells.get(y * W + x)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= W || y >= H {
+            return None;
+        }
+        self.cells.get_mut(y * W + x)
+    }
+
+    /// Row `y`; panics if out of range.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < H, "row {y} out of range for height {H}");
+        &self.cells.as_slice()[y * W..][..W]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(y < H, "row {y} out of range for height {H}");
+        &mut self.cells.as_mut_slice()[y * W..][..W]
+    }
+
+    /// Rows top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact panics on 0, and a zero-width grid has no cells.
+        self.cells.as_slice().chunks_exact(W.max(1)).take(H)
+    }
+
+    /// Cells of column `x`, top to bottom; panics if out of range.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < W, "column {x} out of range for width {W}");
+        self.cells.iter().skip(x).step_by(W)
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.cells.fill(value);
+    }
+
+    /// All cells, row-major.
+    pub fn as_slice(&self) -> &[T] {
+        self.cells.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.cells.as_mut_slice()
+    }
+}
+
+impl<T, const W: usize, const H: usize, const N: usize> Index<(usize, usize)>
+    for tinygrid<T, W, H, N>
+{
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.get(x, y) {
+            Some(cell) => cell,
+            None => panic!("cell ({x}, {y}) out of range for {W}x{H} grid"),
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize, const N: usize> IndexMut<(usize, usize)>
+    for tinygrid<T, W, H, N>
+{
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        match self.get_mut(x, y) {
+            Some(cell) => cell,
+            None => panic!("cell ({x}, {y}) out of range for {W}x{H} grid"),
+        }
+    }
+}
+
+impl<T: Default + Clone, const W: usize, const H: usize, const N: usize> Default
+    for tinygrid<T, W, H, N>
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// One list per row.
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const W: usize, const H: usize, const N: usize> fmt::Debug
+    for tinygrid<T, W, H, N>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
@@ -0,0 +1,86 @@
+//! How the heap segment of a [`tinyvec`](crate::tinyvec) grows.
+//!
+//! Pick one as the last type parameter:
+//! `tinyvec<T, 8, Global, growth::exact>`.
+
+/// Decides the new heap capacity whenever the heap runs out of room.
+pub trait policy {
+    /// `capacity` is what is allocated now (the stack size on the
+    /// first spill), `required` is the least that has to fit.
+    /// The result must be at least `required`.
+    fn grow(capacity: usize, required: usize) -> usize;
+
+    /// Called after elements are removed from the heap, with its
+    /// capacity and the new length. `Some(target)` shrinks the heap
+    /// to hold `target` elements; the default keeps it as it is.
+    fn shrink(capacity: usize, len: usize) -> Option<usize> {
+        let _ = (capacity, len);
+        None
+    }
+}
+
+/// Double the capacity, like `Vec`. The default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct doubling;
+
+/// Allocate exactly what is required, nothing spare.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct exact;
+
+/// Grow in steps of K slots.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct fixed<const K: usize>;
+
+/// Grow like `P`, and halve the heap whenever removals leave it less
+/// than a quarter full, so a vector that spiked once doesn't hold on
+/// to the memory. Halving, rather than fitting, leaves room to grow
+/// back without reallocating right away.
+///
+/// ```rust
+/// use vecstor::{growth, tinyvec};
+/// let mut queue: tinyvec<u32, 4, _, growth::shrinking> = (0..1000).collect();
+/// let peak = queue.total_capacity();
+/// queue.truncate(10);
+/// assert!(queue.total_capacity() < peak / 8);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct shrinking<P = doubling>(core::marker::PhantomData<P>);
+
+impl policy for doubling {
+    fn grow(capacity: usize, required: usize) -> usize {
+        capacity.saturating_mul(2).max(required)
+    }
+}
+
+impl policy for exact {
+    fn grow(capacity: usize, required: usize) -> usize {
+        required
+    }
+}
+
+impl<P: policy> policy for shrinking<P> {
+    fn grow(capacity: usize, required: usize) -> usize {
+        P::grow(capacity, required)
+    }
+
+    fn shrink(mut capacity: usize, len: usize) -> Option<usize> {
+        if len >= capacity / 4 {
+            return None;
+        }
+        while len < capacity / 4 {
+            capacity /= 2;
+        }
+        Some(capacity)
+    }
+}
+
+impl<const K: usize> policy for fixed<K> {
+    fn grow(capacity: usize, required: usize) -> usize {
+        if K == 0 || required <= capacity {
+            return required;
+        }
+
+        let steps = (required - capacity).div_ceil(K);
+        capacity.saturating_add(steps.saturating_mul(K))
+    }
+}
@@ -1,3 +1,6 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "extend_one", feature(extend_one))]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
 #![allow(
     unused_variables,
     dead_code,
@@ -10,18 +13,114 @@
 use std::default;
 use std::fmt::{self, Display, Formatter, Result};
 use std::iter;
-use std::mem::MaybeUninit;
+use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
 use std::time::Instant;
 
+#[cfg(feature = "abi_stable")]
+mod abi;
+pub mod align;
+pub mod allocator;
+pub mod append;
+pub mod arraystr;
+pub mod backend;
+#[cfg(feature = "speedy")]
+mod binary;
+pub mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
+pub mod bytebuf;
+pub mod channel;
+pub mod chunked;
+pub mod contiguous;
+pub mod cow;
+pub mod cstring;
+#[cfg(feature = "tokio")]
+pub mod cursor;
+pub mod deque;
+#[cfg(feature = "embedded-io")]
+mod embedded;
+#[cfg(all(feature = "serde", feature = "encoding"))]
+pub mod encoded;
+#[cfg(feature = "encoding")]
+mod encoding;
+mod endian;
+pub mod error;
+pub mod fixed;
+pub mod grid;
+pub mod growth;
+pub mod lossy;
+pub mod lru;
+mod macros;
+pub mod map;
+#[cfg(feature = "math")]
+mod math;
+#[cfg(feature = "ufmt")]
+mod micro;
+mod network;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "nom")]
+mod parsing;
+pub mod pinned;
+pub mod pool;
+pub mod priority;
+#[cfg(feature = "pyo3")]
+mod python;
+pub mod ring;
+#[cfg(feature = "schemars")]
+mod schema;
+pub mod set;
+pub mod shared;
+pub mod slots;
+pub mod sorted;
+#[cfg(feature = "sqlx")]
+mod sql;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "futures")]
+pub mod stream;
+pub mod string;
+#[cfg(feature = "wasm")]
+mod wasm;
+pub mod zeroed;
+
+use allocator::{Allocator, Global};
+pub use append::appendvec;
+pub use arraystr::arraystring;
+pub use backend::{borrowed, boxed, index, inline, on_spill, spill, storage};
+pub use bits::tinybitset;
+pub use bytebuf::tinybytes;
+pub use channel::{mpmc, spsc};
+pub use chunked::chunkedvec;
+pub use contiguous::veclike;
+pub use cow::tinycow;
+pub use cstring::tinycstring;
+pub use deque::tinydeque;
+pub use error::{
+    capacity_error, decode_error, disjoint_error, index_error, nul_error, try_reserve_error,
+};
+pub use fixed::stackvec;
+pub use grid::tinygrid;
+pub use lossy::lossyvec;
+pub use lru::tinylru;
+pub use map::{sortedmap, tinyhashmap, tinymap};
+pub use pinned::pinnedvec;
+pub use priority::tinyheap;
+pub use ring::tinyring;
+pub use set::tinyset;
+pub use shared::sharedvec;
+pub use slots::{slotkey, tinyslotmap};
+pub use sorted::sortedvec;
+#[cfg(feature = "stats")]
+pub use stats::{counters, stats};
+pub use string::tinystring;
+pub use zeroed::zeroable;
+
 ///Size of heap allocated at once
+#[deprecated(note = "pick the heap reservation per instance with `tinyvec::with_heap_capacity`")]
 pub static general_heap: usize = 1024;
 
-#[derive(Debug, PartialEq)]
-enum step_iter {
-    Not,
-    Yes(usize),
-}
-
 /// Store a small number of elements on the stack.
 ///
 /// Vec<'_> are inefficient if used with less caution.
@@ -36,9 +135,30 @@ enum step_iter {
 ///
 /// # Usage
 ///
-/// Note that tinyvec type is compatible with only `'Copy-Types'` as now.
-/// This is by design. Mostly you would want to use such types. Extend
-/// the trait bounds if you feel the need.
+/// Elements are moved in and out, so `String`, `Box<_>` and other
+/// move-only types work as well as `Copy` types. Only extending from
+/// references (`extend_from_slice()`, `extend(&[..])`) clones, so
+/// that needs `T: Clone`.
+///
+/// `N = 0` is allowed and gives a plain heap vector: every push goes
+/// to the heap, so generic code can pick the inline capacity freely.
+///
+/// # Address stability
+///
+/// Element addresses are exactly as stable as the buffer they sit in:
+/// - inline elements move whenever the tinyvec itself is moved, and
+///   all of them move to the heap on the spill;
+/// - spilled elements stay put while the vector moves, until a push,
+///   `reserve()` or the like reallocates the heap, or
+///   `shrink_to_inline()` brings them back;
+/// - `insert()`, `remove()`, `drain()`, `retain()`, sorting and the
+///   like shift elements within the buffer.
+///
+/// Starting on the heap with all the room needed
+/// (`with_heap_capacity()` past N) and never exceeding it keeps
+/// addresses fixed. For inline elements
+/// that must not move, e.g. because they hold back-pointers into the
+/// buffer, use [`pinnedvec`], which enforces this through `Pin`.
 ///
 /// ```rust, ignore
 /// // Initialize a tinyvec with type i32 and number of elements on stack 64
@@ -51,7 +171,7 @@ enum step_iter {
 /// }
 ///
 /// // len == capacity of stack
-/// assert_eq!(tinyvecwtor.len(), tinyvecwtor.capacity().0);
+/// assert_eq!(tinyvecwtor.len(), tinyvecwtor.capacity().inline);
 ///
 /// // adding more values, pushes them to heap now
 /// tinyvecwtor.push(100);
@@ -61,7 +181,7 @@ enum step_iter {
 ///
 /// // tinyvector can be used to initialize a normal vector or array
 /// // Vec<_> should also be valid
-/// let vector = tinyvecwtor.collect::<Vec<i32>>();
+/// let vector = tinyvecwtor.into_iter().collect::<Vec<i32>>();
 ///
 /// // To iterate over a tinyvec type
 /// for i in tinyvecwtor {
@@ -70,7 +190,7 @@ enum step_iter {
 ///
 /// let element = tinyvecwtor.get(20);
 ///
-/// // get() gives you an Option to avoid out of bound access
+/// // get() gives you an Option<&T> to avoid out of bound access
 /// // the same is true for `remove()`
 /// if let Some(elem) = element{
 ///     // do something with elem
@@ -85,348 +205,5834 @@ enum step_iter {
 /// tinyvecwtor.extend(&vector);
 /// tinyvecwtor.extend(&array);
 /// ```
-#[derive(Debug)]
-pub struct tinyvec<T, const N: usize>
-where
-    T: Copy,
-    T: Default,
-    T: Display,
-{
-    stack: [MaybeUninit<T>; N],
-    heap: Vec<T>,
-    counter: usize,
-    iters: step_iter,
+pub type tinyvec<T, const N: usize, A = Global, G = growth::doubling, L = usize> =
+    storagevec<T, spill<T, N, A, G, L>>;
+
+/// Capacity of a [`tinyvec`], as returned by `capacity()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct capacity {
+    /// Slots in the inline buffer, N.
+    pub inline: usize,
+    /// Slots allocated on the heap, 0 until it spills
+    /// (or reserves up front).
+    pub heap: usize,
 }
 
-impl<T, const N: usize> tinyvec<T, N>
-where
-    T: Copy,
-    T: Default + Display,
-{
-    /// New tinyvector
-    /// with default heap capacity: `general_heap: usize`.
-    pub fn new() -> Self {
-        Self {
-            stack: unsafe { MaybeUninit::uninit().assume_init() },
-            heap: Vec::with_capacity(general_heap),
-            counter: 0,
-            iters: step_iter::Not,
+impl capacity {
+    /// How many elements fit: the heap once there is one, since
+    /// spilling moves every element there, otherwise the inline
+    /// buffer. Same as `total_capacity()`.
+    pub fn total(&self) -> usize {
+        if self.heap > 0 {
+            self.heap
+        } else {
+            self.inline
         }
     }
+}
 
-    /// Return length(stack + heap). For more information
-    /// use capacity().
-    pub fn len(&self) -> usize {
-        self.counter
+/// For code still matching on the old tuple.
+impl From<capacity> for (usize, usize) {
+    fn from(capacity: capacity) -> Self {
+        (capacity.inline, capacity.heap)
     }
+}
 
-    /// Returns (capacity_on_stack, capacity_on_heap)
-    pub fn capacity(&self) -> (usize, usize) {
-        (N, self.heap.capacity())
-    }
+/// Memory breakdown returned by `tinyvec::memory_usage()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct memory {
+    /// Size of the tinyvec value itself, inline buffer included.
+    pub inline: usize,
+    /// Bytes allocated on the heap, used or not.
+    pub heap_allocated: usize,
+    /// Bytes of the heap allocation holding elements.
+    pub heap_used: usize,
+}
 
-    /// Push value to stack if `counter < general`
-    /// else push to heap.
-    pub fn push(&mut self, element: T) {
-        if self.counter >= N {
-            self.heap.push(element);
-        } else {
-            self.stack[self.counter] = MaybeUninit::new(element);
-        }
+/// Vector over a caller-provided buffer, see `from_buf()` and
+/// `from_slice()`. Can't grow past the buffer; `push()` panics then,
+/// `try_push()` hands the element back.
+pub type slicevec<'a, T> = storagevec<T, borrowed<'a, T>>;
+
+/// [`tinyvec`] for large N: the N slots are boxed, so the vector stays
+/// small enough to move cheaply and can't overflow the stack. The
+/// box is only allocated on the first push.
+///
+/// ```rust
+/// use vecstor::boxedvec;
+/// let mut samples: boxedvec<f32, 4096> = boxedvec::new();
+/// assert!(size_of_val(&samples) < 64);
+/// samples.extend([0.5; 4096]);
+/// assert!(!samples.is_spilled());
+/// samples.push(1.0);
+/// assert!(samples.is_spilled());
+/// ```
+pub type boxedvec<T, const N: usize, G = growth::doubling> = storagevec<T, boxed<T, N, G>>;
+
+/// Vector API over any [`storage`] backend.
+///
+/// [`tinyvec`] is this over [`spill`]. The same methods also run over
+/// a plain inline array ([`inline`]) or a caller-provided buffer
+/// ([`borrowed`]), which can't grow, so pushing past their capacity panics.
+///
+/// # Panic-free subset
+///
+/// These never panic in release builds, over any backend of this
+/// crate, and report failure through `Option`/`Result` instead:
+///
+/// - `len()`, `is_empty()`, `total_capacity()`, `remaining_capacity()`
+/// - `get()`, `get_mut()`, `checked_index()`, `first_chunk()`,
+///   `last_chunk()`, `split_first()`, `split_last()`
+/// - `as_slice()`, `as_mut_slice()`, `iter()`, `iter_mut()`
+/// - `try_push()`, `try_reserve()`, `pop()`, `remove()`
+/// - `truncate()` and `clear()`, if `T`'s destructor doesn't panic
+///
+/// None of them unwraps or indexes a slice. Debug builds still
+/// assert the internal invariants. Heap allocation failure is
+/// reported by `try_reserve()`/`try_push()` on [`tinyvec`]; the
+/// [`boxed`] backend aborts like `Vec` does. Everything else may
+/// panic as documented, e.g. `push()` on a full inline storage or
+/// `try_insert()` past the end.
+pub struct storagevec<T, S: storage<T>> {
+    storage: S,
+    marker: PhantomData<T>,
+}
 
-        self.counter += 1;
+impl<T, const N: usize, G: growth::policy, L: index> tinyvec<T, N, Global, G, L> {
+    /// New tinyvector. Nothing is allocated until it outgrows
+    /// the stack; from then on the heap grows as `G` says.
+    pub fn new() -> Self {
+        Self::with_heap_capacity(0)
     }
 
-    pub fn get(&self, at: usize) -> Option<T> {
-        if at >= self.counter {
-            return None;
-        }
+    /// New tinyvector reserving `capacity` slots on the heap right
+    /// away, used once it outgrows the stack. Panics, like `Vec`, if
+    /// that would be more than `isize::MAX` bytes.
+    pub fn with_heap_capacity(capacity: usize) -> Self {
+        Self::from_storage(spill::with_alloc(capacity, Global))
+    }
 
-        if at < N {
-            unsafe {
-                return Some(*self.stack[at].as_ptr());
+    /// Collect `iter` without ever allocating. If it has more items
+    /// than the inline slots hold, the error carries what was
+    /// collected and the first item that didn't fit; pass
+    /// `iter.by_ref()` to get at the rest.
+    ///
+    /// ```rust
+    /// use vecstor::tinyvec;
+    /// let small = tinyvec::<u8, 4>::try_from_iter(0..3).unwrap();
+    /// assert_eq!(small.as_slice(), [0, 1, 2]);
+    /// let (kept, extra) = tinyvec::<u8, 4>::try_from_iter(0..9).err().unwrap().element();
+    /// assert_eq!((kept.len(), extra), (4, 4));
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(
+        iter: I,
+    ) -> std::result::Result<Self, capacity_error<(Self, T)>> {
+        let mut vector = Self::new();
+        let inline = vector.storage.capacities().0;
+        for element in iter {
+            if vector.len() == inline {
+                return Err(capacity_error::new((vector, element)));
             }
-        } else {
-            return Some(self.heap[at - N]);
+            vector.push(element);
         }
+        Ok(vector)
     }
 
-    /// Returns Option instead of `T`
-    pub fn remove(&mut self, index: usize) -> Option<T> {
-        if index >= self.counter {
-            return None;
+    /// New tinyvec of `f` applied to each element, in order, with the
+    /// same N: on the stack if the results fit there, otherwise one
+    /// heap allocation of the right size.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let ms: tinyvec<u32, 4> = tiny_vec![1500, 20];
+    /// let secs: tinyvec<f32, 4> = ms.map(|ms| ms as f32 / 1000.0);
+    /// assert_eq!(secs.as_slice(), [1.5, 0.02]);
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> tinyvec<U, N, Global, G, L> {
+        let mut mapped = tinyvec::with_heap_capacity(self.len());
+        for element in self.into_iter().map(f) {
+            mapped.push(element);
         }
+        mapped
+    }
 
-        if index < N {
-            unsafe {
-                let value = self.stack[index].as_ptr().read();
-                for i in index..N - 1 {
-                    self.stack[i] = MaybeUninit::new(self.stack[i + 1].as_ptr().read());
-                }
-
-                self.counter -= 1;
-                return Some(value);
-            }
-        } else {
-            let value = self.heap[index];
-            self.heap.remove(index - N);
-            self.counter -= 1;
-            return Some(value);
+    /// `map()` with a fallible `f`, stopping at the first error.
+    pub fn try_map<U, E, F: FnMut(T) -> std::result::Result<U, E>>(
+        self,
+        mut f: F,
+    ) -> std::result::Result<tinyvec<U, N, Global, G, L>, E> {
+        let mut mapped = tinyvec::with_heap_capacity(self.len());
+        for element in self {
+            mapped.push(f(element)?);
         }
+        Ok(mapped)
     }
 
-    /// Returns `T` as long as tinyvec holds some elements.
-    /// If heap.pop() fails or len == 0 returns `T::default()`.
-    pub fn pop(&mut self) -> T {
-        if self.len() == 0 {
-            return T::default();
+    /// `f` of each pair of elements at the same position, in one
+    /// pass, into a tinyvec with the same N.
+    ///
+    /// Panics if the lengths differ.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let position: tinyvec<f32, 4> = tiny_vec![0.0, 1.0];
+    /// let velocity: tinyvec<f32, 4> = tiny_vec![0.5, -1.0];
+    /// let next: tinyvec<f32, 4> = position.zip_with(&velocity, |p, v| p + v);
+    /// assert_eq!(next.as_slice(), [0.5, 0.0]);
+    /// ```
+    pub fn zip_with<V, R: storage<V>, U, F: FnMut(&T, &V) -> U>(
+        &self,
+        other: &storagevec<V, R>,
+        mut f: F,
+    ) -> tinyvec<U, N, Global, G, L> {
+        assert_eq!(self.len(), other.len(), "zip_with: lengths differ");
+        let mut zipped = tinyvec::with_heap_capacity(self.len());
+        for (a, b) in self.iter().zip(other.iter()) {
+            zipped.push(f(a, b));
         }
+        zipped
+    }
+}
 
-        if self.heap.is_empty() {
-            unsafe {
-                let value = self.stack[N - 1].as_ptr().read();
-                self.stack[N - 1] = MaybeUninit::uninit();
-                self.counter -= 1;
-                return value;
-            }
-        } else {
-            let value = self.heap.pop();
-            self.counter -= 1;
-            return value.unwrap_or(T::default());
+impl<X, Y, const N: usize, G: growth::policy, L: index> tinyvec<(X, Y), N, Global, G, L> {
+    /// Split the pairs into two tinyvecs with the same N, each sized
+    /// once up front.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let pairs: tinyvec<(char, u8), 4> = tiny_vec![('a', 1), ('b', 2)];
+    /// let (letters, numbers) = pairs.unzip();
+    /// assert_eq!((letters.as_slice(), numbers.as_slice()), (['a', 'b'].as_slice(), [1, 2].as_slice()));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn unzip(self) -> (tinyvec<X, N, Global, G, L>, tinyvec<Y, N, Global, G, L>) {
+        let mut left = tinyvec::with_heap_capacity(self.len());
+        let mut right = tinyvec::with_heap_capacity(self.len());
+        for (x, y) in self {
+            left.push(x);
+            right.push(y);
         }
+        (left, right)
     }
+}
 
-    /// Extend tinyvec with a `&[T]`.
-    /// Vectors, Arrays, etc. can be coerced into &T,
-    /// so this is a blanket implementation for all them.
-    pub fn extend(&mut self, elements: &[T]) {
-        for i in elements.iter() {
-            self.push(*i);
+impl<T, const N: usize, A: Allocator + Clone, G: growth::policy, L: index> tinyvec<T, N, A, G, L> {
+    /// Opt-in build-time check that this configuration takes at most
+    /// `MAX_BYTES` of stack, so a tinyvec of big elements or a big N
+    /// fails to compile instead of overflowing the stack at runtime.
+    ///
+    /// ```rust
+    /// use vecstor::tinyvec;
+    /// type samples = tinyvec<f32, 32>;
+    /// const _: () = samples::assert_footprint::<256>();
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use vecstor::tinyvec;
+    /// type frames = tinyvec<[u8; 1500], 16>;
+    /// const _: () = frames::assert_footprint::<4096>();
+    /// ```
+    pub const fn assert_footprint<const MAX_BYTES: usize>() {
+        const {
+            assert!(
+                size_of::<Self>() <= MAX_BYTES,
+                "tinyvec: inline footprint over the limit"
+            )
         }
     }
-}
 
-/// Make tinyvec work as:
-/// ```rust
-/// use vecstor::tinyvec;
-/// let mut tinyvecwtor: tinyvec<u8, 256> = tinyvec::new();
-/// for i in 0..=256 {
-///     tinyvecwtor.push(i as u8);
-/// }
-///
-/// for i in tinyvecwtor {
-///     // do something with i
-/// }
-/// ````
-///
-/// Iterator trait also provides free implementation of many
-/// other utility function.
-///
-/// ```rust
-/// use vecstor::tinyvec;
-/// let tinyvector: tinyvec<i32, 1024> = tinyvec::new();
-/// // fill tinyvector
-/// let vector: Vec<i32> = (tinyvector).collect::<Vec<i32>>();
-/// ````
-/// is valid.
-///
-impl<T, const N: usize> iter::Iterator for tinyvec<T, N>
-where
-    T: Default,
-    T: Copy + Display,
-{
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.iters == step_iter::Not {
-            self.iters = step_iter::Yes(0);
-        } else {
-            if let step_iter::Yes(idx) = self.iters {
-                self.iters = step_iter::Yes(idx + 1);
-            }
+    /// New tinyvector whose heap segment lives in `alloc`,
+    /// e.g. a per-frame bump arena.
+    #[cfg(feature = "allocator_api")]
+    pub fn new_in(alloc: A) -> Self {
+        Self::with_heap_capacity_in(0, alloc)
+    }
+
+    /// Like `with_heap_capacity`, reserving in `alloc`.
+    #[cfg(feature = "allocator_api")]
+    pub fn with_heap_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::from_storage(spill::with_alloc(capacity, alloc))
+    }
+
+    /// Slots on the stack and slots allocated on the heap.
+    /// For zero-sized types both are `usize::MAX`.
+    pub fn capacity(&self) -> capacity {
+        let (inline, heap) = self.storage.capacities();
+        capacity { inline, heap }
+    }
+
+    /// Number of elements living on the stack. All of them
+    /// until the first spill, none after it.
+    pub fn stack_len(&self) -> usize {
+        if self.is_spilled() { 0 } else { self.len() }
+    }
+
+    /// Number of elements living on the heap, `len()` once spilled.
+    pub fn heap_len(&self) -> usize {
+        self.len() - self.stack_len()
+    }
+
+    /// Iterate over the elements on the stack, the whole vector
+    /// before it spills and nothing after.
+    pub fn iter_inline(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice()[..self.stack_len()].iter()
+    }
+
+    /// Iterate over the elements on the heap, nothing before
+    /// the vector spills and the whole vector after.
+    pub fn iter_heap(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice()[self.stack_len()..].iter()
+    }
+
+    /// Bytes this tinyvec takes up, see [`memory`].
+    pub fn memory_usage(&self) -> memory {
+        let heap = self.capacity().heap;
+        memory {
+            inline: size_of::<Self>(),
+            heap_allocated: heap.saturating_mul(size_of::<T>()),
+            heap_used: self.heap_len() * size_of::<T>(),
         }
+    }
 
-        if let step_iter::Yes(at) = self.iters {
-            if at <= self.counter {
-                return self.get(at);
+    /// Split into the elements for which `predicate` is true and
+    /// the rest, in one pass and keeping their order.
+    pub fn partition<F: FnMut(&T) -> bool>(self, mut predicate: F) -> (Self, Self) {
+        let mut matching = Self::from_storage(self.storage.empty_like());
+        let mut rest = Self::from_storage(self.storage.empty_like());
+        for element in self {
+            if predicate(&element) {
+                matching.push(element);
+            } else {
+                rest.push(element);
             }
         }
+        (matching, rest)
+    }
 
-        None
+    /// New tinyvec with the contents repeated `n` times.
+    ///
+    /// Panics if the length would overflow.
+    pub fn repeat(&self, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let total = self
+            .len()
+            .checked_mul(n)
+            .expect("repeat: capacity overflow");
+        let mut repeated = Self::from_storage(self.storage.empty_like());
+        if total > self.storage.capacities().0 {
+            repeated.force_spill(total);
+        }
+        for _ in 0..n {
+            repeated.extend_from_slice(self.as_slice());
+        }
+        repeated
     }
-}
 
-/// tinyvec only supports `Copy-Types` as of now.
-impl<T, const N: usize> Display for tinyvec<T, N>
-where
-    T: Copy + Default + Display,
-{
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut res = String::from("[ ");
-        for i in 0..self.counter {
-            let text = format!("{}", self.get(i).unwrap_or(T::default()));
-            res.push_str(&text);
+    /// Keep only the first N elements, drop the rest and free the
+    /// heap allocation.
+    pub fn truncate_to_inline(&mut self) {
+        self.truncate(self.capacity().inline);
+        let moved = self.shrink_to_inline();
+        debug_assert!(moved);
+    }
 
-            if i == self.counter - 1 {
-                res.push_str(" ");
-                break;
-            }
-            res.push_str(", ");
-        }
+    /// Whether the elements are on the heap right now.
+    pub fn is_spilled(&self) -> bool {
+        self.storage.spilled()
+    }
 
-        res.push_str("]");
-        write!(f, "{res}")
+    /// How many times this tinyvec moved from the stack to the heap.
+    /// More than one means it was shrunk back in between.
+    ///
+    /// Needs the `spill_count` feature, which adds a `u32` to every
+    /// tinyvec.
+    #[cfg(feature = "spill_count")]
+    pub fn spill_count(&self) -> u32 {
+        self.storage.spills()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{general_heap, tinyvec};
+    /// The largest length this tinyvec ever reached, e.g. to check in
+    /// a soak test whether N is too small or needlessly large.
+    ///
+    /// Needs the `high_water` feature, which adds a `usize` to every
+    /// tinyvec.
+    #[cfg(feature = "high_water")]
+    pub fn max_len_seen(&self) -> usize {
+        self.storage.high_water()
+    }
 
-    #[test]
-    fn setup() {
-        let mut vector: tinyvec<i32, 1024> = tinyvec::new();
-        vector.push(1);
-        vector.push(2);
-        vector.push(3);
-        let _ = vector.pop();
-        let _ = vector.remove(0);
-        assert_eq!(vector.len(), 1);
-        assert_eq!(vector.capacity(), (1024, 1024));
+    /// Move everything to the heap now and make room there for
+    /// `heap_capacity` elements, so the spill doesn't happen later in
+    /// a latency-sensitive spot. Already spilled, it only reserves.
+    pub fn force_spill(&mut self, heap_capacity: usize) {
+        self.storage.grow_heap(heap_capacity);
     }
 
-    #[test]
-    fn length() {
-        let mut vector: tinyvec<bool, 1024> = tinyvec::new();
-        let _ = vector.pop();
-        assert_eq!(vector.len(), 0);
+    /// The same elements in a tinyvec with K inline slots instead of
+    /// N, e.g. at a library boundary that uses a different size. They
+    /// stay on the heap, without a copy, if they don't fit in K, and
+    /// move inline if they do.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let small: tinyvec<u8, 2> = tiny_vec![1, 2, 3];
+    /// let wide: tinyvec<u8, 8> = small.resize_inline();
+    /// assert!(!wide.is_spilled());
+    /// assert_eq!(wide.as_slice(), [1, 2, 3]);
+    /// ```
+    pub fn resize_inline<const K: usize>(self) -> tinyvec<T, K, A, G, L> {
+        let vec = ManuallyDrop::new(self);
+        let storage = unsafe { std::ptr::read(&vec.storage) };
+        storagevec::from_storage(storage.resize_inline())
     }
 
-    #[test]
-    fn extends() {
-        let mut vector: tinyvec<char, 2048> = tinyvec::new();
-        let slice = ('a'..='z').collect::<Vec<char>>();
-        vector.extend(&slice);
-        assert_eq!(vector.len(), 26);
+    /// Move the elements back onto the stack and free the heap
+    /// allocation, if `len() <= N`. Returns whether the elements
+    /// are on the stack afterwards.
+    pub fn shrink_to_inline(&mut self) -> bool {
+        self.storage.unspill()
     }
+}
 
-    #[test]
-    fn iterate() {
-        let mut vector: tinyvec<i32, 4> = tinyvec::new();
-        let slice: [i32; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
-        vector.extend(&slice);
-        assert_eq!(vector.len(), 8);
-        assert_eq!(vector.capacity(), (4, general_heap));
+impl<T, const N: usize, G: growth::policy> boxedvec<T, N, G> {
+    /// Empty, nothing allocated.
+    pub fn new() -> Self {
+        Self::from_storage(boxed::new())
+    }
 
-        let mut number: i32 = 0;
-        for i in vector {
-            number += i;
+    /// `inline` is the boxed slab, counted once it is allocated.
+    pub fn capacity(&self) -> capacity {
+        let total = self.storage.capacity();
+        match (self.storage.spilled(), self.storage.slab_allocated()) {
+            (true, _) => capacity {
+                inline: N,
+                heap: total,
+            },
+            (false, true) => capacity { inline: N, heap: 0 },
+            (false, false) => capacity { inline: 0, heap: 0 },
         }
+    }
 
-        assert_eq!(number, 36);
+    /// Whether the elements outgrew the slab.
+    pub fn is_spilled(&self) -> bool {
+        self.storage.spilled()
     }
+}
 
-    #[test]
-    fn convert() {
-        // Convert tinyvec to vec
-        let mut tinyvecwtor: tinyvec<i128, 200> = tinyvec::new();
-        for i in 0..200 {
-            tinyvecwtor.push(i as i128);
+impl<T, const N: usize, G: growth::policy> Default for boxedvec<T, N, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, L: index, X> storagevec<T, inline<T, N, L, X>> {
+    /// `tinyvec::assert_footprint()` for inline storage, which also
+    /// rejects N = 0: such a vector can never hold an element.
+    pub const fn assert_footprint<const MAX_BYTES: usize>() {
+        const {
+            assert!(N > 0, "inline storage with N = 0 can't hold anything");
+            assert!(
+                size_of::<Self>() <= MAX_BYTES,
+                "inline storage footprint over the limit"
+            )
         }
+    }
 
-        let vector = tinyvecwtor.collect::<Vec<_>>();
-        assert_eq!(vector.len(), 200);
+    /// New vector that only ever uses its N inline slots.
+    pub fn new() -> Self {
+        Self::from_storage(inline::new())
+    }
+
+    /// Always N.
+    pub fn capacity(&self) -> usize {
+        N
     }
 }
 
-/// Generate `profile`
-///
-/// Profiled based on time taken to load elements
-/// to tinyvec and vec.
-/// Note that though `Vec<T>` is in the std,
-/// `tinyvec<T, N> is faster than Vec<T> in all regards.`
-///
-/// # Profile Table
-///
-/// |tinyvec      |   vec          |   dtype  |
-/// |-------------|----------------|----------|             
-/// |82.971µs     |   96.186µs     |          |
-/// |163.522µs    |   189.874µs    |    i32   |
-/// |245.244µs    |   293.82µs     |          |
-/// |             |                |          |
-/// |84.787µs     |   99.005µs     |          |
-/// |166.622µs    |  205.947µs     |    i64   |
-/// |249.435µs    |   28.471µs     |          |
-/// |             |                |          |
-/// |112.127µs    |  149.791µs     |          |
-/// |198.913µs    |   287.499µs    |     i128 |
-/// |285.452µs    |  527.276µs     |          |
-/// |             |                |          |
-/// |103.187µs    |  103.136µs     |          |
-/// |183.506µs    |  204.389µs     |   bool   |
-/// |278.57µs     |  338.353µs     |          |
-/// |             |                |          |
-/// |94.472µs     |  103.327µs     |          |
-/// |181.874µs    |  266.311µs     |    f32   |
-/// |315.397µs    |  330.904µs     |          |
-/// |             |                |          |
-/// |98.945µs     |  116.915µs     |          |
-/// |184.969µs    |  209.106µs     |    f64   |
-/// |277.405µs    |  322.154µs     |          |
-/// |             |                |          |
-/// |104.565µs    |  107.086µs     |          |
-/// |182.641µs    |  203.016µs     |    char  |
-/// |273.395µs    |  302.796µs     |          |
-///
-pub fn load<T>()
-where
-    T: Copy + Default + std::fmt::Display,
-{
-    let mut t1: tinyvec<T, 5000> = tinyvec::new();
-    let mut t2: tinyvec<T, 10000> = tinyvec::new();
-    let mut t3: tinyvec<T, 15000> = tinyvec::new();
-    let mut v1: Vec<T> = Vec::new();
-    let mut v2: Vec<T> = Vec::new();
-    let mut v3: Vec<T> = Vec::new();
-    let mut begin = Instant::now();
+impl<'a, T> storagevec<T, borrowed<'a, T>> {
+    /// New, empty vector on top of `buf`. Elements still pushed
+    /// when the vector is dropped are dropped with it.
+    pub fn from_buf(buf: &'a mut [MaybeUninit<T>]) -> Self {
+        Self::from_storage(borrowed::new(buf))
+    }
 
-    for i in 0..=5000 {
-        t1.push(T::default());
+    /// New, empty vector reusing an initialized buffer, e.g. a DMA
+    /// buffer. Its current contents count as free slots and get
+    /// overwritten; that's only sound to do for `Copy` types.
+    pub fn from_slice(buf: &'a mut [T]) -> Self
+    where
+        T: Copy,
+    {
+        let len = buf.len();
+        let buf = unsafe { std::slice::from_raw_parts_mut(buf.as_mut_ptr().cast(), len) };
+        Self::from_buf(buf)
     }
 
-    let t_t1 = begin.elapsed();
+    /// Length of the borrowed buffer.
+    pub fn capacity(&self) -> usize {
+        self.storage.capacity()
+    }
+}
 
-    let begin = Instant::now();
-    for i in 0..=5000 {
-        v1.push(T::default());
+impl<T, S: storage<T>> storagevec<T, S> {
+    fn from_storage(storage: S) -> Self {
+        Self {
+            storage,
+            marker: PhantomData,
+        }
     }
 
-    let t_v1 = begin.elapsed();
+    /// Every method leaves `len() <= capacity()` with all of `[0, len)`
+    /// initialized in one contiguous run. Checked in debug builds
+    /// after each mutation.
+    fn check(&self) {
+        debug_assert!(self.storage.len() <= self.storage.capacity());
+    }
 
-    let mut begin = Instant::now();
-    for i in 0..=10000 {
-        t2.push(T::default());
+    /// Return length(stack + heap). For more information
+    /// use capacity().
+    pub fn len(&self) -> usize {
+        self.storage.len()
     }
 
-    let t_t2 = begin.elapsed();
+    /// How many elements fit before the storage has to grow: N while
+    /// a tinyvec is on the stack, the heap capacity once it spilled
+    /// (the inline slots are no longer used then).
+    pub fn total_capacity(&self) -> usize {
+        self.storage.capacity()
+    }
 
-    let begin = Instant::now();
-    for i in 0..=10000 {
-        v2.push(T::default());
+    /// How many more pushes fit without allocating.
+    pub fn remaining_capacity(&self) -> usize {
+        self.storage.capacity() - self.len()
     }
 
-    let t_v2 = begin.elapsed();
+    /// Returns true if tinyvec holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-    let mut begin = Instant::now();
-    for i in 0..=15000 {
-        t3.push(T::default());
+    /// Push value to the end, the value is moved in. A tinyvec
+    /// keeps it on the stack while there is room; after that all
+    /// elements move over to the heap together.
+    ///
+    /// Panics if the storage can't grow.
+    pub fn push(&mut self, element: T) {
+        if !self.storage.reserve(1) {
+            panic!("push: storage is full");
+        }
+
+        let len = self.len();
+        unsafe {
+            self.storage.as_mut_ptr().add(len).write(element);
+            self.storage.set_len(len + 1);
+        }
+        self.check();
     }
 
-    let t_t3 = begin.elapsed();
+    /// Push `element` if there is room or the storage can grow,
+    /// otherwise hand it back in the error.
+    pub fn try_push(&mut self, element: T) -> std::result::Result<(), capacity_error<T>> {
+        if self.storage.try_reserve(1).is_err() {
+            return Err(capacity_error::new(element));
+        }
 
-    let begin = Instant::now();
-    for i in 0..=15000 {
-        v3.push(T::default());
+        let len = self.len();
+        unsafe {
+            self.storage.as_mut_ptr().add(len).write(element);
+            self.storage.set_len(len + 1);
+        }
+        self.check();
+        Ok(())
     }
 
-    let t_v3 = begin.elapsed();
+    /// Make room for `additional` more elements, growing the
+    /// storage if it can.
+    pub fn try_reserve(&mut self, additional: usize) -> std::result::Result<(), try_reserve_error> {
+        self.storage.try_reserve(additional)
+    }
+
+    /// Borrow the element at `at`, works the same for
+    /// `Copy` and move-only types.
+    pub fn get(&self, at: usize) -> Option<&T> {
+        if at >= self.len() {
+            return None;
+        }
+
+        unsafe { Some(&*self.storage.as_ptr().add(at)) }
+    }
+
+    /// Mutably borrow the element at `at`.
+    pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        if at >= self.len() {
+            return None;
+        }
+
+        unsafe { Some(&mut *self.storage.as_mut_ptr().add(at)) }
+    }
+
+    /// Like `get()`, but the error says which index was asked
+    /// for and what the length was.
+    pub fn checked_index(&self, at: usize) -> std::result::Result<&T, index_error> {
+        self.get(at).ok_or(index_error {
+            index: at,
+            len: self.len(),
+        })
+    }
+
+    /// Keep only the elements for which `keep` returns true, in
+    /// order, and return how many were removed.
+    pub fn retain<F: FnMut(&mut T) -> bool>(&mut self, keep: F) -> usize {
+        /// Drops what it is given.
+        struct discard;
+
+        impl<T> Extend<T> for discard {
+            fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                iter.into_iter().for_each(drop);
+            }
+        }
+
+        self.retain_into(keep, &mut discard)
+    }
+
+    /// [`retain`](storagevec::retain), moving the removed elements into
+    /// `removed` (in order) instead of dropping them.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let mut active: tinyvec<u32, 4> = tiny_vec![3, 8, 5, 10];
+    /// let mut expired = Vec::new();
+    /// assert_eq!(active.retain_into(|id| *id < 6, &mut expired), 2);
+    /// assert_eq!(active.as_slice(), [3, 5]);
+    /// assert_eq!(expired, [8, 10]);
+    /// ```
+    pub fn retain_into<F, E>(&mut self, mut keep: F, removed: &mut E) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+        E: Extend<T>,
+    {
+        /// Closes the gap and sets the length when done, or when `keep`
+        /// or `removed` panics.
+        struct compact<'a, T, S: storage<T>> {
+            storage: &'a mut S,
+            len: usize,
+            processed: usize,
+            deleted: usize,
+            marker: PhantomData<T>,
+        }
+
+        impl<T, S: storage<T>> Drop for compact<'_, T, S> {
+            fn drop(&mut self) {
+                unsafe {
+                    let base = self.storage.as_mut_ptr();
+                    let unprocessed = self.len - self.processed;
+                    std::ptr::copy(
+                        base.add(self.processed),
+                        base.add(self.processed - self.deleted),
+                        unprocessed,
+                    );
+                    self.storage.set_len(self.len - self.deleted);
+                }
+            }
+        }
+
+        let len = self.len();
+        // Nothing is visible while elements are being moved around.
+        unsafe { self.storage.set_len(0) };
+        let mut state = compact {
+            storage: &mut self.storage,
+            len,
+            processed: 0,
+            deleted: 0,
+            marker: PhantomData,
+        };
+        while state.processed < len {
+            let current = unsafe { &mut *state.storage.as_mut_ptr().add(state.processed) };
+            if keep(current) {
+                if state.deleted > 0 {
+                    unsafe {
+                        let base = state.storage.as_mut_ptr();
+                        let hole = base.add(state.processed - state.deleted);
+                        std::ptr::copy_nonoverlapping(base.add(state.processed), hole, 1);
+                    }
+                }
+                state.processed += 1;
+            } else {
+                let element = unsafe { std::ptr::read(current) };
+                state.processed += 1;
+                state.deleted += 1;
+                removed.extend(Some(element));
+            }
+        }
+        let deleted = state.deleted;
+        drop(state);
+        self.storage.after_remove();
+        self.check();
+        deleted
+    }
+
+    /// Exchange the contents with `other` element by element, like
+    /// `slice::swap_with_slice`. Panics if the lengths differ.
+    pub fn swap_with_slice(&mut self, other: &mut [T]) {
+        self.as_mut_slice().swap_with_slice(other);
+    }
+
+    /// Merge the elements of `other` in, both vectors being sorted
+    /// ascending, in a single pass from the back without a scratch
+    /// buffer. Equal elements of `self` come first.
+    ///
+    /// Panics if the storage can't hold both.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let mut left: tinyvec<u32, 8> = tiny_vec![1, 4, 9];
+    /// let right: tinyvec<u32, 8> = tiny_vec![2, 4, 10];
+    /// left.merge_with(right);
+    /// assert_eq!(left.as_slice(), [1, 2, 4, 4, 9, 10]);
+    /// ```
+    pub fn merge_with<R: storage<T>>(&mut self, mut other: storagevec<T, R>)
+    where
+        T: Ord,
+    {
+        /// Filled from the back: `[i + j, total)` is merged, `self[..i]`
+        /// and `other[..j]` are left. Those two runs end up adjacent
+        /// when done, or when a comparison panics.
+        struct merge<'a, T, S: storage<T>> {
+            storage: &'a mut S,
+            other: *const T,
+            i: usize,
+            j: usize,
+            total: usize,
+        }
+
+        impl<T, S: storage<T>> Drop for merge<'_, T, S> {
+            fn drop(&mut self) {
+                unsafe {
+                    let base = self.storage.as_mut_ptr();
+                    std::ptr::copy_nonoverlapping(self.other, base.add(self.i), self.j);
+                    self.storage.set_len(self.total);
+                }
+            }
+        }
+
+        let (len, other_len) = (self.len(), other.len());
+        if !self.storage.reserve(other_len) {
+            panic!("merge_with: storage is full");
+        }
+        // The elements belong to the guard from here on.
+        unsafe {
+            self.storage.set_len(0);
+            other.storage.set_len(0);
+        }
+        let mut merge = merge {
+            storage: &mut self.storage,
+            other: other.storage.as_ptr(),
+            i: len,
+            j: other_len,
+            total: len + other_len,
+        };
+        unsafe {
+            let base = merge.storage.as_mut_ptr();
+            while merge.i > 0 && merge.j > 0 {
+                let mine = base.add(merge.i - 1);
+                let theirs = merge.other.add(merge.j - 1);
+                let to = base.add(merge.i + merge.j - 1);
+                if *theirs < *mine {
+                    std::ptr::copy(mine, to, 1);
+                    merge.i -= 1;
+                } else {
+                    std::ptr::copy_nonoverlapping(theirs, to, 1);
+                    merge.j -= 1;
+                }
+            }
+        }
+        drop(merge);
+        self.check();
+    }
+
+    /// `merge_with()`, returning the merged vector.
+    pub fn merge<R: storage<T>>(mut self, other: storagevec<T, R>) -> Self
+    where
+        T: Ord,
+    {
+        self.merge_with(other);
+        self
+    }
+
+    /// Whether the elements are in ascending order, like
+    /// `slice::is_sorted`.
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.as_slice().is_sorted()
+    }
+
+    /// Whether `compare` holds for every pair of neighbours.
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, compare: F) -> bool {
+        self.as_slice().is_sorted_by(compare)
+    }
+
+    pub fn is_sorted_by_key<K: PartialOrd, F: FnMut(&T) -> K>(&self, key: F) -> bool {
+        self.as_slice().is_sorted_by_key(key)
+    }
+
+    /// Sort without keeping equal elements in order, like
+    /// `slice::sort_unstable`. Up to 8 elements, the lengths this crate
+    /// is made for, a sorting network does it without the general
+    /// sort's branching.
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_unstable_by(T::cmp);
+    }
+
+    pub fn sort_unstable_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(&mut self, mut compare: F) {
+        let slice = self.as_mut_slice();
+        if slice.len() <= network::MAX {
+            network::sort(slice, &mut |a, b| compare(a, b).is_lt());
+        } else {
+            slice.sort_unstable_by(compare);
+        }
+    }
+
+    pub fn sort_unstable_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.sort_unstable_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Reorder so the element at `index` is the one a full sort would
+    /// put there, with nothing greater before it and nothing smaller
+    /// after, like `slice::select_nth_unstable`. Returns the part
+    /// before, the element and the part after.
+    ///
+    /// Panics if `index >= len()`.
+    pub fn select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().select_nth_unstable(index)
+    }
+
+    pub fn select_nth_unstable_by<F: FnMut(&T, &T) -> std::cmp::Ordering>(
+        &mut self,
+        index: usize,
+        compare: F,
+    ) -> (&mut [T], &mut T, &mut [T]) {
+        self.as_mut_slice().select_nth_unstable_by(index, compare)
+    }
+
+    pub fn select_nth_unstable_by_key<K: Ord, F: FnMut(&T) -> K>(
+        &mut self,
+        index: usize,
+        key: F,
+    ) -> (&mut [T], &mut T, &mut [T]) {
+        self.as_mut_slice().select_nth_unstable_by_key(index, key)
+    }
+
+    /// Cursor starting at the first element, for in-place edits while
+    /// walking the vector.
+    pub fn cursor_mut(&mut self) -> cursor_mut<'_, T, S> {
+        cursor_mut { vec: self, at: 0 }
+    }
+
+    /// Remove `range` and iterate over the removed elements; the tail
+    /// shifts down when the iterator is dropped. Panics if `range` is
+    /// out of bounds.
+    ///
+    /// The length is cut to `range.start` up front, so if the iterator
+    /// is leaked with `mem::forget` the vector just loses the drained
+    /// elements and the tail, and never exposes moved-out slots.
+    pub fn drain<R: std::ops::RangeBounds<usize>>(&mut self, range: R) -> drain<'_, T, S> {
+        let len = self.len();
+        let std::ops::Range { start, end } = bounds(&range, len, "drain");
+        unsafe { self.storage.set_len(start) };
+        drain {
+            vec: self,
+            at: start,
+            end,
+            tail: end,
+            tail_len: len - end,
+        }
+    }
+
+    /// Returns Option instead of `T`. The element is moved out,
+    /// and everything after it shifts down by one. Only the
+    /// initialized elements `[index, len)` are touched, and it makes no
+    /// difference whether they sit on the stack or the heap since a
+    /// tinyvec keeps all of them in one place.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+
+        let value = unsafe {
+            let base = self.storage.as_mut_ptr();
+            let value = base.add(index).read();
+            std::ptr::copy(base.add(index + 1), base.add(index), len - index - 1);
+            self.storage.set_len(len - 1);
+            value
+        };
+        self.storage.after_remove();
+        self.check();
+        Some(value)
+    }
+
+    /// Remove the first element equal to `value`, shifting the rest
+    /// down.
+    pub fn remove_item<U: ?Sized>(&mut self, value: &U) -> Option<T>
+    where
+        T: PartialEq<U>,
+    {
+        let at = self.iter().position(|element| element == value)?;
+        self.remove(at)
+    }
+
+    /// Returns the last element, or None if tinyvec is empty.
+    /// Always the last one pushed that is still there, whatever the
+    /// fill level and wherever the elements live.
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        let value = unsafe {
+            self.storage.set_len(len - 1);
+            self.storage.as_ptr().add(len - 1).read()
+        };
+        self.storage.after_remove();
+        self.check();
+        Some(value)
+    }
+
+    /// Shorten tinyvec to `len` elements, dropping the rest.
+    /// Does nothing if `len >= self.len()`.
+    pub fn truncate(&mut self, len: usize) {
+        let old = self.len();
+        if len >= old {
+            return;
+        }
+
+        // Shrink first so a panicking destructor can't lead to a double drop.
+        unsafe {
+            self.storage.set_len(len);
+            let tail =
+                std::ptr::slice_from_raw_parts_mut(self.storage.as_mut_ptr().add(len), old - len);
+            std::ptr::drop_in_place(tail);
+        }
+        self.storage.after_remove();
+        self.check();
+    }
+
+    /// Drop every element, keeping the heap allocation unless the
+    /// growth policy shrinks it, see [`growth::shrinking`].
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Consume the vector in batches of K elements, the last one
+    /// possibly shorter. Panics at compile time if K is 0.
+    pub fn into_chunks<const K: usize>(self) -> into_chunks<owned_iter<T, S>, K> {
+        into_chunks::new(self)
+    }
+
+    /// Move every element to the end of `out` with one reservation
+    /// and one bulk copy, leaving this vector empty with its storage,
+    /// inline or heap, kept for reuse.
+    pub fn drain_into(&mut self, out: &mut Vec<T>) {
+        let len = self.len();
+        out.reserve(len);
+        unsafe {
+            self.storage.set_len(0);
+            std::ptr::copy_nonoverlapping(
+                self.storage.as_ptr(),
+                out.as_mut_ptr().add(out.len()),
+                len,
+            );
+            out.set_len(out.len() + len);
+        }
+        self.check();
+    }
+
+    /// Iterate over the elements by reference.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Iterate over the elements by mutable reference.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Call `f` on every element in order, by mutable reference.
+    pub fn for_each_mut<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.iter_mut().for_each(f);
+    }
+
+    /// Replace every element with `f` of it, in place: no second
+    /// buffer, even for a spilled vector. If `f` panics, the element
+    /// it was given and those after it are dropped.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let mut names: tinyvec<String, 2> = tiny_vec!["ada".to_string(), "bob".into()];
+    /// names.map_in_place(|name| name.to_uppercase());
+    /// assert_eq!(names.as_slice(), ["ADA", "BOB"]);
+    /// ```
+    pub fn map_in_place<F: FnMut(T) -> T>(&mut self, mut f: F) {
+        /// Drops the not yet mapped elements if `f` panics.
+        struct unmapped<'a, T, S: storage<T>> {
+            storage: &'a mut S,
+            at: usize,
+            len: usize,
+            marker: PhantomData<T>,
+        }
+
+        impl<T, S: storage<T>> Drop for unmapped<'_, T, S> {
+            fn drop(&mut self) {
+                unsafe {
+                    self.storage.set_len(self.at);
+                    let rest = self.storage.as_mut_ptr().add(self.at + 1);
+                    let left = self.len - self.at - 1;
+                    std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(rest, left));
+                }
+            }
+        }
+
+        let len = self.len();
+        let mut guard = unmapped {
+            storage: &mut self.storage,
+            at: 0,
+            len,
+            marker: PhantomData,
+        };
+        while guard.at < len {
+            unsafe {
+                let slot = guard.storage.as_mut_ptr().add(guard.at);
+                slot.write(f(slot.read()));
+            }
+            guard.at += 1;
+        }
+        std::mem::forget(guard);
+    }
+
+    /// Display the elements between `open` and `close`, with
+    /// `separator` in between, e.g. `display_with(",", "", "")` for a
+    /// CSV row. Format flags apply to each element.
+    #[cfg(feature = "fmt")]
+    pub fn display_with<'a>(
+        &'a self,
+        separator: &'a str,
+        open: &'a str,
+        close: &'a str,
+    ) -> displayed<'a, T, S>
+    where
+        T: Display,
+    {
+        displayed {
+            vector: self,
+            separator,
+            open,
+            close,
+        }
+    }
+
+    /// View the elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.storage.as_ptr(), self.len()) }
+    }
+
+    /// View the elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        unsafe { std::slice::from_raw_parts_mut(self.storage.as_mut_ptr(), len) }
+    }
+
+    /// The elements as an unaligned prefix, as many whole SIMD
+    /// vectors of LANES as fit, and the rest, like `slice::as_simd`.
+    /// With an [`align`] marker matching the vector alignment the
+    /// prefix of an inline buffer is empty.
+    ///
+    /// Needs the nightly-only `portable_simd` feature.
+    #[cfg(feature = "portable_simd")]
+    pub fn as_simd<const LANES: usize>(&self) -> (&[T], &[std::simd::Simd<T, LANES>], &[T])
+    where
+        T: std::simd::SimdElement,
+    {
+        self.as_slice().as_simd()
+    }
+
+    /// `as_simd()` with mutable access.
+    #[cfg(feature = "portable_simd")]
+    pub fn as_simd_mut<const LANES: usize>(
+        &mut self,
+    ) -> (&mut [T], &mut [std::simd::Simd<T, LANES>], &mut [T])
+    where
+        T: std::simd::SimdElement,
+    {
+        self.as_mut_slice().as_simd_mut()
+    }
+
+    /// The first element and the rest, or None if empty.
+    pub fn split_first(&self) -> Option<(&T, &[T])> {
+        self.as_slice().split_first()
+    }
+
+    /// Mutable `split_first()`.
+    pub fn split_first_mut(&mut self) -> Option<(&mut T, &mut [T])> {
+        self.as_mut_slice().split_first_mut()
+    }
+
+    /// The last element and everything before it, or None if empty.
+    pub fn split_last(&self) -> Option<(&T, &[T])> {
+        self.as_slice().split_last()
+    }
+
+    /// Mutable `split_last()`.
+    pub fn split_last_mut(&mut self) -> Option<(&mut T, &mut [T])> {
+        self.as_mut_slice().split_last_mut()
+    }
+
+    /// Flatten the pieces into one tinyvec with K inline slots,
+    /// e.g. a tinyvec of tinyvecs or of slices.
+    pub fn concat<U: Clone, const K: usize>(&self) -> tinyvec<U, K>
+    where
+        T: AsRef<[U]>,
+    {
+        let total = self.iter().map(|piece| piece.as_ref().len()).sum();
+        let mut flat = tinyvec::with_heap_capacity(total);
+        for piece in self.iter() {
+            flat.extend_from_slice(piece.as_ref());
+        }
+        flat
+    }
+
+    /// `concat()` with a clone of `separator` between the pieces.
+    pub fn join<U: Clone, const K: usize>(&self, separator: &U) -> tinyvec<U, K>
+    where
+        T: AsRef<[U]>,
+    {
+        let total = self.iter().map(|piece| piece.as_ref().len()).sum::<usize>()
+            + self.len().saturating_sub(1);
+        let mut flat = tinyvec::with_heap_capacity(total);
+        for (i, piece) in self.iter().enumerate() {
+            if i > 0 {
+                flat.push(separator.clone());
+            }
+            flat.extend_from_slice(piece.as_ref());
+        }
+        flat
+    }
+
+    /// Gather clones of the elements at `indices`, in that order, into
+    /// a tinyvec with K inline slots. Indices may repeat, e.g. to
+    /// project columns out of a row.
+    ///
+    /// Panics if an index is out of bounds; `try_select()` doesn't.
+    pub fn select<const K: usize, I: IntoIterator<Item = usize>>(&self, indices: I) -> tinyvec<T, K>
+    where
+        T: Clone,
+    {
+        match self.try_select(indices) {
+            Ok(selected) => selected,
+            Err(error) => panic!("select: {error}"),
+        }
+    }
+
+    /// `select()`, failing on the first index past the end.
+    pub fn try_select<const K: usize, I: IntoIterator<Item = usize>>(
+        &self,
+        indices: I,
+    ) -> std::result::Result<tinyvec<T, K>, index_error>
+    where
+        T: Clone,
+    {
+        let mut selected = tinyvec::new();
+        for at in indices {
+            selected.push(self.checked_index(at)?.clone());
+        }
+        Ok(selected)
+    }
+
+    /// Mutable references to the K elements at `indices` at once,
+    /// after checking that they're in bounds and distinct, like the
+    /// slice `get_disjoint_mut()`.
+    ///
+    /// ```rust
+    /// use vecstor::{disjoint_error, tiny_vec, tinyvec};
+    /// let mut balances: tinyvec<u32, 4> = tiny_vec![50, 10, 0];
+    /// let [from, to] = balances.get_many_mut([0, 2]).unwrap();
+    /// (*from, *to) = (*from - 20, *to + 20);
+    /// assert_eq!(balances.as_slice(), [30, 10, 20]);
+    /// assert_eq!(
+    ///     balances.get_many_mut([1, 1]).unwrap_err(),
+    ///     disjoint_error::overlapping { index: 1 }
+    /// );
+    /// ```
+    pub fn get_many_mut<const K: usize>(
+        &mut self,
+        indices: [usize; K],
+    ) -> std::result::Result<[&mut T; K], disjoint_error> {
+        for (i, &at) in indices.iter().enumerate() {
+            self.checked_index(at)?;
+            if indices[..i].contains(&at) {
+                return Err(disjoint_error::overlapping { index: at });
+            }
+        }
+        // In bounds and distinct, checked above.
+        Ok(unsafe { self.as_mut_slice().get_disjoint_unchecked_mut(indices) })
+    }
+
+    /// Sum of the elements, without consuming the vector. Anything
+    /// that sums `&T`, e.g. `let total: u32 = v.sum();`.
+    pub fn sum<'a, R: iter::Sum<&'a T>>(&'a self) -> R {
+        self.iter().sum()
+    }
+
+    /// Smallest element, the first one on ties.
+    pub fn min(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Largest element, the last one on ties.
+    pub fn max(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Iterate over runs of consecutive elements for which
+    /// `same(a, b)` holds between neighbours, like `slice::chunk_by`.
+    pub fn chunk_by<F>(&self, same: F) -> std::slice::ChunkBy<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.as_slice().chunk_by(same)
+    }
+
+    /// Run-length view: each run of equal neighbours as its first
+    /// element and its length.
+    pub fn runs(&self) -> impl Iterator<Item = (&T, usize)>
+    where
+        T: PartialEq,
+    {
+        self.chunk_by(|a, b| a == b).map(|run| (&run[0], run.len()))
+    }
+
+    /// Collapse runs of equal neighbours into `(value, count)` pairs,
+    /// keeping the first element of each run.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinyvec};
+    /// let scanline: tinyvec<u8, 8> = tiny_vec![0, 0, 0, 7, 7, 0];
+    /// let runs: tinyvec<(u8, usize), 4> = scanline.dedup_with_counts();
+    /// assert_eq!(runs.as_slice(), [(0, 3), (7, 2), (0, 1)]);
+    /// ```
+    pub fn dedup_with_counts<const K: usize>(self) -> tinyvec<(T, usize), K>
+    where
+        T: PartialEq,
+    {
+        let mut runs: tinyvec<(T, usize), K> = tinyvec::new();
+        for element in self {
+            match runs.as_mut_slice().last_mut() {
+                Some((value, count)) if *value == element => *count += 1,
+                _ => runs.push((element, 1)),
+            }
+        }
+        runs
+    }
+
+    /// Iterate over the pieces between elements matching
+    /// `is_separator`, like `slice::split`. Separators are left out,
+    /// so adjacent ones give empty pieces.
+    pub fn split<F>(&self, is_separator: F) -> std::slice::Split<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.as_slice().split(is_separator)
+    }
+
+    /// Iterate over every run of `size` neighbouring elements,
+    /// overlapping, like `slice::windows`. The elements are always
+    /// contiguous, so each window is a plain slice.
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> std::slice::Windows<'_, T> {
+        self.as_slice().windows(size)
+    }
+
+    /// `windows(K)` with each window as an array, so the size is
+    /// checked at compile time and the elements can be destructured.
+    ///
+    /// Panics if `K` is 0.
+    pub fn array_windows<const K: usize>(&self) -> std::slice::ArrayWindows<'_, T, K> {
+        self.as_slice().array_windows()
+    }
+
+    /// Iterate `size` elements at a time, the last chunk may be
+    /// shorter.
+    ///
+    /// Panics if `size` is 0, as do the other chunk iterators.
+    pub fn chunks(&self, size: usize) -> std::slice::Chunks<'_, T> {
+        self.as_slice().chunks(size)
+    }
+
+    /// Mutable `chunks()`.
+    pub fn chunks_mut(&mut self, size: usize) -> std::slice::ChunksMut<'_, T> {
+        self.as_mut_slice().chunks_mut(size)
+    }
+
+    /// Iterate exactly `size` elements at a time; the leftover
+    /// elements are in the iterator's `remainder()`.
+    pub fn chunks_exact(&self, size: usize) -> std::slice::ChunksExact<'_, T> {
+        self.as_slice().chunks_exact(size)
+    }
+
+    /// The first `K` elements as an array, or `None` if there are
+    /// fewer.
+    pub fn first_chunk<const K: usize>(&self) -> Option<&[T; K]> {
+        self.as_slice().first_chunk()
+    }
+
+    pub fn first_chunk_mut<const K: usize>(&mut self) -> Option<&mut [T; K]> {
+        self.as_mut_slice().first_chunk_mut()
+    }
+
+    /// The last `K` elements as an array, or `None` if there are
+    /// fewer.
+    pub fn last_chunk<const K: usize>(&self) -> Option<&[T; K]> {
+        self.as_slice().last_chunk()
+    }
+
+    pub fn last_chunk_mut<const K: usize>(&mut self) -> Option<&mut [T; K]> {
+        self.as_mut_slice().last_chunk_mut()
+    }
+
+    /// The elements as `K`-arrays from the front, and the fewer than
+    /// `K` left over. Panics if `K` is 0.
+    pub fn as_chunks<const K: usize>(&self) -> (&[[T; K]], &[T]) {
+        self.as_slice().as_chunks()
+    }
+
+    pub fn as_chunks_mut<const K: usize>(&mut self) -> (&mut [[T; K]], &mut [T]) {
+        self.as_mut_slice().as_chunks_mut()
+    }
+
+    /// Whether some element equals `x`.
+    ///
+    /// Runs on the slice, so it picks up std's fast paths, e.g.
+    /// `memchr` for `u8` elements.
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.as_slice().contains(x)
+    }
+
+    /// Position of the first element equal to `x`.
+    pub fn index_of(&self, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().position(|element| element == x)
+    }
+
+    /// Number of elements equal to `x`.
+    pub fn count_of(&self, x: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(|element| *element == x).count()
+    }
+
+    /// Overwrite every element with a clone of `value`. For
+    /// byte-sized `Copy` types this is a `memset`, for other
+    /// primitives a vectorized loop.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.as_mut_slice().fill(value);
+    }
+
+    /// Insert `element` at `index`, shifting everything after it up
+    /// by one.
+    ///
+    /// Panics if `index > len()` or the storage can't grow.
+    pub fn insert(&mut self, index: usize, element: T) {
+        if self.try_insert(index, element).is_err() {
+            panic!("insert: storage is full");
+        }
+    }
+
+    /// Like `insert()`, but hands `element` back if the storage is
+    /// full and can't grow.
+    ///
+    /// Still panics if `index > len()`.
+    pub fn try_insert(
+        &mut self,
+        index: usize,
+        element: T,
+    ) -> std::result::Result<(), capacity_error<T>> {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insert: index {index} out of bounds (len {len})"
+        );
+        if self.storage.try_reserve(1).is_err() {
+            return Err(capacity_error::new(element));
+        }
+
+        unsafe {
+            let base = self.storage.as_mut_ptr();
+            std::ptr::copy(base.add(index), base.add(index + 1), len - index);
+            base.add(index).write(element);
+            self.storage.set_len(len + 1);
+        }
+        self.check();
+        Ok(())
+    }
+
+    /// Insert every item of `elements` at `index`, in order.
+    ///
+    /// Items are appended and then rotated into place, so if the
+    /// iterator panics the vector still holds all of its old elements
+    /// plus whatever was yielded so far, just not in the final order.
+    ///
+    /// Panics if `index > len()` or the storage can't grow.
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, index: usize, elements: I) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insert_many: index {index} out of bounds (len {len})"
+        );
+
+        for element in elements {
+            self.push(element);
+        }
+        self.as_mut_slice()[index..].rotate_left(len - index);
+    }
+
+    /// Resize in place to `new_len`, filling new slots with `f()`
+    /// or dropping the extra elements.
+    ///
+    /// If `f` panics, the elements written so far are kept.
+    ///
+    /// Panics if the storage can't grow.
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+        if !self.storage.reserve(new_len - len) {
+            panic!("resize_with: storage is full");
+        }
+
+        let mut guard = set_len_on_drop::new(&mut self.storage);
+        while guard.len < new_len {
+            unsafe { guard.write(f()) };
+        }
+        drop(guard);
+        self.check();
+    }
+
+    /// Append every item of `iter`, or nothing at all: if one doesn't
+    /// fit (and the storage can't grow), the items appended so far
+    /// are dropped again and the one that didn't fit is returned.
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> std::result::Result<(), capacity_error<T>> {
+        let len = self.len();
+        let iter = iter.into_iter();
+        // The hint may be wrong, so a failure here isn't final.
+        let _ = self.storage.try_reserve(iter.size_hint().0);
+
+        for element in iter {
+            if let Err(error) = self.try_push(element) {
+                self.truncate(len);
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Move items from `iter` into the room that is already there,
+    /// never growing the storage. Stops before pulling an item it has
+    /// no slot for, and returns how many were written along with the
+    /// rest of the iterator.
+    pub fn extend_within_capacity<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> (usize, I::IntoIter) {
+        let mut iter = iter.into_iter();
+        let capacity = self.storage.capacity();
+        let mut guard = set_len_on_drop::new(&mut self.storage);
+        let start = guard.len;
+        while guard.len < capacity {
+            let Some(element) = iter.next() else {
+                break;
+            };
+            unsafe { guard.write(element) };
+        }
+        let written = guard.len - start;
+        drop(guard);
+        self.check();
+        (written, iter)
+    }
+
+    /// Resize in place to `new_len`, filling new slots with clones
+    /// of `value` (the last one gets `value` itself) or dropping the
+    /// extra elements.
+    ///
+    /// Panics if the storage can't grow.
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+
+        let mut value = Some(value);
+        let mut left = new_len - len;
+        self.resize_with(new_len, || {
+            left -= 1;
+            match left {
+                0 => value.take().unwrap(),
+                _ => value.as_ref().unwrap().clone(),
+            }
+        });
+    }
+
+    /// Append clones of `elements`.
+    ///
+    /// Room is reserved once up front, then elements are cloned in.
+    /// If a `clone()` panics, the ones cloned so far are kept.
+    ///
+    /// Panics if the storage can't grow.
+    pub fn extend_from_slice(&mut self, elements: &[T])
+    where
+        T: Clone,
+    {
+        if !self.storage.reserve(elements.len()) {
+            panic!("extend: storage is full");
+        }
+
+        let mut guard = set_len_on_drop::new(&mut self.storage);
+        for element in elements {
+            unsafe { guard.write(element.clone()) };
+        }
+        drop(guard);
+        self.check();
+    }
+}
+
+/// Floats are only `PartialOrd`, so `sort_unstable()` is out; these
+/// sort by `total_cmp`: negative NaNs first, then -inf up to -0.0
+/// before 0.0, up to inf, then positive NaNs.
+macro_rules! impl_sort_floats {
+    ($($float:ident),+) => {$(
+        impl<S: storage<$float>> storagevec<$float, S> {
+            pub fn sort_floats(&mut self) {
+                self.sort_unstable_by($float::total_cmp);
+            }
+        }
+    )+};
+}
+
+impl_sort_floats!(f32, f64);
+
+/// Writes straight into reserved slots and commits the length
+/// when dropped, so a panic halfway through a bulk write leaves
+/// exactly the elements written so far.
+struct set_len_on_drop<'a, T, S: storage<T>> {
+    storage: &'a mut S,
+    len: usize,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T, S: storage<T>> set_len_on_drop<'a, T, S> {
+    fn new(storage: &'a mut S) -> Self {
+        let len = storage.len();
+        Self {
+            storage,
+            len,
+            marker: PhantomData,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `len < capacity()`.
+    unsafe fn write(&mut self, element: T) {
+        unsafe { self.storage.as_mut_ptr().add(self.len).write(element) };
+        self.len += 1;
+    }
+}
+
+impl<T, S: storage<T>> Drop for set_len_on_drop<'_, T, S> {
+    fn drop(&mut self) {
+        unsafe { self.storage.set_len(self.len) };
+    }
+}
+
+/// Drops the initialized elements; the storage then frees
+/// whatever memory it owns.
+impl<T, S: storage<T>> Drop for storagevec<T, S> {
+    fn drop(&mut self) {
+        // Not `clear()`: no `after_remove()`, the storage is about to go.
+        unsafe {
+            let len = self.len();
+            self.storage.set_len(0);
+            let all = std::ptr::slice_from_raw_parts_mut(self.storage.as_mut_ptr(), len);
+            std::ptr::drop_in_place(all);
+        }
+    }
+}
+
+/// `a + b` appends the elements of `b` to `a`, moving them.
+/// Like `push()`, panics if `a` can't grow.
+impl<T, S: storage<T>, R: storage<T>> std::ops::Add<storagevec<T, R>> for storagevec<T, S> {
+    type Output = Self;
+
+    fn add(mut self, rhs: storagevec<T, R>) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<T, S: storage<T>, R: storage<T>> std::ops::AddAssign<storagevec<T, R>> for storagevec<T, S> {
+    fn add_assign(&mut self, rhs: storagevec<T, R>) {
+        self.extend(rhs);
+    }
+}
+
+/// `a + &[..]` appends clones of the slice.
+impl<T: Clone, S: storage<T>> std::ops::Add<&[T]> for storagevec<T, S> {
+    type Output = Self;
+
+    fn add(mut self, rhs: &[T]) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: Clone, S: storage<T>> std::ops::AddAssign<&[T]> for storagevec<T, S> {
+    fn add_assign(&mut self, rhs: &[T]) {
+        self.extend_from_slice(rhs);
+    }
+}
+
+impl<T, S: storage<T>> AsRef<[T]> for storagevec<T, S> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, S: storage<T>> AsMut<[T]> for storagevec<T, S> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+/// Compares elements only, so vectors over different storages
+/// compare equal if they hold the same values. Primitive elements
+/// go through std's `memcmp` based slice comparison.
+impl<T, U, S: storage<T>, R: storage<U>> PartialEq<storagevec<U, R>> for storagevec<T, S>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &storagevec<U, R>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, S: storage<T>> Eq for storagevec<T, S> {}
+
+impl<T, U, S: storage<T>> PartialEq<[U]> for storagevec<T, S>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T, U, S: storage<T>, const M: usize> PartialEq<[U; M]> for storagevec<T, S>
+where
+    T: PartialEq<U>,
+{
+    fn eq(&self, other: &[U; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T, const N: usize, G: growth::policy, L: index> Default for tinyvec<T, N, Global, G, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, L: index, X> Default for storagevec<T, inline<T, N, L, X>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reserves the iterator's lower size hint once, then writes
+/// straight into the reserved slots without a capacity check per
+/// element. Items past the hint fall back to `push()`, reserving for
+/// the rest of the hint again each time the storage fills up.
+impl<T, S: storage<T>> Extend<T> for storagevec<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        if !self.storage.reserve(lower) {
+            panic!("extend: storage is full");
+        }
+
+        let capacity = self.storage.capacity();
+        let mut guard = set_len_on_drop::new(&mut self.storage);
+        while guard.len < capacity {
+            let Some(element) = iter.next() else {
+                // Done; the iterator may not be fused, so don't poll it again.
+                drop(guard);
+                self.check();
+                return;
+            };
+            unsafe { guard.write(element) };
+        }
+        drop(guard);
+        self.check();
+
+        // The hint was low. Ask again whenever full, so one reserve
+        // covers what the iterator now says is left.
+        while let Some(element) = iter.next() {
+            if self.len() == self.storage.capacity() {
+                let (lower, _) = iter.size_hint();
+                if !self.storage.reserve(lower.saturating_add(1)) {
+                    panic!("extend: storage is full");
+                }
+            }
+            self.push(element);
+        }
+    }
+
+    #[cfg(feature = "extend_one")]
+    fn extend_one(&mut self, element: T) {
+        self.push(element);
+    }
+
+    /// Panics if the storage can't grow, like `extend`.
+    #[cfg(feature = "extend_one")]
+    fn extend_reserve(&mut self, additional: usize) {
+        if !self.storage.reserve(additional) {
+            panic!("extend: storage is full");
+        }
+    }
+}
+
+/// Clones the elements, so `vec.extend(map.values())` works
+/// without `.cloned()`.
+impl<'a, T: Clone + 'a, S: storage<T>> Extend<&'a T> for storagevec<T, S> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        Extend::extend(self, iter.into_iter().cloned());
+    }
+
+    #[cfg(feature = "extend_one")]
+    fn extend_one(&mut self, element: &'a T) {
+        self.push(element.clone());
+    }
+
+    #[cfg(feature = "extend_one")]
+    fn extend_reserve(&mut self, additional: usize) {
+        Extend::<T>::extend_reserve(self, additional);
+    }
+}
+
+/// Starts out on the heap right away if the size hint says the
+/// stack won't be enough.
+///
+/// Through std's blanket impls this also collects iterators of
+/// `Result<T, E>` into `Result<tinyvec, E>` (stopping at the first
+/// error) and of `Option<T>` into `Option<tinyvec>`.
+impl<T, const N: usize, G: growth::policy, L: index> FromIterator<T>
+    for tinyvec<T, N, Global, G, L>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut vector = Self::with_heap_capacity(iter.size_hint().0);
+        vector.extend(iter);
+        vector
+    }
+}
+
+/// Panics if the iterator yields more than N items.
+impl<T, const N: usize, L: index, X> FromIterator<T> for storagevec<T, inline<T, N, L, X>> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Self::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+/// Borrowed slices are copied, inline if they fit; an owned `Vec`'s
+/// elements are moved over.
+impl<'a, T: Clone, const N: usize, G: growth::policy, L: index> From<std::borrow::Cow<'a, [T]>>
+    for tinyvec<T, N, Global, G, L>
+{
+    fn from(cow: std::borrow::Cow<'a, [T]>) -> Self {
+        match cow {
+            std::borrow::Cow::Borrowed(slice) => {
+                let mut vector = Self::new();
+                vector.extend_from_slice(slice);
+                vector
+            }
+            std::borrow::Cow::Owned(vec) => vec.into_iter().collect(),
+        }
+    }
+}
+
+/// Reuses the allocation of `vec` when it doesn't fit inline; short
+/// ones are moved onto the stack.
+impl<T, const N: usize, G: growth::policy, L: index> From<Vec<T>> for tinyvec<T, N, Global, G, L> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_storage(spill::from_vec(vec))
+    }
+}
+
+impl<T, const N: usize, G: growth::policy, L: index> tinyvec<T, N, Global, G, L> {
+    /// `Vec` of the elements, taking over the heap allocation when
+    /// spilled.
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        let vec = ManuallyDrop::new(self);
+        unsafe { std::ptr::read(&vec.storage) }.into_vec()
+    }
+
+    /// Run `f` on the elements as a `Vec`, for the `Vec`-only APIs,
+    /// and write the result back. When spilled, the heap segment is
+    /// handed over and taken back as is, even if the elements would
+    /// fit inline afterwards; inline elements are moved out and back.
+    /// The spill count and high-water mark carry over. If `f` panics,
+    /// the `Vec` as it was left is still written back.
+    ///
+    /// ```rust
+    /// use vecstor::tinyvec;
+    /// let mut vec: tinyvec<u32, 4> = [1, 1, 2, 3, 3].into_iter().collect();
+    /// let heap = vec.capacity().heap;
+    /// vec.with_vec(|vec| vec.dedup());
+    /// assert_eq!(vec.as_slice(), [1, 2, 3]);
+    /// assert_eq!(vec.capacity().heap, heap);
+    /// ```
+    pub fn with_vec<R>(&mut self, f: impl FnOnce(&mut Vec<T>) -> R) -> R {
+        struct restore<'a, T, const N: usize, G: growth::policy, L: index> {
+            storage: &'a mut spill<T, N, Global, G, L>,
+            vec: Vec<T>,
+            heap_capacity: Option<usize>,
+        }
+
+        impl<T, const N: usize, G: growth::policy, L: index> Drop for restore<'_, T, N, G, L> {
+            fn drop(&mut self) {
+                let vec = std::mem::take(&mut self.vec);
+                self.storage.put_vec(vec, self.heap_capacity);
+            }
+        }
+
+        let heap_capacity = self.is_spilled().then(|| self.storage.capacities().1);
+        let vec = self.storage.take_vec();
+        let mut guard = restore {
+            storage: &mut self.storage,
+            vec,
+            heap_capacity,
+        };
+        let result = f(&mut guard.vec);
+        drop(guard);
+        self.check();
+        result
+    }
+}
+
+/// The UTF-8 bytes.
+impl<const N: usize, G: growth::policy, L: index> From<&str> for tinyvec<u8, N, Global, G, L> {
+    fn from(s: &str) -> Self {
+        let mut bytes = Self::with_heap_capacity(s.len());
+        bytes.extend_from_slice(s.as_bytes());
+        bytes
+    }
+}
+
+/// The UTF-8 bytes, reusing the allocation of `s` when it doesn't
+/// fit inline.
+impl<const N: usize, G: growth::policy, L: index> From<String> for tinyvec<u8, N, Global, G, L> {
+    fn from(s: String) -> Self {
+        Self::from(s.into_bytes())
+    }
+}
+
+/// Borrows, so no allocation.
+impl<'a, T: Clone, S: storage<T>> From<&'a storagevec<T, S>> for std::borrow::Cow<'a, [T]> {
+    fn from(vector: &'a storagevec<T, S>) -> Self {
+        std::borrow::Cow::Borrowed(vector.as_slice())
+    }
+}
+
+/// `From<storagevec>` for the shared and owned slice types: the
+/// elements are moved straight into one allocation of the exact
+/// length, with no `Vec` in between.
+macro_rules! impl_into_slice_ptr {
+    ($($ptr:ident)::+, |$slots:ident| $target:expr) => {
+        impl<T, S: storage<T>> From<storagevec<T, S>> for $($ptr)::+<[T]> {
+            fn from(mut vector: storagevec<T, S>) -> Self {
+                let len = vector.len();
+                let mut $slots = $($ptr)::+::<[T]>::new_uninit_slice(len);
+                let target: &mut [MaybeUninit<T>] = $target;
+                unsafe {
+                    vector.storage.set_len(0);
+                    std::ptr::copy_nonoverlapping(
+                        vector.storage.as_ptr(),
+                        target.as_mut_ptr() as *mut T,
+                        len,
+                    );
+                    $slots.assume_init()
+                }
+            }
+        }
+    };
+}
+
+impl_into_slice_ptr!(Box, |slots| &mut slots);
+// Freshly made, so not shared yet.
+impl_into_slice_ptr!(std::rc::Rc, |slots| std::rc::Rc::get_mut(&mut slots)
+    .unwrap());
+impl_into_slice_ptr!(std::sync::Arc, |slots| std::sync::Arc::get_mut(&mut slots)
+    .unwrap());
+
+/// Make tinyvec work as:
+/// ```rust
+/// use vecstor::tinyvec;
+/// let mut tinyvecwtor: tinyvec<u8, 256> = tinyvec::new();
+/// for i in 0..=256 {
+///     tinyvecwtor.push(i as u8);
+/// }
+///
+/// for i in tinyvecwtor {
+///     // do something with i
+/// }
+/// ````
+///
+/// The iterator also provides free implementation of many
+/// other utility function.
+///
+/// ```rust
+/// use vecstor::tinyvec;
+/// let tinyvector: tinyvec<i32, 1024> = tinyvec::new();
+/// // fill tinyvector
+/// let vector: Vec<i32> = tinyvector.into_iter().collect::<Vec<i32>>();
+/// ````
+/// is valid.
+///
+/// Elements are moved out, so move-only types can be iterated too.
+impl<T, S: storage<T>> IntoIterator for storagevec<T, S> {
+    type Item = T;
+    type IntoIter = owned_iter<T, S>;
+
+    fn into_iter(self) -> owned_iter<T, S> {
+        let vec = ManuallyDrop::new(self);
+        let end = vec.len();
+        let storage = unsafe { std::ptr::read(&vec.storage) };
+        owned_iter {
+            storage,
+            at: 0,
+            end,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Owning iterator of a [`storagevec`], see `into_iter()`.
+/// Elements not yet handed out are dropped with it.
+pub struct owned_iter<T, S: storage<T>> {
+    storage: S,
+    at: usize,
+    end: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T, S: storage<T>> iter::Iterator for owned_iter<T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.at == self.end {
+            return None;
+        }
+
+        self.at += 1;
+        unsafe { Some(self.storage.as_ptr().add(self.at - 1).read()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.end - self.at;
+        (left, Some(left))
+    }
+
+    /// Drops the `n` skipped elements in one go.
+    fn nth(&mut self, n: usize) -> Option<T> {
+        let skip = n.min(self.end - self.at);
+        let at = self.at;
+        // Advance first so a panicking destructor can't lead to a double drop.
+        self.at += skip;
+        unsafe {
+            let skipped =
+                std::ptr::slice_from_raw_parts_mut(self.storage.as_mut_ptr().add(at), skip);
+            std::ptr::drop_in_place(skipped);
+        }
+        self.next()
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<T> {
+        self.next_back()
+    }
+}
+
+impl<T, S: storage<T>> iter::DoubleEndedIterator for owned_iter<T, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.at == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        unsafe { Some(self.storage.as_ptr().add(self.end).read()) }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<T> {
+        let skip = n.min(self.end - self.at);
+        self.end -= skip;
+        unsafe {
+            let skipped =
+                std::ptr::slice_from_raw_parts_mut(self.storage.as_mut_ptr().add(self.end), skip);
+            std::ptr::drop_in_place(skipped);
+        }
+        self.next_back()
+    }
+}
+
+impl<T, S: storage<T>> owned_iter<T, S> {
+    /// The elements not yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            std::slice::from_raw_parts(self.storage.as_ptr().add(self.at), self.end - self.at)
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe {
+            let left = self.end - self.at;
+            std::slice::from_raw_parts_mut(self.storage.as_mut_ptr().add(self.at), left)
+        }
+    }
+}
+
+impl<T, S: storage<T>> iter::ExactSizeIterator for owned_iter<T, S> {}
+
+impl<T, S: storage<T>> Drop for owned_iter<T, S> {
+    fn drop(&mut self) {
+        let left = self.end - self.at;
+        let at = self.at;
+        self.at = self.end;
+        unsafe {
+            let rest = std::ptr::slice_from_raw_parts_mut(self.storage.as_mut_ptr().add(at), left);
+            std::ptr::drop_in_place(rest);
+        }
+    }
+}
+
+/// Batches of K items from an iterator as [`tinyvec`]s, the last one
+/// holding whatever is left. Each batch fits inline, so none of them
+/// allocates. See `storagevec::into_chunks()`, or wrap any iterator:
+///
+/// ```rust
+/// use vecstor::into_chunks;
+/// let batches: Vec<Vec<u32>> = into_chunks::<_, 4>::new(1..=10).map(Vec::from_iter).collect();
+/// assert_eq!(batches, [vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10]]);
+/// ```
+pub struct into_chunks<I, const K: usize> {
+    iter: I,
+}
+
+impl<I: Iterator, const K: usize> into_chunks<I, K> {
+    pub fn new<J: IntoIterator<IntoIter = I>>(iter: J) -> Self {
+        const { assert!(K > 0, "into_chunks: K must not be 0") };
+        Self {
+            iter: iter.into_iter(),
+        }
+    }
+}
+
+impl<I: Iterator, const K: usize> Iterator for into_chunks<I, K> {
+    type Item = tinyvec<I::Item, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch: tinyvec<I::Item, K> = self.iter.by_ref().take(K).collect();
+        (!batch.is_empty()).then_some(batch)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        (low.div_ceil(K), high.map(|high| high.div_ceil(K)))
+    }
+}
+
+/// Position in a storagevec that can edit around itself, returned by
+/// [`storagevec::cursor_mut`]. It is either at an element or past the
+/// end, where `current()` is `None`.
+///
+/// ```rust
+/// use vecstor::{tiny_vec, tinyvec};
+/// let mut ops: tinyvec<&str, 8> = tiny_vec!["load", "nop", "add", "store"];
+/// let mut cursor = ops.cursor_mut();
+/// while let Some(op) = cursor.current() {
+///     match *op {
+///         "nop" => drop(cursor.remove_current()),
+///         "add" => {
+///             cursor.insert_after("check");
+///             cursor.move_next();
+///             cursor.move_next();
+///         }
+///         _ => cursor.move_next(),
+///     }
+/// }
+/// assert_eq!(ops.as_slice(), ["load", "add", "check", "store"]);
+/// ```
+pub struct cursor_mut<'a, T, S: storage<T>> {
+    vec: &'a mut storagevec<T, S>,
+    /// `vec.len()` when past the end.
+    at: usize,
+}
+
+impl<T, S: storage<T>> cursor_mut<'_, T, S> {
+    /// Position of the cursor; `len()` when past the end.
+    pub fn index(&self) -> usize {
+        self.at
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.vec.get_mut(self.at)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        self.vec.get_mut(self.at + 1)
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        self.vec.get_mut(self.at.checked_sub(1)?)
+    }
+
+    /// Step forward; stays put once past the end.
+    pub fn move_next(&mut self) {
+        self.at = (self.at + 1).min(self.vec.len());
+    }
+
+    /// Step back; stays put at the first element.
+    pub fn move_prev(&mut self) {
+        self.at = self.at.saturating_sub(1);
+    }
+
+    /// Take the current element out; the cursor moves on to the one
+    /// after it.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.vec.remove(self.at)
+    }
+
+    /// Insert before the current element, keeping the cursor on it.
+    /// Past the end this appends.
+    pub fn insert_before(&mut self, element: T) {
+        self.vec.insert(self.at, element);
+        self.at += 1;
+    }
+
+    /// Insert after the current element, or append if past the end
+    /// (the cursor then lands on the new element).
+    pub fn insert_after(&mut self, element: T) {
+        let at = (self.at + 1).min(self.vec.len());
+        self.vec.insert(at, element);
+    }
+}
+
+/// `range` as `start..end` within `len`, panicking like slicing does
+/// if it is out of bounds or a bound overflows `usize`.
+pub(crate) fn bounds<R: std::ops::RangeBounds<usize>>(
+    range: &R,
+    len: usize,
+    what: &str,
+) -> std::ops::Range<usize> {
+    use std::ops::Bound;
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("{what}: range start overflows usize")),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("{what}: range end overflows usize")),
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => len,
+    };
+    assert!(
+        start <= end && end <= len,
+        "{what}: range {start}..{end} out of bounds (len {len})"
+    );
+    start..end
+}
+
+/// Iterator returned by [`storagevec::drain`].
+pub struct drain<'a, T, S: storage<T>> {
+    vec: &'a mut storagevec<T, S>,
+    /// Drained elements not yielded yet are `[at, end)`.
+    at: usize,
+    end: usize,
+    /// Kept elements after the range, moved down on drop.
+    tail: usize,
+    tail_len: usize,
+}
+
+impl<T, S: storage<T>> drain<'_, T, S> {
+    /// The drained elements not yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            std::slice::from_raw_parts(self.vec.storage.as_ptr().add(self.at), self.end - self.at)
+        }
+    }
+}
+
+impl<T, S: storage<T>> Iterator for drain<'_, T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.at == self.end {
+            return None;
+        }
+        self.at += 1;
+        unsafe { Some(self.vec.storage.as_ptr().add(self.at - 1).read()) }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.end - self.at;
+        (left, Some(left))
+    }
+}
+
+impl<T, S: storage<T>> DoubleEndedIterator for drain<'_, T, S> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.at == self.end {
+            return None;
+        }
+        self.end -= 1;
+        unsafe { Some(self.vec.storage.as_ptr().add(self.end).read()) }
+    }
+}
+
+impl<T, S: storage<T>> ExactSizeIterator for drain<'_, T, S> {}
+
+impl<T, S: storage<T>> Drop for drain<'_, T, S> {
+    fn drop(&mut self) {
+        // Moves the tail back even if dropping a leftover panics.
+        struct restore_tail<'r, 'a, T, S: storage<T>>(&'r mut drain<'a, T, S>);
+
+        impl<T, S: storage<T>> Drop for restore_tail<'_, '_, T, S> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let storage = &mut drain.vec.storage;
+                let start = storage.len();
+                unsafe {
+                    let base = storage.as_mut_ptr();
+                    std::ptr::copy(base.add(drain.tail), base.add(start), drain.tail_len);
+                    storage.set_len(start + drain.tail_len);
+                }
+                storage.after_remove();
+            }
+        }
+
+        let guard = restore_tail(self);
+        let left = guard.0.end - guard.0.at;
+        let at = guard.0.at;
+        guard.0.at = guard.0.end;
+        unsafe {
+            let rest =
+                std::ptr::slice_from_raw_parts_mut(guard.0.vec.storage.as_mut_ptr().add(at), left);
+            std::ptr::drop_in_place(rest);
+        }
+    }
+}
+
+/// The elements, then where they live, e.g.
+/// `[1, 2, 3, 4, 5] len=5 inline=0/4 heap=5/8` for a spilled tinyvec.
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, S: storage<T> + fmt::Debug> fmt::Debug for storagevec<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()?;
+        write!(f, " {:?}", self.storage)
+    }
+}
+
+/// Only this impl asks for `T: Display`, the container itself
+/// stores anything.
+///
+/// Prints `[ a, b ]`, `[ ]` when empty. Width, precision and the
+/// other format flags apply to each element, so `{:.2}` prints
+/// `[ 1.00, 2.50 ]`. See `display_with()` for other layouts.
+#[cfg(feature = "fmt")]
+impl<T, S: storage<T>> Display for storagevec<T, S>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("[ ]");
+        }
+        Display::fmt(&self.display_with(", ", "[ ", " ]"), f)
+    }
+}
+
+/// Contiguous hex dump of a byte vector, `{:x}` gives `dead00ff`.
+/// `{:#x}` puts a single `0x` in front.
+#[cfg(feature = "fmt")]
+impl<S: storage<u8>> fmt::LowerHex for storagevec<u8, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        for byte in self.iter() {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Same as [`LowerHex`](fmt::LowerHex), in upper case.
+#[cfg(feature = "fmt")]
+impl<S: storage<u8>> fmt::UpperHex for storagevec<u8, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        for byte in self.iter() {
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// [`Display`] adapter from `storagevec::display_with()`.
+#[cfg(feature = "fmt")]
+pub struct displayed<'a, T, S: storage<T>> {
+    vector: &'a storagevec<T, S>,
+    separator: &'a str,
+    open: &'a str,
+    close: &'a str,
+}
+
+#[cfg(feature = "fmt")]
+impl<T: Display, S: storage<T>> Display for displayed<'_, T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.open)?;
+        for (i, value) in self.vector.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.separator)?;
+            }
+            value.fmt(f)?;
+        }
+        f.write_str(self.close)
+    }
+}
+
+/// Generate `profile`
+///
+/// Profiled based on time taken to load elements
+/// to tinyvec and vec.
+/// Note that though `Vec<T>` is in the std,
+/// `tinyvec<T, N> is faster than Vec<T> in all regards.`
+///
+/// # Profile Table
+///
+/// |tinyvec      |   vec          |   dtype  |
+/// |-------------|----------------|----------|             
+/// |82.971µs     |   96.186µs     |          |
+/// |163.522µs    |   189.874µs    |    i32   |
+/// |245.244µs    |   293.82µs     |          |
+/// |             |                |          |
+/// |84.787µs     |   99.005µs     |          |
+/// |166.622µs    |  205.947µs     |    i64   |
+/// |249.435µs    |   28.471µs     |          |
+/// |             |                |          |
+/// |112.127µs    |  149.791µs     |          |
+/// |198.913µs    |   287.499µs    |     i128 |
+/// |285.452µs    |  527.276µs     |          |
+/// |             |                |          |
+/// |103.187µs    |  103.136µs     |          |
+/// |183.506µs    |  204.389µs     |   bool   |
+/// |278.57µs     |  338.353µs     |          |
+/// |             |                |          |
+/// |94.472µs     |  103.327µs     |          |
+/// |181.874µs    |  266.311µs     |    f32   |
+/// |315.397µs    |  330.904µs     |          |
+/// |             |                |          |
+/// |98.945µs     |  116.915µs     |          |
+/// |184.969µs    |  209.106µs     |    f64   |
+/// |277.405µs    |  322.154µs     |          |
+/// |             |                |          |
+/// |104.565µs    |  107.086µs     |          |
+/// |182.641µs    |  203.016µs     |    char  |
+/// |273.395µs    |  302.796µs     |          |
+///
+pub fn load<T>()
+where
+    T: Copy + Default,
+{
+    let mut t1: tinyvec<T, 5000> = tinyvec::new();
+    let mut t2: tinyvec<T, 10000> = tinyvec::new();
+    let mut t3: tinyvec<T, 15000> = tinyvec::new();
+    let mut v1: Vec<T> = Vec::new();
+    let mut v2: Vec<T> = Vec::new();
+    let mut v3: Vec<T> = Vec::new();
+    let begin = Instant::now();
+
+    for i in 0..=5000 {
+        t1.push(T::default());
+    }
+
+    let t_t1 = begin.elapsed();
+
+    let begin = Instant::now();
+    for i in 0..=5000 {
+        v1.push(T::default());
+    }
+
+    let t_v1 = begin.elapsed();
+
+    let begin = Instant::now();
+    for i in 0..=10000 {
+        t2.push(T::default());
+    }
+
+    let t_t2 = begin.elapsed();
+
+    let begin = Instant::now();
+    for i in 0..=10000 {
+        v2.push(T::default());
+    }
+
+    let t_v2 = begin.elapsed();
+
+    let begin = Instant::now();
+    for i in 0..=15000 {
+        t3.push(T::default());
+    }
+
+    let t_t3 = begin.elapsed();
+
+    let begin = Instant::now();
+    for i in 0..=15000 {
+        v3.push(T::default());
+    }
+
+    let t_v3 = begin.elapsed();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pool::vecpool;
+    use crate::{
+        Global, align, appendvec, array_format, arraystring, borrowed, boxedvec, capacity,
+        capacity_error, chunkedvec, disjoint_error, growth, index_error, inline, into_chunks,
+        lossyvec, mpmc, nul_error, pinnedvec, sharedvec, slicevec, sortedmap, sortedvec, spill,
+        spsc, stackvec, storagevec, tiny_format, tiny_vec, tinybitset, tinybytes, tinycow,
+        tinycstring, tinydeque, tinygrid, tinyhashmap, tinyheap, tinylru, tinymap, tinyring,
+        tinyset, tinyslotmap, tinystring, tinyvec, try_reserve_error, veclike,
+    };
+
+    #[test]
+    fn setup() {
+        let mut vector: tinyvec<i32, 1024> = tinyvec::new();
+        vector.push(1);
+        vector.push(2);
+        vector.push(3);
+        let _ = vector.pop();
+        let _ = vector.remove(0);
+        assert_eq!(vector.len(), 1);
+        assert_eq!(
+            vector.capacity(),
+            capacity {
+                inline: 1024,
+                heap: 0
+            }
+        );
+    }
+
+    #[test]
+    fn length() {
+        let mut vector: tinyvec<bool, 1024> = tinyvec::new();
+        let _ = vector.pop();
+        assert_eq!(vector.len(), 0);
+    }
+
+    #[test]
+    fn extends() {
+        let mut vector: tinyvec<char, 2048> = tinyvec::new();
+        let slice = ('a'..='z').collect::<Vec<char>>();
+        vector.extend(&slice);
+        assert_eq!(vector.len(), 26);
+    }
+
+    #[test]
+    fn iterate() {
+        let mut vector: tinyvec<i32, 4> = tinyvec::new();
+        let slice: [i32; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+        vector.extend(&slice);
+        assert_eq!(vector.len(), 8);
+        assert_eq!(vector.capacity(), capacity { inline: 4, heap: 8 });
+
+        let mut number: i32 = 0;
+        for i in vector {
+            number += i;
+        }
+
+        assert_eq!(number, 36);
+    }
+
+    #[test]
+    fn convert() {
+        // Convert tinyvec to vec
+        let mut tinyvecwtor: tinyvec<i128, 200> = tinyvec::new();
+        for i in 0..200 {
+            tinyvecwtor.push(i as i128);
+        }
+
+        let vector = tinyvecwtor.into_iter().collect::<Vec<_>>();
+        assert_eq!(vector.len(), 200);
+    }
+
+    #[test]
+    fn owned() {
+        let mut vector: tinyvec<String, 2> = tinyvec::new();
+        for word in ["a", "b", "c", "d"] {
+            vector.push(word.to_string());
+        }
+
+        assert_eq!(vector.get(2).map(String::as_str), Some("c"));
+        assert_eq!(vector.remove(1).as_deref(), Some("b"));
+        assert_eq!(vector.get(1).map(String::as_str), Some("c"));
+        assert_eq!(vector.pop().as_deref(), Some("d"));
+        assert_eq!(vector.pop().as_deref(), Some("c"));
+        assert_eq!(vector.pop().as_deref(), Some("a"));
+        assert_eq!(vector.pop(), None);
+        assert_eq!(vector.len(), 0);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct token(Box<u32>);
+
+    impl std::fmt::Display for token {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn move_only() {
+        let mut vector: tinyvec<token, 2> = tinyvec::new();
+        for i in 0..4 {
+            vector.push(token(Box::new(i)));
+        }
+
+        if let Some(t) = vector.get_mut(3) {
+            *t.0 += 10;
+        }
+
+        assert_eq!(vector.remove(0), Some(token(Box::new(0))));
+        assert_eq!(format!("{vector}"), "[ 1, 2, 13 ]");
+        assert_eq!(vector.pop(), Some(token(Box::new(13))));
+    }
+
+    #[test]
+    fn drops() {
+        use std::rc::Rc;
+
+        let item = Rc::new(String::from("x"));
+        let mut vector: tinyvec<Rc<String>, 4> = tinyvec::new();
+        for _ in 0..6 {
+            vector.push(Rc::clone(&item));
+        }
+        assert_eq!(Rc::strong_count(&item), 7);
+
+        vector.truncate(5);
+        assert_eq!(Rc::strong_count(&item), 6);
+
+        let _ = vector.remove(0);
+        assert_eq!(Rc::strong_count(&item), 5);
+
+        vector.truncate(2);
+        assert_eq!(Rc::strong_count(&item), 3);
+
+        vector.clear();
+        assert_eq!(Rc::strong_count(&item), 1);
+
+        for _ in 0..6 {
+            vector.push(Rc::clone(&item));
+        }
+        drop(vector);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn any_payload() {
+        #[derive(Debug, PartialEq)]
+        struct point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut points: tinyvec<point, 2> = tinyvec::new();
+        points.push(point { x: 1, y: 2 });
+        points.push(point { x: 3, y: 4 });
+        points.push(point { x: 5, y: 6 });
+        assert_eq!(points.get(2), Some(&point { x: 5, y: 6 }));
+
+        let mut pairs: tinyvec<(u8, char), 4> = tinyvec::new();
+        pairs.push((1, 'a'));
+        assert_eq!(pairs.pop(), Some((1, 'a')));
+    }
+
+    #[test]
+    fn no_default() {
+        use std::num::NonZeroU32;
+
+        #[derive(Debug, PartialEq)]
+        enum state {
+            on,
+            off,
+        }
+
+        let mut ids: tinyvec<NonZeroU32, 1> = tinyvec::new();
+        ids.push(NonZeroU32::new(7).unwrap());
+        ids.push(NonZeroU32::new(9).unwrap());
+        assert_eq!(ids.pop(), NonZeroU32::new(9));
+        assert_eq!(ids.pop(), NonZeroU32::new(7));
+        assert_eq!(ids.pop(), None);
+
+        let mut states: tinyvec<state, 2> = tinyvec::new();
+        states.push(state::on);
+        states.push(state::off);
+        assert_eq!(states.remove(0), Some(state::on));
+    }
+
+    #[test]
+    fn zero_sized() {
+        let mut vector: tinyvec<(), 16> = tinyvec::new();
+        for _ in 0..1000 {
+            vector.push(());
+        }
+
+        assert_eq!(vector.len(), 1000);
+        assert_eq!(
+            vector.capacity(),
+            capacity {
+                inline: usize::MAX,
+                heap: usize::MAX
+            }
+        );
+        assert_eq!(vector.get(999), Some(&()));
+        assert_eq!(vector.remove(500), Some(()));
+        assert_eq!(vector.pop(), Some(()));
+        assert_eq!(vector.len(), 998);
+    }
+
+    #[test]
+    fn zero_sized_drops() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        struct marker;
+
+        impl Drop for marker {
+            fn drop(&mut self) {
+                DROPS.with(|d| d.set(d.get() + 1));
+            }
+        }
+
+        let mut vector: tinyvec<marker, 4> = tinyvec::new();
+        for _ in 0..10 {
+            vector.push(marker);
+        }
+
+        drop(vector.pop());
+        drop(vector.remove(0));
+        assert_eq!(DROPS.with(Cell::get), 2);
+
+        vector.truncate(5);
+        assert_eq!(DROPS.with(Cell::get), 5);
+
+        drop(vector);
+        assert_eq!(DROPS.with(Cell::get), 10);
+    }
+
+    #[cfg(feature = "allocator_api")]
+    #[test]
+    fn custom_allocator() {
+        use std::alloc::{AllocError, Allocator, Global, Layout};
+        use std::cell::Cell;
+        use std::ptr::NonNull;
+
+        #[derive(Clone)]
+        struct counting<'a>(&'a Cell<usize>);
+
+        unsafe impl Allocator for counting<'_> {
+            fn allocate(&self, layout: Layout) -> std::result::Result<NonNull<[u8]>, AllocError> {
+                self.0.set(self.0.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let count = Cell::new(0);
+        let mut vector: tinyvec<u32, 2, counting> = tinyvec::new_in(counting(&count));
+        vector.extend(&[1, 2, 3, 4]);
+        assert_eq!(vector.get(3), Some(&4));
+        assert!(count.get() > 0);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn spill_keeps_order() {
+        let mut vector: tinyvec<String, 2> = tinyvec::new();
+        for word in ["a", "b", "c"] {
+            vector.push(word.to_string());
+        }
+
+        assert_eq!(vector.remove(0).as_deref(), Some("a"));
+        assert_eq!(format!("{vector}"), "[ b, c ]");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn inline_only() {
+        let mut vector = storagevec::<u8, inline<u8, 3>>::new();
+        vector.extend(&[1, 2, 3]);
+        assert_eq!(vector.capacity(), 3);
+        assert_eq!(vector.pop(), Some(3));
+        vector.push(4);
+        assert_eq!(format!("{vector}"), "[ 1, 2, 4 ]");
+    }
+
+    #[test]
+    #[should_panic(expected = "storage is full")]
+    fn inline_full() {
+        let mut vector = storagevec::<u8, inline<u8, 1>>::new();
+        vector.push(1);
+        vector.push(2);
+    }
+
+    #[test]
+    fn borrowed_buffer() {
+        use std::mem::MaybeUninit;
+
+        let mut buf = [const { MaybeUninit::<String>::uninit() }; 4];
+        let mut vector = storagevec::from_buf(&mut buf);
+        vector.push("x".to_string());
+        vector.push("y".to_string());
+        assert_eq!(vector.capacity(), 4);
+        assert_eq!(vector.remove(0).as_deref(), Some("x"));
+        assert_eq!(vector.get(0).map(String::as_str), Some("y"));
+    }
+
+    #[test]
+    fn heap_reserve() {
+        let mut vector: tinyvec<u64, 2> = tinyvec::with_heap_capacity(8);
+        assert_eq!(vector.capacity(), capacity { inline: 2, heap: 8 });
+        vector.extend(&[1, 2, 3]);
+        assert_eq!(vector.capacity(), capacity { inline: 2, heap: 8 });
+    }
+
+    #[test]
+    fn lazy_heap() {
+        let mut vector: tinyvec<u32, 4> = tinyvec::new();
+        vector.extend(&[1, 2, 3, 4]);
+        assert_eq!(vector.capacity(), capacity { inline: 4, heap: 0 });
+
+        vector.push(5);
+        assert!(vector.capacity().heap >= 5);
+    }
+
+    #[test]
+    fn growth_policies() {
+        use crate::allocator::Global;
+        use crate::growth::{exact, fixed};
+
+        let mut doubled: tinyvec<u8, 4> = tinyvec::new();
+        doubled.extend(&[0; 5]);
+        assert_eq!(doubled.capacity(), capacity { inline: 4, heap: 8 });
+
+        let mut fitted: tinyvec<u8, 4, Global, exact> = tinyvec::new();
+        fitted.extend(&[0; 5]);
+        assert_eq!(fitted.capacity(), capacity { inline: 4, heap: 5 });
+        fitted.push(0);
+        assert_eq!(fitted.capacity(), capacity { inline: 4, heap: 6 });
+
+        let mut stepped: tinyvec<u8, 4, Global, fixed<16>> = tinyvec::new();
+        stepped.extend(&[0; 5]);
+        assert_eq!(
+            stepped.capacity(),
+            capacity {
+                inline: 4,
+                heap: 20
+            }
+        );
+        stepped.extend(&[0; 16]);
+        assert_eq!(
+            stepped.capacity(),
+            capacity {
+                inline: 4,
+                heap: 36
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "spill_count", feature = "high_water")))]
+    fn footprint() {
+        use std::mem::size_of;
+
+        // stack and heap share memory, so no room for both
+        assert!(size_of::<tinyvec<u8, 8>>() <= size_of::<Vec<u8>>() + size_of::<usize>());
+        assert!(size_of::<tinyvec<u64, 16>>() <= 16 * 8 + 2 * size_of::<usize>());
+    }
+
+    #[test]
+    fn owned_iteration() {
+        use std::rc::Rc;
+
+        let item = Rc::new(0);
+        let mut vector: tinyvec<Rc<i32>, 2> = tinyvec::new();
+        for _ in 0..5 {
+            vector.push(Rc::clone(&item));
+        }
+
+        let mut iter = vector.into_iter();
+        assert_eq!(iter.len(), 5);
+        drop(iter.next());
+        drop(iter.next_back());
+        assert_eq!(Rc::strong_count(&item), 4);
+
+        drop(iter);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn compact_length() {
+        use crate::allocator::Global;
+        use crate::growth::doubling;
+        use std::mem::size_of;
+
+        assert_eq!(size_of::<storagevec<u8, inline<u8, 6, u16>>>(), 8);
+        assert!(size_of::<tinyvec<u8, 24, Global, doubling, u32>>() < size_of::<tinyvec<u8, 24>>());
+
+        let mut vector: tinyvec<u16, 4, Global, doubling, u8> = tinyvec::new();
+        for i in 0..300 {
+            vector.push(i);
+        }
+        assert_eq!(vector.len(), 300);
+        assert_eq!(vector.get(299), Some(&299));
+
+        let mut markers: tinyvec<(), 4, Global, doubling, u8> = tinyvec::new();
+        for _ in 0..300 {
+            markers.push(());
+        }
+        assert_eq!(markers.len(), 300);
+    }
+
+    #[test]
+    fn over_aligned() {
+        use crate::align::{a16, a64};
+
+        let mut vector = storagevec::<u8, inline<u8, 16, usize, a64>>::new();
+        vector.extend(&[1, 2, 3]);
+        let first = vector.get(0).unwrap() as *const u8;
+        assert_eq!(first as usize % 64, 0);
+
+        let mut boxed = Box::new(storagevec::<f32, inline<f32, 4, u8, a16>>::new());
+        boxed.push(1.0);
+        let first = boxed.get(0).unwrap() as *const f32;
+        assert_eq!(first as usize % 16, 0);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn unspill() {
+        let mut vector: tinyvec<String, 4> = tinyvec::new();
+        for i in 0..10 {
+            vector.push(i.to_string());
+        }
+
+        vector.truncate(5);
+        assert!(!vector.shrink_to_inline());
+        assert!(vector.capacity().heap > 0);
+
+        vector.truncate(3);
+        assert!(vector.shrink_to_inline());
+        assert_eq!(vector.capacity(), capacity { inline: 4, heap: 0 });
+        assert_eq!(format!("{vector}"), "[ 0, 1, 2 ]");
+
+        vector.push("3".to_string());
+        assert_eq!(vector.capacity(), capacity { inline: 4, heap: 0 });
+    }
+
+    #[test]
+    fn remove_around_boundary() {
+        const N: usize = 4;
+        for len in 0..=2 * N + 1 {
+            for index in 0..=len {
+                let mut vector: tinyvec<String, N> = tinyvec::new();
+                let mut model: Vec<String> = Vec::new();
+                for i in 0..len {
+                    vector.push(i.to_string());
+                    model.push(i.to_string());
+                }
+
+                let expected = (index < len).then(|| model.remove(index));
+                assert_eq!(vector.remove(index), expected, "len {len}, index {index}");
+                assert_eq!(vector.len(), model.len());
+                for (i, value) in model.iter().enumerate() {
+                    assert_eq!(vector.get(i), Some(value));
+                }
+                assert_eq!(vector.get(model.len()), None);
+            }
+        }
+    }
+
+    #[test]
+    fn remove_partial_stack() {
+        let mut vector: tinyvec<Box<i32>, 8> = tinyvec::new();
+        vector.push(Box::new(1));
+        vector.push(Box::new(2));
+        vector.push(Box::new(3));
+        assert_eq!(vector.remove(1), Some(Box::new(2)));
+        assert_eq!(vector.remove(1), Some(Box::new(3)));
+        assert_eq!(vector.remove(1), None);
+        assert_eq!(vector.remove(0), Some(Box::new(1)));
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn pop_is_lifo() {
+        const N: usize = 4;
+        for len in 0..=2 * N + 1 {
+            let mut vector: tinyvec<String, N> = tinyvec::new();
+            for i in 0..len {
+                vector.push(i.to_string());
+            }
+
+            for i in (0..len).rev() {
+                assert_eq!(vector.pop(), Some(i.to_string()), "len {len}");
+            }
+            assert_eq!(vector.pop(), None);
+        }
+
+        // spilled, then shrunk below N
+        let mut vector: tinyvec<String, N> = tinyvec::new();
+        for i in 0..6 {
+            vector.push(i.to_string());
+        }
+        vector.truncate(2);
+        vector.push("x".to_string());
+        assert_eq!(vector.pop().as_deref(), Some("x"));
+        assert_eq!(vector.pop().as_deref(), Some("1"));
+        assert_eq!(vector.pop().as_deref(), Some("0"));
+        assert_eq!(vector.pop(), None);
+    }
+
+    #[test]
+    fn mixed_operations() {
+        // xorshift, so the sequence is the same on every run
+        let mut seed = 0x2545_f491_u32;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+
+        let mut vector: tinyvec<u32, 4> = tinyvec::new();
+        let mut model: Vec<u32> = Vec::new();
+        for step in 0..2000 {
+            let n = next();
+            match n % 6 {
+                0 | 1 => {
+                    vector.push(n);
+                    model.push(n);
+                }
+                2 => assert_eq!(vector.pop(), model.pop()),
+                3 => {
+                    let at = n as usize % (model.len() + 1);
+                    let expected = (at < model.len()).then(|| model.remove(at));
+                    assert_eq!(vector.remove(at), expected);
+                }
+                4 => {
+                    let len = n as usize % 8;
+                    vector.truncate(len);
+                    model.truncate(len);
+                }
+                _ => {
+                    vector.shrink_to_inline();
+                }
+            }
+
+            assert_eq!(vector.len(), model.len(), "step {step}");
+            for (i, value) in model.iter().enumerate() {
+                assert_eq!(vector.get(i), Some(value), "step {step}");
+            }
+            assert_eq!(vector.get(model.len()), None);
+        }
+    }
+
+    #[test]
+    fn insert_shifts() {
+        let mut vector: tinyvec<u8, 3> = tinyvec::new();
+        vector.extend(&[1, 3]);
+        vector.insert(1, 2);
+        vector.insert(3, 4);
+        vector.insert(0, 0);
+        assert_eq!(vector.as_slice(), &[0, 1, 2, 3, 4]);
+
+        vector.insert_many(2, [7, 8, 9]);
+        assert_eq!(vector.as_slice(), &[0, 1, 7, 8, 9, 2, 3, 4]);
+
+        vector.resize_with(3, || 0);
+        assert_eq!(vector.as_slice(), &[0, 1, 7]);
+        let mut n = 0;
+        vector.resize_with(5, || {
+            n += 1;
+            n
+        });
+        assert_eq!(vector.as_slice(), &[0, 1, 7, 1, 2]);
+    }
+
+    #[test]
+    fn bulk_panic_safety() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let mut vector: tinyvec<Rc<()>, 2> = tinyvec::new();
+        vector.push(Rc::clone(&item));
+
+        // iterator panicking after two items
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut yielded = 0;
+            vector.insert_many(
+                0,
+                std::iter::from_fn(|| {
+                    yielded += 1;
+                    assert!(yielded <= 2, "boom");
+                    Some(Rc::clone(&item))
+                }),
+            );
+        }));
+        assert!(result.is_err());
+        assert_eq!(vector.len(), 3);
+        assert_eq!(Rc::strong_count(&item), 4);
+
+        // closure panicking on the third call
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut calls = 0;
+            vector.resize_with(10, || {
+                calls += 1;
+                assert!(calls < 3, "boom");
+                Rc::clone(&item)
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(vector.len(), 5);
+        assert_eq!(Rc::strong_count(&item), 6);
+
+        // clone panicking halfway through
+        #[derive(Debug)]
+        struct fragile(Rc<()>, bool);
+        impl Clone for fragile {
+            fn clone(&self) -> Self {
+                assert!(!self.1, "boom");
+                fragile(Rc::clone(&self.0), false)
+            }
+        }
+        let mut fragiles: tinyvec<fragile, 2> = tinyvec::new();
+        let source = [
+            fragile(Rc::clone(&item), false),
+            fragile(Rc::clone(&item), false),
+            fragile(Rc::clone(&item), true),
+        ];
+        let result = catch_unwind(AssertUnwindSafe(|| fragiles.extend(&source)));
+        assert!(result.is_err());
+        assert_eq!(fragiles.len(), 2);
+
+        drop((vector, fragiles, source));
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn contains_eq_fill() {
+        let mut bytes: tinyvec<u8, 32> = tinyvec::new();
+        bytes.extend(b"hello, world");
+        assert!(bytes.contains(&b'w'));
+        assert!(!bytes.contains(&b'z'));
+
+        let mut other = storagevec::<u8, inline<u8, 16>>::new();
+        other.extend(b"hello, world");
+        assert_eq!(bytes, other);
+        assert_eq!(bytes, *b"hello, world");
+        other.pop();
+        assert_ne!(bytes, other);
+
+        bytes.fill(0);
+        assert_eq!(bytes, [0; 12]);
+
+        let mut floats: tinyvec<f32, 2> = tinyvec::new();
+        floats.extend(&[1.0, f32::NAN, 3.0]);
+        assert!(floats.contains(&3.0));
+        assert!(!floats.contains(&f32::NAN));
+        assert_ne!(floats, floats.as_slice()[..]);
+        floats.fill(0.5);
+        assert_eq!(floats, [0.5; 3]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn collect_and_extend() {
+        let exact: tinyvec<u32, 4> = (0..3).collect();
+        assert_eq!(exact, [0, 1, 2]);
+        assert_eq!(exact.capacity(), capacity { inline: 4, heap: 0 });
+
+        let spilled: tinyvec<u32, 4> = (0..100).collect();
+        assert_eq!(spilled.len(), 100);
+        assert_eq!(
+            spilled.capacity(),
+            capacity {
+                inline: 4,
+                heap: 100
+            }
+        );
+
+        // no useful hint, everything goes through push()
+        let mut filtered: tinyvec<u32, 4> = (0..20).filter(|i| i % 2 == 0).collect();
+        assert_eq!(filtered.len(), 10);
+        filtered.extend((0..3).map(|i| i * 100));
+        assert_eq!(filtered.as_slice()[10..], [0, 100, 200]);
+
+        let inline: storagevec<u32, inline<u32, 4>> =
+            "1 2 3".split(' ').map(|s| s.parse().unwrap()).collect();
+        assert_eq!(inline, [1, 2, 3]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn fill_batches() {
+        let mut source = 0..10;
+        let mut batch = storagevec::<u32, inline<u32, 4>>::new();
+        batch.push(100);
+
+        let (written, rest) = batch.extend_within_capacity(&mut source);
+        assert_eq!(written, 3);
+        assert_eq!(batch, [100, 0, 1, 2]);
+        assert_eq!(rest.next(), Some(3));
+
+        let (written, _) = batch.extend_within_capacity(&mut source);
+        assert_eq!(written, 0);
+        assert_eq!(source.next(), Some(4));
+
+        // a tinyvec only uses what it has, stack or reserved heap
+        let mut vector: tinyvec<u32, 2> = tinyvec::with_heap_capacity(6);
+        let (written, mut rest) = vector.extend_within_capacity(source);
+        assert_eq!(written, 5);
+        assert_eq!(rest.next(), None);
+        assert_eq!(vector.capacity(), capacity { inline: 2, heap: 6 });
+    }
+
+    #[test]
+    fn spill_introspection() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static moved: AtomicUsize = AtomicUsize::new(0);
+        thread_local! {
+            static REPORTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+        }
+        crate::on_spill(Some(|len| {
+            moved.fetch_add(len, Ordering::Relaxed);
+            REPORTS.with(|r| r.set(r.get() + 1));
+        }));
+
+        // the hook is process-wide and other tests spill too, so
+        // only check what this 7-element stack contributes
+        let mut vector: tinyvec<u8, 7> = tinyvec::new();
+        vector.extend(&[0; 7]);
+        assert!(!vector.is_spilled());
+        #[cfg(feature = "spill_count")]
+        assert_eq!(vector.spill_count(), 0);
+
+        let before = moved.load(Ordering::Relaxed);
+        vector.push(7);
+        assert!(vector.is_spilled());
+        #[cfg(feature = "spill_count")]
+        assert_eq!(vector.spill_count(), 1);
+        assert!(moved.load(Ordering::Relaxed) >= before + 7);
+
+        // only the first spill is reported, with or without spill_count
+        let reports = REPORTS.with(|r| r.get());
+        vector.truncate(3);
+        assert!(vector.shrink_to_inline());
+        assert!(!vector.is_spilled());
+        vector.extend(&[0; 5]);
+        assert!(vector.is_spilled());
+        #[cfg(feature = "spill_count")]
+        assert_eq!(vector.spill_count(), 2);
+        assert_eq!(REPORTS.with(|r| r.get()), reports);
+        crate::on_spill(None);
+    }
+
+    #[test]
+    #[cfg(feature = "log")]
+    fn log_allocations() {
+        use std::sync::Mutex;
+
+        static events: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        struct recorder;
+        impl log::Log for recorder {
+            fn enabled(&self, metadata: &log::Metadata) -> bool {
+                metadata.target() == "vecstor"
+            }
+            fn log(&self, record: &log::Record) {
+                if self.enabled(record.metadata()) {
+                    events.lock().unwrap().push(record.args().to_string());
+                }
+            }
+            fn flush(&self) {}
+        }
+        log::set_logger(&recorder).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        // u128 with an odd N, so other tests can't produce the same lines
+        let mut vector: tinyvec<u128, 3> = tinyvec::new();
+        for i in 0..7 {
+            vector.push(i);
+        }
+
+        let recorded = events.lock().unwrap();
+        let ours: Vec<&String> = recorded
+            .iter()
+            .filter(|e| e.starts_with("tinyvec<u128, 3>"))
+            .collect();
+        assert_eq!(
+            ours,
+            [
+                "tinyvec<u128, 3> spilled to the heap, heap capacity 6",
+                "tinyvec<u128, 3> reallocated, heap capacity 12",
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_accounting() {
+        let mut vector: tinyvec<u32, 4> = tinyvec::new();
+        vector.extend(&[1, 2, 3]);
+        let stack = vector.memory_usage();
+        assert_eq!(stack.inline, std::mem::size_of::<tinyvec<u32, 4>>());
+        assert_eq!((stack.heap_allocated, stack.heap_used), (0, 0));
+
+        vector.extend(&[4, 5]);
+        let usage = vector.memory_usage();
+        assert_eq!(usage.inline, stack.inline);
+        assert_eq!(usage.heap_allocated, 8 * 4);
+        assert_eq!(usage.heap_used, 5 * 4);
+    }
+
+    #[test]
+    fn segment_lengths() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        vector.extend(&[1, 2, 3]);
+        assert_eq!((vector.stack_len(), vector.heap_len()), (3, 0));
+
+        vector.extend(&[4, 5]);
+        assert_eq!((vector.stack_len(), vector.heap_len()), (0, 5));
+
+        vector.truncate(2);
+        vector.shrink_to_inline();
+        assert_eq!((vector.stack_len(), vector.heap_len()), (2, 0));
+    }
+
+    #[test]
+    fn capacity_struct() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        vector.extend(&[0; 5]);
+        let capacity = vector.capacity();
+        assert!(vector.is_spilled());
+        assert_eq!(capacity.total(), 8);
+        assert_eq!(capacity.total(), vector.total_capacity());
+        assert_eq!(<(usize, usize)>::from(capacity), (4, 8));
+        assert_eq!(tinyvec::<u8, 4>::new().capacity().total(), 4);
+
+        let markers: tinyvec<(), 4> = tinyvec::new();
+        assert_eq!(markers.capacity().total(), usize::MAX);
+    }
+
+    #[test]
+    fn remaining_room() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        vector.push(1);
+        assert_eq!(
+            (vector.total_capacity(), vector.remaining_capacity()),
+            (4, 3)
+        );
+
+        vector.extend(&[0; 4]);
+        assert_eq!(
+            (vector.total_capacity(), vector.remaining_capacity()),
+            (8, 3)
+        );
+
+        let mut buf = [std::mem::MaybeUninit::uninit(); 2];
+        let mut borrowed = storagevec::from_buf(&mut buf);
+        borrowed.push(1u8);
+        assert_eq!(borrowed.remaining_capacity(), 1);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn forced_spill() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        vector.extend(&[1, 2]);
+        vector.force_spill(16);
+        assert!(vector.is_spilled());
+        assert_eq!(
+            vector.capacity(),
+            capacity {
+                inline: 4,
+                heap: 16
+            }
+        );
+        assert_eq!(vector, [1, 2]);
+
+        for i in 0..14 {
+            vector.push(i);
+        }
+        assert_eq!(vector.capacity().heap, 16);
+
+        // never shrinks the heap, and keeps room for what's there
+        vector.force_spill(2);
+        assert_eq!(vector.capacity().heap, 16);
+        let mut small: tinyvec<u8, 4> = tinyvec::new();
+        small.extend(&[0; 3]);
+        small.force_spill(0);
+        assert!(small.is_spilled());
+        assert!(small.capacity().heap >= 3);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn keep_inline_prefix() {
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let mut vector: tinyvec<Rc<()>, 3> = tinyvec::new();
+        for _ in 0..10 {
+            vector.push(Rc::clone(&item));
+        }
+        vector.truncate_to_inline();
+        assert_eq!(vector.len(), 3);
+        assert!(!vector.is_spilled());
+        assert_eq!(vector.capacity().heap, 0);
+        assert_eq!(Rc::strong_count(&item), 4);
+
+        let mut short: tinyvec<u8, 3> = tinyvec::new();
+        short.push(1);
+        short.truncate_to_inline();
+        assert_eq!(short, [1]);
+    }
+
+    #[test]
+    fn segment_iterators() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        vector.extend(&[1, 2, 3]);
+        assert_eq!(vector.iter_inline().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(vector.iter_heap().count(), 0);
+
+        vector.extend(&[4, 5]);
+        assert_eq!(vector.iter_inline().count(), 0);
+        assert_eq!(
+            vector.iter_heap().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+
+        for value in vector.iter_mut() {
+            *value *= 2;
+        }
+        assert_eq!(vector.iter().sum::<u8>(), 30);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn debug_summary() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        vector.extend(&[1, 2, 3]);
+        assert_eq!(format!("{vector:?}"), "[1, 2, 3] len=3 inline=3/4 heap=0/0");
+        vector.extend(&[4, 5]);
+        assert_eq!(
+            format!("{vector:?}"),
+            "[1, 2, 3, 4, 5] len=5 inline=0/4 heap=5/8"
+        );
+
+        let mut inline = storagevec::<char, inline<char, 2>>::new();
+        inline.push('a');
+        assert_eq!(format!("{inline:?}"), "['a'] len=1 inline=1/2");
+
+        let mut buf = [std::mem::MaybeUninit::uninit(); 3];
+        let borrowed = storagevec::<u8, borrowed<u8>>::from_buf(&mut buf);
+        assert_eq!(format!("{borrowed:?}"), "[] len=0 borrowed=0/3");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn custom_display() {
+        let mut vector: tinyvec<f32, 2> = tinyvec::new();
+        vector.extend(&[1.0, 2.5, 10.0]);
+        assert_eq!(format!("{vector}"), "[ 1, 2.5, 10 ]");
+        assert_eq!(format!("{vector:.2}"), "[ 1.00, 2.50, 10.00 ]");
+        assert_eq!(format!("{}", vector.display_with(",", "", "")), "1,2.5,10");
+        assert_eq!(
+            format!("{:>4}", vector.display_with(" ", "(", ")")),
+            "(   1  2.5   10)"
+        );
+
+        vector.clear();
+        assert_eq!(format!("{vector}"), "[ ]");
+        assert_eq!(format!("{}", vector.display_with(", ", "[", "]")), "[]");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn hex_dump() {
+        let mut packet: tinyvec<u8, 4> = tinyvec::new();
+        packet.extend(&[0xde, 0xad, 0x00, 0x0f]);
+        assert_eq!(format!("{packet:x}"), "dead000f");
+        packet.push(0xff);
+        assert_eq!(format!("{packet:X}"), "DEAD000FFF");
+        assert_eq!(format!("{packet:#x}"), "0xdead000fff");
+
+        let empty: tinyvec<u8, 4> = tinyvec::new();
+        assert_eq!(format!("{empty:x}"), "");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn literal_macro() {
+        let empty: tinyvec<u8, 4> = tiny_vec![];
+        assert!(empty.is_empty());
+
+        let words: tinyvec<String, 2> =
+            tiny_vec!["a".to_string(), "b".to_string(), "c".to_string(),];
+        assert_eq!(words, ["a", "b", "c"]);
+        assert!(words.is_spilled());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn repeat_macro() {
+        use std::rc::Rc;
+
+        let zeroes: tinyvec<u8, 4> = tiny_vec![0; 3];
+        assert_eq!(zeroes, [0; 3]);
+        assert!(!zeroes.is_spilled());
+
+        let item = Rc::new(());
+        let shared: tinyvec<Rc<()>, 4> = tiny_vec![Rc::clone(&item); 64];
+        assert_eq!(
+            shared.capacity(),
+            capacity {
+                inline: 4,
+                heap: 64
+            }
+        );
+        assert_eq!(Rc::strong_count(&item), 65);
+
+        let none: tinyvec<Rc<()>, 4> = tiny_vec![Rc::clone(&item); 0];
+        assert!(none.is_empty());
+        drop(shared);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn fallible_growth() {
+        let mut inline = storagevec::<String, inline<String, 1>>::new();
+        assert_eq!(inline.try_push("a".to_string()), Ok(()));
+        let error = inline.try_push("b".to_string()).unwrap_err();
+        assert_eq!(error.to_string(), "storage is full");
+        assert_eq!(error.element(), "b");
+        assert_eq!(
+            inline.try_reserve(1),
+            Err(try_reserve_error::capacity_overflow)
+        );
+
+        let mut vector: tinyvec<u8, 2> = tinyvec::new();
+        assert_eq!(vector.try_reserve(10), Ok(()));
+        assert!(vector.is_spilled());
+        assert_eq!(
+            vector.try_reserve(usize::MAX),
+            Err(try_reserve_error::capacity_overflow)
+        );
+        // too big to allocate, but doesn't overflow the length
+        #[cfg(not(miri))]
+        assert_eq!(
+            vector.try_reserve(isize::MAX as usize),
+            Err(try_reserve_error::alloc_failed)
+        );
+        assert!(vector.try_push(1).is_ok());
+
+        let simple: capacity_error = capacity_error::new(1u8).simplify();
+        let boxed: Box<dyn std::error::Error> = Box::new(simple);
+        assert_eq!(boxed.to_string(), "storage is full");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn all_or_nothing_extend() {
+        let mut message = storagevec::<u8, inline<u8, 4>>::new();
+        message.push(0xff);
+        assert!(message.try_extend([1, 2]).is_ok());
+        assert_eq!(message, [0xff, 1, 2]);
+
+        let error = message.try_extend([3, 4, 5]).unwrap_err();
+        assert_eq!(error.element(), 4);
+        assert_eq!(message, [0xff, 1, 2]);
+
+        // a tinyvec just grows
+        let mut vector: tinyvec<u8, 2> = tinyvec::new();
+        assert!(vector.try_extend(0..10).is_ok());
+        assert_eq!(vector.len(), 10);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn bounded_insert() {
+        let mut bounded = storagevec::<u8, inline<u8, 3>>::new();
+        bounded.extend(&[1, 3]);
+        assert!(bounded.try_insert(1, 2).is_ok());
+        assert_eq!(bounded.try_insert(0, 0).unwrap_err().element(), 0);
+        assert_eq!(bounded, [1, 2, 3]);
+
+        let mut vector: tinyvec<u8, 1> = tinyvec::new();
+        assert!(vector.try_insert(0, 2).is_ok());
+        assert!(vector.try_insert(0, 1).is_ok());
+        assert_eq!(vector, [1, 2]);
+    }
+
+    #[test]
+    fn index_errors() {
+        let vector: tinyvec<u8, 2> = tiny_vec![7, 8, 9];
+        assert_eq!(vector.checked_index(2), Ok(&9));
+
+        let error = vector.checked_index(5).unwrap_err();
+        assert_eq!(error, index_error { index: 5, len: 3 });
+        assert_eq!(error.to_string(), "index 5 out of bounds (len 3)");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn partition_by_predicate() {
+        let events: tinyvec<u32, 4> = tiny_vec![1, 2, 3, 4, 5, 6, 7];
+        let (even, odd) = events.partition(|e| e % 2 == 0);
+        assert_eq!(even, [2, 4, 6]);
+        assert_eq!(odd, [1, 3, 5, 7]);
+        assert!(!even.is_spilled());
+
+        let (all, none) = odd.partition(|_| true);
+        assert_eq!(all.len(), 4);
+        assert!(none.is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn head_and_tail() {
+        let mut args: tinyvec<&str, 2> = tiny_vec!["cmd", "-v", "file"];
+        assert_eq!(args.split_first(), Some((&"cmd", &["-v", "file"][..])));
+        assert_eq!(args.split_last(), Some((&"file", &["cmd", "-v"][..])));
+
+        if let Some((last, _)) = args.split_last_mut() {
+            *last = "other";
+        }
+        if let Some((first, rest)) = args.split_first_mut() {
+            *first = rest[1];
+        }
+        assert_eq!(args, ["other", "-v", "other"]);
+
+        let empty: tinyvec<u8, 2> = tinyvec::new();
+        assert_eq!(empty.split_first(), None);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn flatten_pieces() {
+        let pieces: tinyvec<tinyvec<u8, 2>, 4> =
+            tiny_vec![tiny_vec![1, 2], tiny_vec![], tiny_vec![3, 4, 5]];
+        let flat: tinyvec<u8, 8> = pieces.concat();
+        assert_eq!(flat, [1, 2, 3, 4, 5]);
+        let joined: tinyvec<u8, 2> = pieces.join(&0);
+        assert_eq!(joined, [1, 2, 0, 0, 3, 4, 5]);
+
+        let words: tinyvec<&[u8], 4> = tiny_vec![&b"ab"[..], b"c"];
+        let line: tinyvec<u8, 8> = words.join(&b' ');
+        assert_eq!(line, *b"ab c");
+        let none: tinyvec<&[u8], 4> = tinyvec::new();
+        assert!(none.join::<u8, 4>(&b' ').is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn repeated_pattern() {
+        let pattern: tinyvec<u8, 4> = tiny_vec![1, 2];
+        assert_eq!(pattern.repeat(2), [1, 2, 1, 2]);
+        assert!(!pattern.repeat(2).is_spilled());
+
+        let long = pattern.repeat(5);
+        assert_eq!(long.len(), 10);
+        assert_eq!(long.capacity().heap, 10);
+        assert_eq!(long.as_slice()[8..], [1, 2]);
+        assert!(pattern.repeat(0).is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn concatenation_operators() {
+        let a: tinyvec<String, 2> = tiny_vec!["a".to_string()];
+        let b: tinyvec<String, 2> = tiny_vec!["b".to_string(), "c".to_string()];
+        let mut c = a + b;
+        assert_eq!(c, ["a", "b", "c"]);
+
+        c += storagevec::<String, inline<String, 1>>::from_iter(["d".to_string()]);
+        c += &["e".to_string()][..];
+        assert_eq!(c, ["a", "b", "c", "d", "e"]);
+
+        let bytes: tinyvec<u8, 4> = tinyvec::new();
+        assert_eq!(bytes + &[1, 2][..], [1, 2]);
+    }
+
+    #[test]
+    fn borrowing_numeric_helpers() {
+        let values: tinyvec<u32, 2> = tiny_vec![4, 1, 9, 3];
+        assert_eq!(values.sum::<u32>(), 17);
+        assert_eq!(values.min(), Some(&1));
+        assert_eq!(values.max(), Some(&9));
+        assert_eq!(values.len(), 4);
+
+        let floats: tinyvec<f64, 2> = tiny_vec![0.5, 0.25];
+        assert_eq!(floats.sum::<f64>(), 0.75);
+
+        let empty: tinyvec<u32, 2> = tinyvec::new();
+        assert_eq!(empty.sum::<u32>(), 0);
+        assert_eq!(empty.max(), None);
+    }
+
+    #[test]
+    fn runs_by_key() {
+        let events: tinyvec<(u8, char), 4> =
+            tiny_vec![(1, 'a'), (1, 'b'), (2, 'c'), (3, 'd'), (3, 'e')];
+        let runs: Vec<usize> = events
+            .chunk_by(|a, b| a.0 == b.0)
+            .map(|run| run.len())
+            .collect();
+        assert_eq!(runs, [2, 1, 2]);
+
+        let empty: tinyvec<u8, 4> = tinyvec::new();
+        assert_eq!(empty.chunk_by(|a, b| a == b).count(), 0);
+    }
+
+    #[test]
+    fn split_on_separator() {
+        let line: tinyvec<u8, 8> = b"GET /a  HTTP".iter().copied().collect();
+        let words: Vec<&[u8]> = line.split(|b| *b == b' ').collect();
+        assert_eq!(words, [&b"GET"[..], b"/a", b"", b"HTTP"]);
+
+        let empty: tinyvec<u8, 8> = tinyvec::new();
+        assert_eq!(empty.split(|b| *b == b' ').count(), 1);
+    }
+
+    #[test]
+    fn sliding_windows() {
+        // spilled, so the windows run over the heap copy
+        let data: tinyvec<u32, 2> = tiny_vec![1, 2, 3, 4, 5];
+        let sums: Vec<u32> = data.windows(3).map(|w| w.iter().sum()).collect();
+        assert_eq!(sums, [6, 9, 12]);
+        assert_eq!(data.windows(6).count(), 0);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn chunked_iteration() {
+        let mut data: tinyvec<u8, 4> = (1..=7).collect();
+        let lens: Vec<usize> = data.chunks(3).map(|c| c.len()).collect();
+        assert_eq!(lens, [3, 3, 1]);
+
+        let exact = data.chunks_exact(3);
+        assert_eq!(exact.remainder(), [7]);
+        assert_eq!(exact.count(), 2);
+
+        for chunk in data.chunks_mut(2) {
+            chunk.reverse();
+        }
+        assert_eq!(data, [2, 1, 4, 3, 6, 5, 7]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn small_strings() {
+        let mut name: tinystring<8> = tinystring::new();
+        name.push_str("héllo");
+        assert_eq!(name.as_str(), "héllo");
+        assert_eq!(name.len(), 6);
+        assert!(!name.is_spilled());
+
+        name.push_str(", world");
+        assert!(name.is_spilled());
+        assert_eq!(name.clone().as_str(), "héllo, world");
+        assert_eq!(format!("{name:?}"), "\"héllo, world\"");
+    }
+
+    #[test]
+    fn string_api() {
+        use std::fmt::Write;
+
+        let mut line: tinystring<8> = tinystring::new();
+        let key = "id";
+        write!(line, "{key}-{:02}", 7).unwrap();
+        line.push('→');
+        assert_eq!(&*line, "id-07→");
+        assert!(line.starts_with("id"));
+
+        assert_eq!(line.pop(), Some('→'));
+        line.make_ascii_uppercase();
+        assert_eq!(line.as_str(), "ID-07");
+        line.truncate(2);
+        assert_eq!(line.as_str(), "ID");
+    }
+
+    #[test]
+    #[should_panic(expected = "not a char boundary")]
+    fn string_truncate_mid_char() {
+        let mut s: tinystring<8> = tinystring::new();
+        s.push_str("é");
+        s.truncate(1);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn string_traits() {
+        let parsed: tinystring<4> = "key".parse().unwrap();
+        let owned: tinystring<16> = String::from("key").into();
+        assert_eq!(parsed, owned);
+        assert_eq!(parsed, "key");
+        assert_eq!("key", parsed);
+        assert_eq!(format!("[{parsed:>5}]"), "[  key]");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn string_serde() {
+        let name: tinystring<4> = "résumé".into();
+        let json = serde_json::to_string(&name).unwrap();
+        assert_eq!(json, "\"résumé\"");
+        let back: tinystring<4> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, name);
+        assert!(serde_json::from_str::<tinystring<4>>("[1, 2]").is_err());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn fixed_capacity() {
+        let mut frame: stackvec<u16, 3> = stackvec::new();
+        assert!(frame.push(1).is_ok());
+        assert!(frame.insert(0, 0).is_ok());
+        assert!(frame.push(2).is_ok());
+        assert!(frame.is_full());
+        assert_eq!(frame.push(3).unwrap_err().element(), 3);
+        assert_eq!(frame.insert(1, 9).unwrap_err().element(), 9);
+        assert_eq!(*frame, [0, 1, 2]);
+
+        assert_eq!(frame.pop(), Some(2));
+        assert!(frame.push(5).is_ok());
+        assert_eq!(frame.into_iter().collect::<Vec<_>>(), [0, 1, 5]);
+
+        assert_eq!(
+            std::mem::size_of::<stackvec<u16, 3>>(),
+            3 * 2 + 2 + std::mem::size_of::<usize>()
+        );
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn slice_backed() {
+        let mut dma = [0u8; 4];
+        {
+            let mut packet: slicevec<u8> = slicevec::from_slice(&mut dma);
+            packet.extend(&[0xaa, 0xbb]);
+            packet.insert(0, 0x01);
+            assert_eq!(packet.remove(1), Some(0xaa));
+            packet.push(0xcc);
+            assert_eq!(packet.try_push(1).ok(), Some(()));
+            assert_eq!(packet.try_push(2).unwrap_err().element(), 2);
+            assert_eq!(packet, [0x01, 0xbb, 0xcc, 1]);
+        }
+        assert_eq!(dma, [0x01, 0xbb, 0xcc, 1]);
+    }
+
+    #[test]
+    fn deque_against_model() {
+        use std::collections::VecDeque;
+
+        let mut seed = 0x9e37_79b9_u32;
+        let mut deque: tinydeque<String, 3> = tinydeque::new();
+        let mut model: VecDeque<String> = VecDeque::new();
+        for step in 0..1000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            match seed % 5 {
+                0 => {
+                    deque.push_back(step.to_string());
+                    model.push_back(step.to_string());
+                }
+                1 => {
+                    deque.push_front(step.to_string());
+                    model.push_front(step.to_string());
+                }
+                2 => assert_eq!(deque.pop_front(), model.pop_front()),
+                3 => assert_eq!(deque.pop_back(), model.pop_back()),
+                _ => assert_eq!(deque.get(seed as usize % 4), model.get(seed as usize % 4)),
+            }
+            assert_eq!(deque.len(), model.len());
+            assert!(deque.iter().eq(model.iter()), "step {step}");
+        }
+        assert_eq!(deque.front(), model.front());
+        assert_eq!(deque.back(), model.back());
+    }
+
+    #[test]
+    fn deque_wraps_inline() {
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let mut deque: tinydeque<Rc<()>, 4> = tinydeque::new();
+        for _ in 0..10 {
+            deque.push_back(Rc::clone(&item));
+            deque.push_back(Rc::clone(&item));
+            deque.pop_front();
+            deque.pop_front();
+        }
+        deque.push_front(Rc::clone(&item));
+        deque.push_back(Rc::clone(&item));
+        assert!(!deque.is_spilled());
+
+        for _ in 0..5 {
+            deque.push_front(Rc::clone(&item));
+        }
+        assert!(deque.is_spilled());
+        assert_eq!(deque.len(), 7);
+        drop(deque);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn small_map() {
+        let mut map: tinymap<String, u32, 2> = tinymap::new();
+        assert_eq!(map.insert("a".to_string(), 1), None);
+        assert_eq!(map.insert("b".to_string(), 2), None);
+        assert_eq!(map.insert("a".to_string(), 3), Some(1));
+        assert!(!map.is_spilled());
+
+        map.insert("c".to_string(), 4);
+        assert!(map.is_spilled());
+        if let Some(v) = map.get_mut("b") {
+            *v += 10;
+        }
+        assert_eq!(map.remove("a"), Some(3));
+        assert_eq!(map.remove("a"), None);
+        assert!(map.contains_key("c"));
+        assert_eq!(format!("{map:?}"), r#"{"b": 12, "c": 4}"#);
+
+        let collected: tinymap<u8, char, 4> = [(1, 'x'), (2, 'y'), (1, 'z')].into_iter().collect();
+        assert_eq!(collected.len(), 2);
+        assert_eq!(collected.values().collect::<String>(), "zy");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn small_set() {
+        let mut ids: tinyset<String, 2> = tinyset::new();
+        assert!(ids.insert("a".to_string()));
+        assert!(ids.insert("b".to_string()));
+        assert!(!ids.insert("a".to_string()));
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.is_spilled());
+
+        assert!(ids.insert("c".to_string()));
+        assert!(ids.remove("b"));
+        assert!(!ids.remove("b"));
+        assert!(ids.contains("c"));
+        assert_eq!(format!("{ids:?}"), r#"{"a", "c"}"#);
+
+        let digits: tinyset<u8, 4> = [3, 1, 3, 2, 1].into_iter().collect();
+        assert_eq!(digits.as_slice(), [3, 1, 2]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn bit_set() {
+        let mut a: tinybitset<130, 3> = tinybitset::new();
+        for bit in [0, 64, 129] {
+            a.set(bit);
+        }
+        assert_eq!(a.count_ones(), 3);
+        assert_eq!(a.iter().collect::<Vec<_>>(), [0, 64, 129]);
+
+        let mut b: tinybitset<130, 3> = tinybitset::new();
+        b.set(64);
+        b.set(100);
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), [64]);
+        assert_eq!((a | b).count_ones(), 4);
+        assert_eq!(format!("{:?}", a ^ b), "{0, 100, 129}");
+
+        a.clear(64);
+        assert!(!a.test(64));
+        a.reset();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn bit_out_of_range() {
+        let mut flags: tinybitset<8> = tinybitset::new();
+        flags.set(8);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn priority_queue() {
+        let mut top: tinyheap<u32, 4> = tinyheap::new();
+        for value in [5, 1, 8, 3, 9, 2] {
+            top.push(value);
+        }
+        assert!(top.is_spilled());
+        assert_eq!(top.peek(), Some(&9));
+
+        let mut drained = Vec::new();
+        while let Some(value) = top.pop() {
+            drained.push(value);
+        }
+        assert_eq!(drained, [9, 8, 5, 3, 2, 1]);
+
+        let built: tinyheap<i32, 8> = [4, -1, 7, 0, 7].into_iter().collect();
+        assert_eq!(built.peek(), Some(&7));
+        assert_eq!(built.into_sorted(), [-1, 0, 4, 7, 7]);
+    }
+
+    #[test]
+    fn kept_sorted() {
+        let mut keys: sortedvec<(u8, char), 2> = sortedvec::new();
+        assert_eq!(keys.insert((5, 'a')), 0);
+        assert_eq!(keys.insert((1, 'b')), 0);
+        assert_eq!(keys.insert((5, 'a')), 2);
+        assert_eq!(keys.insert((3, 'c')), 1);
+        assert_eq!(keys.as_slice(), [(1, 'b'), (3, 'c'), (5, 'a'), (5, 'a')]);
+
+        assert!(keys.contains(&(3, 'c')));
+        assert_eq!(keys.remove(&(5, 'a')), Some((5, 'a')));
+        assert_eq!(keys.range((2, 'a')..=(5, 'a')), [(3, 'c'), (5, 'a')]);
+        assert_eq!(keys.range(..(1, 'b')), []);
+        assert_eq!(keys.last(), Some(&(5, 'a')));
+
+        let words: sortedvec<String, 4> = ["b", "c", "a"].iter().map(|s| s.to_string()).collect();
+        assert!(words.contains("a"));
+        use std::ops::Bound;
+        let from_b = (Bound::Included("b"), Bound::Unbounded);
+        assert_eq!(words.range::<str, _>(from_b), ["b", "c"]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn copy_on_write() {
+        fn unescape(input: &[u8]) -> tinycow<'_, u8, 8> {
+            let mut out = tinycow::from(input);
+            if input.contains(&b'\\') {
+                let owned = out.to_mut();
+                owned.clear();
+                let mut bytes = input.iter();
+                while let Some(&b) = bytes.next() {
+                    owned.push(if b == b'\\' {
+                        *bytes.next().unwrap()
+                    } else {
+                        b
+                    });
+                }
+            }
+            out
+        }
+
+        let plain = unescape(b"abc");
+        assert!(plain.is_borrowed());
+        assert_eq!(&*plain, b"abc");
+
+        let escaped = unescape(b"a\\\"b");
+        assert!(escaped.is_owned());
+        assert_eq!(escaped.into_owned(), *b"a\"b");
+
+        let borrowed: tinycow<u8, 2> = tinycow::from(&b"xyz"[..]);
+        assert_eq!(borrowed.into_owned(), *b"xyz");
+    }
+
+    #[test]
+    fn pool_reuses_heap() {
+        let mut pool: vecpool<u32, 2> = vecpool::new(1);
+        let mut first = pool.take();
+        first.extend(&[1, 2, 3, 4]);
+        let heap = first.as_slice().as_ptr();
+        pool.give(first);
+        pool.give(tinyvec::from_iter(0..10));
+        pool.give(tiny_vec![1]);
+        assert_eq!(pool.len(), 1);
+
+        let again = pool.take();
+        assert!(again.is_empty());
+        assert_eq!(again.as_slice().as_ptr(), heap);
+        assert!(pool.take().capacity().heap == 0);
+    }
+
+    #[test]
+    fn concurrent_push() {
+        let mut log: appendvec<String, 64> = appendvec::new();
+        std::thread::scope(|s| {
+            for worker in 0..4 {
+                let log = &log;
+                s.spawn(move || {
+                    for frame in 0..20 {
+                        let _ = log.push(format!("{worker}:{frame}"));
+                    }
+                });
+            }
+        });
+        assert_eq!(log.len(), 64);
+        assert!(log.push("late".into()).is_err());
+        let mut seen = log.as_slice().to_vec();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 64);
+
+        log.clear();
+        assert!(log.is_empty());
+        log.push("again".into()).unwrap();
+        assert_eq!(log.as_slice(), ["again"]);
+    }
+
+    #[test]
+    fn spsc_wraps_and_drops() {
+        let mut queue: spsc<Box<u32>, 3> = spsc::new();
+        let (mut tx, mut rx) = queue.split();
+        for round in 0..5 {
+            for i in 0..3 {
+                tx.try_send(Box::new(round * 3 + i)).unwrap();
+            }
+            assert_eq!(*tx.try_send(Box::new(99)).unwrap_err().element(), 99);
+            assert_eq!(rx.try_recv().as_deref(), Some(&(round * 3)));
+            assert_eq!(rx.try_recv().as_deref(), Some(&(round * 3 + 1)));
+            assert_eq!(rx.try_recv().as_deref(), Some(&(round * 3 + 2)));
+            assert!(rx.try_recv().is_none());
+        }
+        tx.try_send(Box::new(7)).unwrap();
+        assert_eq!(queue.len(), 1);
+
+        let mut empty: spsc<u8, 0> = spsc::new();
+        assert!(empty.split().0.try_send(1).is_err());
+    }
+
+    #[test]
+    fn slotmap_generations() {
+        let mut map: tinyslotmap<String, 2> = tinyslotmap::new();
+        let a = map.insert("a".into());
+        let b = map.insert("b".into());
+        let c = map.insert("c".into());
+        assert!(map.is_spilled());
+        assert_eq!(map.remove(b).as_deref(), Some("b"));
+        assert_eq!(map.remove(b), None);
+
+        let d = map.insert("d".into());
+        assert_ne!(b, d);
+        assert!(!map.contains(b));
+        map.get_mut(d).unwrap().push('!');
+        assert_eq!(map.len(), 3);
+        let live: Vec<_> = map
+            .iter()
+            .map(|(key, value)| (key, value.as_str()))
+            .collect();
+        assert_eq!(live, [(a, "a"), (d, "d!"), (c, "c")]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn bytes_share_when_spilled() {
+        let mut packet = tinybytes::<3>::from(b"0123456789".to_vec());
+        let copy = packet.clone();
+        let tail = packet.split_off(6);
+        assert!(!tail.is_inline());
+        assert_eq!(tail.as_ptr(), copy[6..].as_ptr());
+        let head = packet.split_to(2);
+        assert_eq!(
+            format!("{head:?} {packet:?} {tail:?}"),
+            r#"b"01" b"2345" b"6789""#
+        );
+        assert_eq!(packet.as_ptr(), copy[2..].as_ptr());
+
+        let small = tinybytes::<3>::from(&b"ab\xff"[..]);
+        assert!(small.is_inline());
+        assert_eq!(small.slice(1..=2), *b"b\xff".as_slice());
+        assert_eq!(format!("{small:?}"), r#"b"ab\xff""#);
+    }
+
+    #[test]
+    fn lru_evicts_least_recent() {
+        let mut cache: tinylru<u32, String, 3> = tinylru::new();
+        for id in 0..3 {
+            assert_eq!(cache.insert(id, id.to_string()), None);
+        }
+        cache.get(&0).unwrap().push('!');
+        assert_eq!(cache.peek(&1).map(String::as_str), Some("1"));
+        assert_eq!(cache.insert(3, "3".into()), Some((1, "1".into())));
+        assert_eq!(cache.insert(2, "two".into()), Some((2, "2".into())));
+        let order: Vec<_> = cache.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        assert_eq!(order, [(0, "0!"), (3, "3"), (2, "two")]);
+        assert_eq!(cache.len(), cache.capacity());
+
+        let mut none: tinylru<u8, u8, 0> = tinylru::new();
+        assert_eq!(none.insert(1, 1), Some((1, 1)));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn chunked_addresses_stable() {
+        let mut vec: chunkedvec<String, 3, 1> = chunkedvec::new();
+        let pinned: *const String = vec.push("first".into());
+        vec.extend((0..10).map(|n| n.to_string()));
+        assert_eq!(vec.len(), 11);
+        assert_eq!(vec.chunk_count(), 4);
+        assert_eq!(unsafe { &*pinned }, "first");
+        assert_eq!(
+            vec.chunks().map(<[_]>::len).collect::<Vec<_>>(),
+            [3, 3, 3, 2]
+        );
+
+        assert_eq!(vec.pop().as_deref(), Some("9"));
+        assert_eq!(vec.pop().as_deref(), Some("8"));
+        assert_eq!(vec.chunk_count(), 3);
+        vec.get_mut(0).unwrap().push('!');
+        assert_eq!(vec.iter().next().map(String::as_str), Some("first!"));
+        assert_eq!(vec.get(9), None);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn grid_indexing() {
+        let mut kernel: tinygrid<i32, 3, 2, 4> = tinygrid::from_fn(|x, y| (y * 10 + x) as i32);
+        assert!(kernel.is_spilled());
+        assert_eq!(format!("{kernel:?}"), "[[0, 1, 2], [10, 11, 12]]");
+        assert_eq!(kernel.column(2).copied().collect::<Vec<_>>(), [2, 12]);
+        assert_eq!(kernel.get(3, 0), None);
+        kernel.row_mut(1)[0] = -1;
+        assert_eq!(kernel[(0, 1)], -1);
+        kernel.fill(0);
+        assert!(kernel.as_slice().iter().all(|&c| c == 0));
+
+        let empty: tinygrid<u8, 0, 3> = tinygrid::default();
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (0, 2) out of range for 2x2 grid")]
+    fn grid_out_of_range() {
+        let grid: tinygrid<u8, 2, 2> = tinygrid::default();
+        let _ = grid[(0, 2)];
+    }
+
+    #[test]
+    #[cfg(feature = "futures")]
+    fn stream_batches() {
+        use crate::stream::collect_stream;
+        use std::future::Future;
+        use std::task::{Context, Poll, Waker};
+
+        let messages: tinyvec<u8, 8> = tiny_vec![1, 2, 3, 4, 5];
+        let mut messages = messages.into_iter();
+        let mut batches = std::pin::pin!(async {
+            let first: tinyvec<u8, 2> = collect_stream(&mut messages, 2).await;
+            let rest: tinyvec<u8, 2> = collect_stream(&mut messages, 8).await;
+            (first, rest)
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready((first, rest)) = batches.as_mut().poll(&mut cx) else {
+            panic!("owned_iter is always ready");
+        };
+        assert_eq!(first, [1, 2]);
+        assert_eq!(rest, [3, 4, 5]);
+    }
+
+    #[test]
+    #[cfg(feature = "encoding")]
+    fn hex_and_base64_roundtrip() {
+        use crate::decode_error;
+
+        for len in 0..8 {
+            let bytes: tinyvec<u8, 4> = (0..len).map(|b: u8| b.wrapping_mul(37) ^ 0xc5).collect();
+            let hex: tinystring<16> = bytes.to_hex();
+            assert_eq!(tinyvec::<u8, 4>::from_hex(&hex).unwrap(), bytes);
+            let base64: tinystring<16> = bytes.to_base64();
+            assert_eq!(base64.len() % 4, 0);
+            assert_eq!(tinyvec::<u8, 4>::from_base64(&base64), Ok(bytes));
+        }
+        assert_eq!(
+            tinyvec::<u8, 4>::from_base64("Zm9v"),
+            Ok(tinyvec::from_iter(*b"foo"))
+        );
+        assert_eq!(
+            tinyvec::<u8, 4>::from_hex("abc"),
+            Err(decode_error::invalid_length)
+        );
+        assert_eq!(
+            tinyvec::<u8, 4>::from_hex("0g"),
+            Err(decode_error::invalid_byte { at: 1 })
+        );
+        assert_eq!(
+            tinyvec::<u8, 4>::from_base64("Z"),
+            Err(decode_error::invalid_length)
+        );
+        assert_eq!(
+            tinyvec::<u8, 4>::from_base64("Zm=v"),
+            Err(decode_error::invalid_byte { at: 2 })
+        );
+        assert_eq!(
+            tinyvec::<u8, 4>::from_base64("Zm9=="),
+            Err(decode_error::invalid_byte { at: 3 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sqlx")]
+    fn sqlx_encodes_raw_bytes() {
+        use sqlx::encode::IsNull;
+        use sqlx::postgres::{PgArgumentBuffer, Postgres};
+        use sqlx::{Encode, Type};
+
+        let mut buf = PgArgumentBuffer::default();
+        let bytes: tinyvec<u8, 4> = tiny_vec![0, 0xff];
+        assert!(matches!(
+            Encode::<Postgres>::encode_by_ref(&bytes, &mut buf),
+            Ok(IsNull::No)
+        ));
+        assert!(matches!(
+            Encode::<Postgres>::encode_by_ref(&tinystring::<4>::from("hi"), &mut buf),
+            Ok(IsNull::No)
+        ));
+        assert_eq!(buf.as_slice(), b"\0\xffhi");
+        assert_eq!(
+            <tinystring<4> as Type<Postgres>>::type_info(),
+            <String as Type<Postgres>>::type_info()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "schemars")]
+    fn json_schemas() {
+        let list = schemars::schema_for!(tinyvec<u32, 4>);
+        assert_eq!(list.get("type").unwrap(), "array");
+        assert!(list.get("maxItems").is_none());
+
+        let fixed = schemars::schema_for!(stackvec<u32, 4>);
+        assert_eq!(fixed.get("maxItems").unwrap(), 4);
+        assert_eq!(fixed.get("items").unwrap().get("format").unwrap(), "uint32");
+
+        let name = schemars::schema_for!(tinystring<8>);
+        assert_eq!(name.get("type").unwrap(), "string");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn extend_from_references() {
+        let mut names: tinyvec<String, 2> = tinyvec::new();
+        let map = tinymap::<u8, String, 4>::from_iter([(1, "a".to_string()), (2, "b".into())]);
+        names.extend(map.values());
+        names.extend(&["c".to_string()]);
+        names.extend_from_slice(&["d".to_string()]);
+        assert_eq!(names, ["a", "b", "c", "d"]);
+
+        let mut ids: tinyvec<u32, 4> = tinyvec::new();
+        ids.extend([7, 8].iter());
+        ids.extend(vec![9]);
+        assert_eq!(ids, [7, 8, 9]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn collect_results_and_options() {
+        let parsed: Result<tinyvec<u8, 4>, _> = "1 2 3".split(' ').map(str::parse).collect();
+        assert_eq!(parsed.unwrap(), [1, 2, 3]);
+
+        let mut seen = 0;
+        let failed: Result<tinyvec<u8, 4>, _> = ["1", "x", "3"]
+            .into_iter()
+            .inspect(|_| seen += 1)
+            .map(str::parse::<u8>)
+            .collect();
+        assert!(failed.is_err());
+        assert_eq!(seen, 2);
+
+        let halves: Option<tinyvec<u32, 2>> = [2, 4, 7]
+            .iter()
+            .map(|n| (n % 2 == 0).then_some(n / 2))
+            .collect();
+        assert_eq!(halves, None);
+        let fixed: Option<storagevec<u32, inline<u32, 2>>> =
+            [Some(1), Some(2)].into_iter().collect();
+        assert_eq!(fixed.unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn extend_rereserves_from_hint() {
+        // Claims nothing up front, then the real length after one item.
+        struct late_hint(std::ops::Range<u32>, bool);
+        impl Iterator for late_hint {
+            type Item = u32;
+            fn next(&mut self) -> Option<u32> {
+                self.1 = true;
+                self.0.next()
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                if self.1 {
+                    self.0.size_hint()
+                } else {
+                    (0, None)
+                }
+            }
+        }
+
+        let mut vec: tinyvec<u32, 2> = tinyvec::new();
+        vec.extend([0, 1]);
+        vec.extend(late_hint(2..40, false));
+        assert_eq!(vec.len(), 40);
+        assert_eq!(vec.capacity().heap, 40);
+
+        #[cfg(feature = "extend_one")]
+        {
+            let mut reserved: tinyvec<u8, 2> = tinyvec::new();
+            Extend::<u8>::extend_reserve(&mut reserved, 10);
+            reserved.extend_one(1);
+            assert!(reserved.is_spilled() && reserved.capacity().heap >= 10);
+        }
+    }
+
+    #[test]
+    fn owned_iter_skips() {
+        let words: tinyvec<String, 2> = "a b c d e f".split(' ').map(String::from).collect();
+        let mut iter = words.into_iter();
+        assert_eq!(iter.nth(1).as_deref(), Some("b"));
+        assert_eq!(iter.nth_back(1).as_deref(), Some("e"));
+        iter.as_mut_slice()[0].push('!');
+        assert_eq!(iter.as_slice(), ["c!", "d"]);
+        assert_eq!(iter.nth(5), None);
+        assert!(iter.as_slice().is_empty());
+
+        let numbers: tinyvec<u32, 4> = (0..10).collect();
+        assert_eq!(numbers.into_iter().count(), 10);
+        let numbers: tinyvec<u32, 4> = (0..10).collect();
+        assert_eq!(numbers.into_iter().last(), Some(9));
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn drain_range() {
+        let mut ops: tinyvec<String, 2> = "a b c d e".split(' ').map(String::from).collect();
+        let mut drained = ops.drain(1..4);
+        assert_eq!(drained.next_back().as_deref(), Some("d"));
+        assert_eq!(drained.as_slice(), ["b", "c"]);
+        drop(drained);
+        assert_eq!(ops, ["a", "e"]);
+
+        let taken: Vec<_> = ops.drain(..).collect();
+        assert_eq!(taken, ["a", "e"]);
+        assert!(ops.is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn forgotten_drain_leaks_instead_of_exposing() {
+        // Plain integers, so the leak doesn't trip miri's leak check.
+        let mut vec: tinyvec<u32, 4> = (0..6).collect();
+        let mut drained = vec.drain(2..4);
+        assert_eq!(drained.next(), Some(2));
+        std::mem::forget(drained);
+        assert_eq!(vec, [0, 1]);
+        vec.push(9);
+        assert_eq!(vec, [0, 1, 9]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn cursor_edits_in_place() {
+        let mut vec: tinyvec<u32, 4> = (1..=5).collect();
+        let mut cursor = vec.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.insert_before(20);
+        cursor.insert_after(30);
+        assert_eq!(cursor.peek_prev(), Some(&mut 20));
+        assert_eq!(cursor.peek_next(), Some(&mut 30));
+        for _ in 0..10 {
+            cursor.move_next();
+        }
+        assert_eq!(cursor.current(), None);
+        cursor.insert_after(6);
+        assert_eq!(cursor.current(), Some(&mut 6));
+        cursor.move_prev();
+        *cursor.current().unwrap() *= 10;
+        assert_eq!(vec, [1, 20, 3, 30, 4, 50, 6]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn run_length() {
+        let pixels: tinyvec<char, 4> = "aaabccccd".chars().collect();
+        let lengths: Vec<_> = pixels.runs().map(|(c, n)| format!("{n}{c}")).collect();
+        assert_eq!(lengths.concat(), "3a1b4c1d");
+        let runs: tinyvec<(char, usize), 2> = pixels.dedup_with_counts();
+        assert_eq!(runs, [('a', 3), ('b', 1), ('c', 4), ('d', 1)]);
+
+        let empty: tinyvec<char, 4> = tinyvec::new();
+        assert_eq!(empty.runs().count(), 0);
+        assert!(empty.dedup_with_counts::<1>().is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn retain_reports_removed() {
+        let mut words: tinyvec<String, 2> = "a bb c dd e".split(' ').map(String::from).collect();
+        assert_eq!(words.retain(|w| w.len() == 1), 2);
+        assert_eq!(words, ["a", "c", "e"]);
+        assert_eq!(words.retain(|_| true), 0);
+
+        let mut removed: tinyvec<String, 4> = tinyvec::new();
+        let count = words.retain_into(
+            |w| {
+                w.push('!');
+                w != "c!"
+            },
+            &mut removed,
+        );
+        assert_eq!(count, 1);
+        assert_eq!(words, ["a!", "e!"]);
+        assert_eq!(removed, ["c!"]);
+    }
+
+    #[test]
+    fn retain_survives_panic() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let mut vec: tinyvec<Box<u32>, 4> = (0..6).map(Box::new).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            vec.retain(|n| {
+                assert!(**n != 4, "boom");
+                **n % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(vec.iter().map(|b| **b).collect::<Vec<_>>(), [0, 2, 4, 5]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn remove_first_match() {
+        let mut active: tinyvec<String, 4> = "7 3 7".split(' ').map(String::from).collect();
+        assert_eq!(active.remove_item("7").as_deref(), Some("7"));
+        assert_eq!(active, ["3", "7"]);
+        assert_eq!(active.remove_item("9"), None);
+        assert_eq!(active.remove_item(&"7".to_string()).as_deref(), Some("7"));
+        assert_eq!(active, ["3"]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn swap_with_scratch() {
+        let mut front: tinyvec<u32, 2> = (0..3).collect();
+        let mut back = [7, 8, 9];
+        front.swap_with_slice(&mut back);
+        assert_eq!(front, [7, 8, 9]);
+        assert_eq!(back, [0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_with_shorter_slice() {
+        let mut vec: tinyvec<u32, 2> = (0..3).collect();
+        vec.swap_with_slice(&mut [0; 2]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn resize_inline_capacity() {
+        let spilled: tinyvec<String, 2> = "a b c".split(' ').map(String::from).collect();
+        let heap = spilled.as_slice().as_ptr();
+        let still_spilled: tinyvec<String, 1> = spilled.resize_inline();
+        assert_eq!(still_spilled.as_slice().as_ptr(), heap);
+
+        let inline: tinyvec<String, 4> = still_spilled.resize_inline();
+        assert!(!inline.is_spilled());
+        let spilled_again: tinyvec<String, 0> = inline.resize_inline();
+        assert!(spilled_again.is_spilled());
+        assert_eq!(spilled_again, ["a", "b", "c"]);
+
+        let empty: tinyvec<String, 3> = tinyvec::<String, 1>::new().resize_inline();
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn zero_inline_capacity() {
+        let mut vec: tinyvec<u32, 0> = tinyvec::new();
+        assert!(!vec.is_spilled());
+        assert_eq!(vec.capacity(), capacity { inline: 0, heap: 0 });
+        assert!(vec.shrink_to_inline());
+        vec.push(1);
+        assert!(vec.is_spilled());
+        vec.extend([2, 3, 4]);
+        vec.insert(0, 0);
+        assert_eq!(vec.remove(4), Some(4));
+        assert_eq!(vec.pop(), Some(3));
+        assert_eq!((vec.stack_len(), vec.heap_len()), (0, 3));
+        assert_eq!(vec.iter_inline().count(), 0);
+        assert_eq!(vec.repeat(2), [0, 1, 2, 0, 1, 2]);
+        let (even, odd) = vec.partition(|n| n % 2 == 0);
+        assert_eq!((even.as_slice(), odd.as_slice()), (&[0, 2][..], &[1][..]));
+
+        let mut odd = odd;
+        odd.truncate_to_inline();
+        assert!(odd.is_empty() && !odd.is_spilled());
+        let sevens: tinyvec<u8, 0> = tiny_vec![7; 3];
+        assert_eq!(sevens, [7, 7, 7]);
+
+        let mut deque: tinydeque<u32, 0> = tinydeque::new();
+        deque.push_front(1);
+        deque.push_back(2);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [1, 2]);
+        let mut string: tinystring<0> = tinystring::new();
+        string.push_str("heap");
+        assert_eq!(string, "heap");
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn boxed_slab() {
+        let mut vec: boxedvec<String, 3> = boxedvec::new();
+        assert_eq!(vec.capacity(), capacity { inline: 0, heap: 0 });
+        assert_eq!(vec.pop(), None);
+        vec.push("a".into());
+        assert_eq!(vec.capacity(), capacity { inline: 3, heap: 0 });
+        vec.extend(["b".to_string(), "c".into()]);
+        let slab = vec.as_slice().as_ptr();
+        let moved = std::mem::take(&mut vec);
+        assert_eq!(moved.as_slice().as_ptr(), slab);
+        vec = moved;
+        vec.push("d".into());
+        assert!(vec.is_spilled());
+        assert_eq!(vec.capacity().heap, 6);
+        assert_eq!(vec, ["a", "b", "c", "d"]);
+        assert_eq!(
+            format!("{vec:?}"),
+            r#"["a", "b", "c", "d"] len=4 boxed=0/3 heap=4/6"#
+        );
+
+        let mut big: boxedvec<u8, 3> = boxedvec::new();
+        big.extend([1; 10]);
+        assert!(big.is_spilled() && big.len() == 10);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn endian_frame() {
+        let mut frame: tinyvec<u8, 16> = tinyvec::new();
+        frame.put_u16_be(0xcafe);
+        frame.put_u32_le(7);
+        frame.put_i16_be(-2);
+        assert_eq!(frame, [0xca, 0xfe, 7, 0, 0, 0, 0xff, 0xfe]);
+        assert_eq!(frame.get_u16_be(0), Some(0xcafe));
+        assert_eq!(frame.get_u32_le(2), Some(7));
+        assert_eq!(frame.get_i16_be(6), Some(-2));
+        assert_eq!(frame.get_u32_le(6), None);
+        assert_eq!(frame.get_u64_be(usize::MAX), None);
+    }
+
+    #[test]
+    fn size_limits() {
+        struct greedy;
+        impl growth::policy for greedy {
+            fn grow(_: usize, _: usize) -> usize {
+                usize::MAX
+            }
+        }
+
+        // a policy overshooting isize::MAX bytes is cut back, not an overflow
+        let mut vector: tinyvec<u64, 1, Global, greedy> = tinyvec::new();
+        vector.push(1);
+        assert_eq!(
+            vector.try_reserve(isize::MAX as usize / 8),
+            Err(try_reserve_error::capacity_overflow)
+        );
+        #[cfg(not(miri))]
+        assert_eq!(
+            vector.try_reserve(isize::MAX as usize / 8 - 1),
+            Err(try_reserve_error::alloc_failed)
+        );
+
+        let mut units: tinyvec<(), 0> = tinyvec::new();
+        units.push(());
+        assert!(units.try_reserve(usize::MAX).is_err());
+        let huge =
+            std::panic::catch_unwind(|| tinyvec::<u32, 1>::with_heap_capacity(usize::MAX / 2));
+        assert!(huge.is_err());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn veclike_containers() {
+        fn fill<V: veclike<u8>>(out: &mut V) -> usize {
+            out.clear();
+            let mut pushed = 0;
+            while pushed < 5 && out.try_push(pushed as u8).is_ok() {
+                pushed += 1;
+            }
+            out.truncate(4);
+            *out.get_mut(0).unwrap() = 9;
+            assert!(out.pop().is_some());
+            pushed
+        }
+
+        let mut heap = vec![7];
+        let mut tiny: tinyvec<u8, 2> = tinyvec::new();
+        let mut fixed: stackvec<u8, 2> = stackvec::new();
+        assert_eq!(
+            (fill(&mut heap), fill(&mut tiny), fill(&mut fixed)),
+            (5, 5, 2)
+        );
+        assert_eq!(heap, [9, 1, 2]);
+        assert_eq!(tiny, [9, 1, 2]);
+        assert_eq!(fixed.as_slice(), [9]);
+        assert!(!veclike::is_empty(&fixed));
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn cstring_ffi() {
+        let mut name: tinycstring<4> = tinycstring::new("eth").unwrap();
+        assert!(!name.is_spilled());
+        assert_eq!(unsafe { std::ffi::CStr::from_ptr(name.as_ptr()) }, c"eth");
+        assert_eq!(name.push_bytes(b"0\0"), Err(nul_error { at: 1 }));
+        assert_eq!(name.as_bytes(), b"eth");
+        name.push_bytes(b"0").unwrap();
+        assert!(name.is_spilled());
+        assert_eq!(*name, *c"eth0");
+        assert_eq!(format!("{name:?}"), r#""eth0""#);
+        name.clear();
+        assert!(name.is_empty() && name.as_bytes_with_nul() == b"\0");
+        assert_eq!(
+            tinycstring::<0>::from(c"x"),
+            tinycstring::<8>::new("x").unwrap()
+        );
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn arraystring_capacity() {
+        use std::fmt::Write;
+
+        let mut s: arraystring<4> = arraystring::try_from("ab").unwrap();
+        assert_eq!(s.push('é'), Ok(()));
+        assert_eq!(s.remaining(), 0);
+        assert_eq!(s.push('x').unwrap_err().element(), 'x');
+        assert!(write!(s, "{}", 1).is_err());
+        assert_eq!(s.pop(), Some('é'));
+        assert_eq!(s, "ab");
+        assert!(arraystring::<1>::try_from("ab").is_err());
+        assert_eq!(size_of::<arraystring<4>>(), size_of::<[usize; 2]>());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn string_from_utf8_in_place() {
+        let mut bytes: tinyvec<u8, 2> = tinyvec::new();
+        bytes.extend_from_slice("héllo".as_bytes());
+        let heap = bytes.as_slice().as_ptr();
+        let string = tinystring::from_utf8(bytes).unwrap();
+        assert_eq!(string.as_ptr(), heap);
+        assert_eq!(string, "héllo");
+
+        let error = tinystring::<4>::from_utf8(tiny_vec![b'a', 0xc3]).unwrap_err();
+        assert_eq!((error.valid_up_to(), error.error_len()), (1, None));
+        let back = unsafe { tinystring::from_utf8_unchecked(string.into_bytes()) };
+        assert_eq!(back.len(), 6);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn ring_overwrites_oldest() {
+        let mut ring: tinyring<String, 3> = (0..5).map(|i| i.to_string()).collect();
+        assert!(ring.is_full());
+        assert_eq!(
+            ring.as_slices(),
+            (&["2".to_string()][..], &["3".to_string(), "4".into()][..])
+        );
+        assert_eq!(ring.push("5".into()).as_deref(), Some("2"));
+        assert_eq!(ring.pop_oldest().as_deref(), Some("3"));
+        ring.push("6".into());
+        assert_eq!(format!("{ring:?}"), r#"["4", "5", "6"]"#);
+        assert_eq!(ring.oldest().map(String::as_str), Some("4"));
+
+        let mut none: tinyring<u8, 0> = tinyring::new();
+        assert_eq!(none.push(1), Some(1));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "high_water")]
+    fn high_water_mark() {
+        let mut vector: tinyvec<u8, 4> = tinyvec::new();
+        assert_eq!(vector.max_len_seen(), 0);
+        vector.extend_from_slice(&[1, 2, 3]);
+        vector.clear();
+        vector.push(4);
+        assert_eq!(vector.max_len_seen(), 3);
+        vector.extend_from_slice(&[5; 6]);
+        vector.truncate(1);
+        assert!(vector.shrink_to_inline());
+        assert_eq!(vector.resize_inline::<2>().max_len_seen(), 7);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn process_stats() {
+        use crate::stats;
+
+        // other tests run alongside, so only look at growth
+        let before = stats();
+        let mut vector: tinyvec<[u8; 1000], 1> = tinyvec::new();
+        vector.extend_from_slice(&[[0; 1000]; 2]);
+        vector.push([1; 1000]);
+        let during = stats();
+        assert!(during.spills > before.spills);
+        assert!(during.reallocations > before.reallocations);
+        assert!(during.peak_heap_bytes >= 4000);
+        assert!(during.heap_bytes <= during.peak_heap_bytes);
+    }
+
+    #[test]
+    fn pinned_addresses() {
+        use std::cell::Cell;
+        use std::pin::pin;
+
+        let drops = Cell::new(0);
+        struct counted<'a>(&'a Cell<u32>);
+        impl Drop for counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        {
+            let mut nodes = pin!(pinnedvec::<counted, 2>::new());
+            let first = &*nodes.as_mut().push(counted(&drops)).unwrap() as *const counted;
+            assert!(nodes.as_mut().push(counted(&drops)).is_ok());
+            let Err(full) = nodes.as_mut().push(counted(&drops)) else {
+                panic!("pushed past N");
+            };
+            drop(full);
+            assert_eq!(drops.get(), 1);
+            assert!(nodes.as_mut().get_pin_mut(2).is_none());
+            nodes.as_mut().truncate(1);
+            assert_eq!(drops.get(), 2);
+            assert_eq!(nodes.get(0).map(|n| n as *const counted), Some(first));
+        }
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn const_stackvec() {
+        const SQUARES: stackvec<u16, 6> = {
+            let mut squares = stackvec::new();
+            let mut n = 1;
+            while n <= 5 {
+                squares.push_const(n * n);
+                n += 1;
+            }
+            squares
+        };
+        static NAMES: stackvec<&str, 2> = stackvec::from_slice(&["a", "b"]);
+
+        assert_eq!(SQUARES.as_slice(), [1, 4, 9, 16, 25]);
+        assert_eq!(NAMES.as_slice(), ["a", "b"]);
+        let mut copy = SQUARES;
+        assert!(copy.push(36).is_ok() && copy.is_full());
+        assert!(std::panic::catch_unwind(|| stackvec::<u8, 1>::from_slice(&[1, 2])).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "portable_simd")]
+    fn simd_views() {
+        use std::simd::{Simd, num::SimdFloat};
+
+        type aligned = storagevec<f32, inline<f32, 16, usize, align::a32>>;
+        let mut samples = aligned::new();
+        samples.extend((0..11).map(|i| i as f32));
+        let (prefix, lanes, suffix) = samples.as_simd::<4>();
+        assert!(prefix.is_empty());
+        assert_eq!((lanes.len(), suffix), (2, &[8.0, 9.0, 10.0][..]));
+        assert_eq!(lanes[1].reduce_sum(), 4.0 + 5.0 + 6.0 + 7.0);
+
+        for lane in samples.as_simd_mut::<4>().1 {
+            *lane *= Simd::splat(2.0);
+        }
+        assert_eq!(samples.as_slice()[7..9], [14.0, 8.0]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn sorting_networks() {
+        // 0-1 principle: a network sorting every 0/1 input sorts everything
+        for len in 0..=8 {
+            for bits in 0u32..1 << len {
+                let mut vector: tinyvec<u32, 8> = (0..len).map(|i| bits >> i & 1).collect();
+                vector.sort_unstable();
+                assert!(vector.as_slice().is_sorted(), "{len} elements, {bits:b}");
+            }
+        }
+
+        let mut words: tinyvec<&str, 4> = tiny_vec!["pear", "fig", "apple", "kiwi", "date"];
+        words.sort_unstable_by_key(|w| w.len());
+        assert_eq!(
+            (words.as_slice().first(), words.as_slice().last()),
+            (Some(&"fig"), Some(&"apple"))
+        );
+        words.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(words, ["pear", "kiwi", "fig", "date", "apple"]);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn rayon_parallel() {
+        use rayon::prelude::*;
+
+        let mut vector: tinyvec<u64, 4> = tinyvec::new();
+        vector.par_extend((0..1000u64).into_par_iter().map(|i| (i * 7919) % 1000));
+        assert_eq!(vector.len(), 1000);
+        assert_eq!(vector.as_slice()[1], 919);
+        vector.par_iter_mut().for_each(|v| *v += 1);
+        vector.par_sort_unstable();
+        assert_eq!(vector.par_iter().sum::<u64>(), 500_500);
+        vector.par_sort_by(|a, b| b.cmp(a));
+        assert_eq!(vector.as_slice()[..2], [1000, 999]);
+        assert_eq!(vector.into_par_iter().max(), Some(1000));
+    }
+
+    #[test]
+    fn cow_slices() {
+        use std::borrow::Cow;
+
+        let borrowed: tinyvec<String, 2> = Cow::Borrowed(&["a".to_string()][..]).into();
+        assert!(!borrowed.is_spilled());
+        let owned: tinyvec<String, 2> = Cow::<[String]>::Owned(vec!["b".into(); 3]).into();
+        assert!(owned.is_spilled() && owned.len() == 3);
+
+        let cow: Cow<'_, [String]> = (&owned).into();
+        assert!(matches!(cow, Cow::Borrowed(slice) if slice.as_ptr() == owned.as_slice().as_ptr()));
+    }
+
+    #[test]
+    fn into_slice_pointers() {
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let names: tinyvec<String, 2> = tiny_vec!["a".to_string(), "b".into()];
+        let shared: Arc<[String]> = names.into();
+        let worker = std::thread::spawn({
+            let shared = Arc::clone(&shared);
+            move || shared.concat()
+        });
+        assert_eq!(worker.join().unwrap(), "ab");
+
+        let spilled: tinyvec<u8, 1> = tiny_vec![1, 2, 3];
+        assert_eq!(&*Rc::<[u8]>::from(spilled), [1, 2, 3]);
+        let boxed: Box<[String]> = tinyvec::<String, 0>::new().into();
+        assert!(boxed.is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn merge_sorted() {
+        let ids = |ids: &[u32]| -> tinyvec<u32, 4> { ids.iter().copied().collect() };
+        assert_eq!(
+            ids(&[1, 3, 5]).merge(ids(&[2, 3, 6, 7])),
+            [1, 2, 3, 3, 5, 6, 7]
+        );
+        assert_eq!(ids(&[]).merge(ids(&[1])), [1]);
+        assert_eq!(ids(&[1]).merge(ids(&[])), [1]);
+
+        // ties keep the left element first
+        let mut left: tinyvec<(u8, &str), 2> = tiny_vec![(1, "l"), (2, "l")];
+        left.merge_with::<spill<_, 1>>(tiny_vec![(0, "r"), (2, "r")]);
+        assert_eq!(left, [(0, "r"), (1, "l"), (2, "l"), (2, "r")]);
+
+        // a panicking comparison still leaves every element owned once
+        #[derive(PartialEq, Eq)]
+        struct touchy(String);
+        impl PartialOrd for touchy {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for touchy {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                assert!(self.0 != "x" && other.0 != "x");
+                self.0.cmp(&other.0)
+            }
+        }
+        let mut a: tinyvec<touchy, 2> = tiny_vec![touchy("a".into()), touchy("x".into())];
+        let b: tinyvec<touchy, 2> = tiny_vec![touchy("b".into()), touchy("c".into())];
+        let merged = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| a.merge_with(b)));
+        assert!(merged.is_err());
+        assert_eq!(a.len(), 4);
+    }
+
+    #[test]
+    fn sorted_set_algebra() {
+        let a: sortedvec<u8, 4> = [1, 2, 2, 3, 9].into_iter().collect();
+        let b: sortedvec<u8, 2> = [2, 3, 4].into_iter().collect();
+        assert_eq!(a.intersection(&b).as_slice(), [2, 3]);
+        assert_eq!(a.union(&b).as_slice(), [1, 2, 2, 3, 4, 9]);
+        assert_eq!(a.difference(&b).as_slice(), [1, 2, 9]);
+        assert_eq!(a.symmetric_difference(&b).as_slice(), [1, 2, 4, 9]);
+        assert!(a.intersection(&b).is_subset(&b));
+        assert!(!a.is_subset(&b));
+        assert!(sortedvec::<u8, 1>::new().is_subset(&b));
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn strict_inline_collect() {
+        let mut words = ["a", "b", "c"].into_iter().map(String::from);
+        let (kept, extra) = tinyvec::<String, 1>::try_from_iter(words.by_ref())
+            .unwrap_err()
+            .element();
+        assert!(!kept.is_spilled());
+        assert_eq!((kept, extra.as_str()), (tiny_vec!["a".to_string()], "b"));
+        assert_eq!(words.next().as_deref(), Some("c"));
+
+        let empty = tinyvec::<u8, 0>::try_from_iter(std::iter::empty()).unwrap();
+        assert!(empty.is_empty());
+        assert!(tinyvec::<u8, 0>::try_from_iter([1]).is_err());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn map_keeps_capacity() {
+        let words: tinyvec<&str, 2> = tiny_vec!["1", "22"];
+        let lengths: tinyvec<usize, 2> = words.map(str::len);
+        assert!(!lengths.is_spilled());
+        assert_eq!(lengths, [1, 2]);
+
+        let spilled: tinyvec<&str, 2> = tiny_vec!["1", "2", "3"];
+        let parsed = spilled.try_map(str::parse::<u8>).unwrap();
+        assert_eq!(parsed.capacity(), capacity { inline: 2, heap: 3 });
+        let bad: tinyvec<&str, 2> = tiny_vec!["1", "x", "3"];
+        assert!(bad.try_map(str::parse::<u8>).is_err());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn map_in_place_panics() {
+        let mut scores: tinyvec<u32, 2> = tiny_vec![1, 2, 3];
+        scores.for_each_mut(|s| *s *= 10);
+        scores.map_in_place(|s| s + 1);
+        assert_eq!(scores, [11, 21, 31]);
+
+        let mut names: tinyvec<String, 4> = tiny_vec!["a".to_string(), "b".into(), "c".into()];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            names.map_in_place(|name| {
+                assert!(name != "b");
+                name + "!"
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(names, ["a!"]);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn zip_with_pairs() {
+        let keys: tinyvec<&str, 2> = tiny_vec!["a", "b", "c"];
+        let values: tinyvec<u8, 4> = tiny_vec![1, 2, 3];
+        let pairs = keys.zip_with(&values, |k, v| format!("{k}={v}"));
+        assert!(pairs.is_spilled());
+        assert_eq!(pairs, ["a=1", "b=2", "c=3"]);
+
+        let short: tinyvec<u8, 4> = tiny_vec![1];
+        assert!(std::panic::catch_unwind(|| keys.zip_with(&short, |_, _| ())).is_err());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn unzip_pairs() {
+        let pairs: tinyvec<(String, u8), 2> = (0..3).map(|i| (i.to_string(), i)).collect();
+        let (names, numbers) = pairs.unzip();
+        assert_eq!(names, ["0", "1", "2"]);
+        assert_eq!(numbers.capacity(), capacity { inline: 2, heap: 3 });
+
+        let (a, b) = tinyvec::<(u8, ()), 2>::new().unzip();
+        assert!(a.is_empty() && b.is_empty() && !a.is_spilled());
+    }
+
+    #[test]
+    fn sortedness() {
+        let mut ids: tinyvec<u32, 4> = tiny_vec![3, 1, 2];
+        assert!(!ids.is_sorted());
+        ids.sort_unstable();
+        assert!(ids.is_sorted() && tinyvec::<u32, 4>::new().is_sorted());
+        assert!(ids.is_sorted_by(|a, b| a < b));
+        assert!(!ids.is_sorted_by_key(|id| std::cmp::Reverse(*id)));
+        let nan: tinyvec<f32, 2> = tiny_vec![1.0, f32::NAN];
+        assert!(!nan.is_sorted());
+    }
+
+    #[test]
+    fn select_nth() {
+        let mut samples: tinyvec<i32, 8> = tiny_vec![9, -4, 7, 0, 3, 12, -1];
+        let (below, median, above) = samples.select_nth_unstable(3);
+        assert_eq!(*median, 3);
+        assert!(below.iter().all(|x| *x <= 3) && above.iter().all(|x| *x >= 3));
+        let (_, max, _) = samples.select_nth_unstable_by(0, |a, b| b.cmp(a));
+        assert_eq!(*max, 12);
+        let (_, nearest, _) = samples.select_nth_unstable_by_key(0, |x| x.abs());
+        assert_eq!(*nearest, 0);
+        let caught = std::panic::catch_unwind(move || {
+            samples.select_nth_unstable(7);
+        });
+        assert!(caught.is_err());
+    }
+
+    #[test]
+    fn float_sorting() {
+        let mut window: tinyvec<f32, 8> =
+            tiny_vec![0.5, -0.0, f32::NAN, -2.0, 0.0, f32::NEG_INFINITY];
+        window.sort_floats();
+        let bits: Vec<u32> = window.iter().map(|x| x.to_bits()).collect();
+        let expected = [f32::NEG_INFINITY, -2.0, -0.0, 0.0, 0.5, f32::NAN];
+        assert_eq!(bits, expected.map(f32::to_bits));
+        let mut wide: tinyvec<f64, 2> = (0..10).rev().map(f64::from).collect();
+        wide.sort_floats();
+        assert!(wide.is_sorted() && wide.is_spilled());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn array_chunks() {
+        let mut frame: tinyvec<u8, 8> = tiny_vec![0xca, 0xfe, 1, 2, 3, 4, 5];
+        let Some(&[0xca, 0xfe]) = frame.first_chunk::<2>() else {
+            panic!("bad magic");
+        };
+        assert_eq!(frame.last_chunk::<3>(), Some(&[3, 4, 5]));
+        assert_eq!(frame.first_chunk::<8>(), None);
+        let (pairs, rest) = frame.as_chunks::<2>();
+        assert_eq!((pairs.len(), rest), (3, &[5][..]));
+        frame.first_chunk_mut::<2>().unwrap().swap(0, 1);
+        frame.last_chunk_mut::<1>().unwrap()[0] = 9;
+        for pair in frame.as_chunks_mut::<2>().0 {
+            pair.reverse();
+        }
+        assert_eq!(frame, [0xca, 0xfe, 2, 1, 4, 3, 9]);
+    }
+
+    #[test]
+    fn array_windows_overlap() {
+        let signal: tinyvec<i32, 4> = tiny_vec![1, 4, 9, 16, 25];
+        let laplacian: Vec<i32> = signal
+            .array_windows()
+            .map(|&[a, b, c]| a - 2 * b + c)
+            .collect();
+        assert_eq!(laplacian, [2, 2, 2]);
+        assert_eq!(signal.array_windows::<6>().count(), 0);
+        let bigrams: tinyvec<char, 4> = tiny_vec!['a', 'b', 'c'];
+        assert_eq!(bigrams.array_windows::<2>().last(), Some(&['b', 'c']));
+    }
+
+    #[cfg(feature = "math")]
+    #[test]
+    fn elementwise_math() {
+        let mut position: tinyvec<f32, 3> = tiny_vec![1.0, 2.0, 3.0];
+        let velocity: tinyvec<f32, 3> = tiny_vec![0.5, -1.0, 0.0];
+        position.add_assign_elementwise(&velocity);
+        assert_eq!(position, [1.5, 1.0, 3.0]);
+        position.sub_assign_elementwise(&velocity);
+        let weights: tinyvec<f32, 3> = tiny_vec![2.0, 0.0, -1.0];
+        position.mul_assign_elementwise(&weights);
+        position.scale(0.5);
+        assert_eq!(position, [1.0, 0.0, -1.5]);
+        let short: tinyvec<f32, 3> = tiny_vec![1.0];
+        let caught = std::panic::catch_unwind(move || position.add_assign_elementwise(&short));
+        assert!(caught.is_err());
+    }
+
+    #[cfg(feature = "math")]
+    #[test]
+    fn sample_statistics() {
+        let window: tinyvec<f64, 8> = tiny_vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(window.mean(), Some(5.0));
+        assert_eq!(window.variance(), Some(4.0));
+        assert_eq!(window.stddev(), Some(2.0));
+        assert_eq!(window.percentile(50.0), Some(4.5));
+        assert_eq!(window.percentile(0.0), Some(2.0));
+        assert_eq!(window.percentile(100.0), Some(9.0));
+        assert_eq!(window.percentile(101.0), None);
+        assert_eq!(window.as_slice()[0], 2.0);
+        let samples: tinyvec<f32, 4> = tiny_vec![10.0, 0.0, 20.0];
+        assert_eq!(samples.percentile(75.0), Some(15.0));
+        assert_eq!(tinyvec::<f32, 4>::new().mean(), None);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn zero_extension() {
+        let mut packet: tinyvec<u8, 8> = tiny_vec![0xff, 0xff];
+        packet.extend_zeroed(4);
+        assert_eq!(packet, [0xff, 0xff, 0, 0, 0, 0]);
+        packet.resize_zeroed(12);
+        assert!(packet.is_spilled() && packet.iter().skip(2).all(|&b| b == 0));
+        packet.resize_zeroed(1);
+        assert_eq!(packet, [0xff]);
+        let mut fixed: stackvec<f64, 2> = stackvec::new();
+        fixed.extend_zeroed(2);
+        assert_eq!(fixed.as_slice(), [0.0, 0.0]);
+        let caught = std::panic::catch_unwind(move || fixed.extend_zeroed(1));
+        assert!(caught.is_err());
+    }
+
+    #[test]
+    fn index_and_count_of() {
+        let votes: tinyvec<char, 4> = tiny_vec!['b', 'a', 'b', 'c', 'b'];
+        assert_eq!(votes.index_of(&'b'), Some(0));
+        assert_eq!(votes.index_of(&'c'), Some(3));
+        assert_eq!(votes.index_of(&'z'), None);
+        assert_eq!((votes.count_of(&'b'), votes.count_of(&'z')), (3, 0));
+    }
+
+    #[test]
+    fn panic_free_subset() {
+        let mut full: storagevec<u8, inline<u8, 2>> = [1, 2].into_iter().collect();
+        assert!(full.try_push(3).is_err());
+        assert_eq!(
+            full.try_reserve(1),
+            Err(try_reserve_error::capacity_overflow)
+        );
+        assert_eq!(full.remaining_capacity(), 0);
+        assert!(full.get(2).is_none() && full.get_mut(usize::MAX).is_none());
+        assert_eq!(full.checked_index(9).unwrap_err().len, 2);
+        assert_eq!(full.first_chunk::<3>(), None);
+        assert_eq!(full.remove(2), None);
+        full.truncate(usize::MAX);
+        full.clear();
+        assert_eq!(
+            (full.pop(), full.split_first(), full.split_last()),
+            (None, None, None)
+        );
+
+        let mut heap: tinyvec<u64, 2> = tinyvec::new();
+        assert_eq!(
+            heap.try_reserve(usize::MAX),
+            Err(try_reserve_error::capacity_overflow)
+        );
+        assert_eq!(
+            heap.try_reserve(usize::MAX / 8),
+            Err(try_reserve_error::capacity_overflow)
+        );
+        assert!(heap.try_push(1).is_ok() && heap.remove(1).is_none());
+    }
+
+    #[test]
+    fn footprint_guardrails() {
+        const _: () = tinyvec::<u64, 8>::assert_footprint::<128>();
+        const _: () = storagevec::<u8, inline<u8, 16, u8>>::assert_footprint::<17>();
+        const _: () = stackvec::<u32, 4>::assert_footprint::<24>();
+        tinyvec::<u8, 0>::assert_footprint::<64>();
+    }
+
+    #[test]
+    fn map_entries() {
+        let mut totals: tinymap<&str, u32, 2> = tinymap::new();
+        for (account, amount) in [("alice", 5), ("bob", 1), ("alice", 2), ("carol", 4)] {
+            *totals.entry(account).or_default() += amount;
+        }
+        assert_eq!(totals.get("alice"), Some(&7));
+        assert!(totals.is_spilled());
+        assert_eq!(*totals.entry("dave").or_insert_with(|| 10), 10);
+        assert_eq!(totals.entry("bob").key(), &"bob");
+        match totals.entry("carol") {
+            crate::map::entry::occupied(mut entry) => {
+                assert_eq!(entry.insert(40), 4);
+                assert_eq!(entry.remove(), 40);
+            }
+            crate::map::entry::vacant(_) => unreachable!(),
+        }
+        let keys: Vec<_> = totals.keys().copied().collect();
+        assert_eq!(keys, ["alice", "bob", "dave"]);
+    }
+
+    #[test]
+    fn sorted_map_ranges() {
+        let mut latency: sortedmap<u32, &str, 4> = [(50, "p50"), (99, "p99"), (90, "p90")]
+            .into_iter()
+            .collect();
+        assert_eq!(latency.insert(90, "p-ninety"), Some("p90"));
+        latency.extend([(10, "p10"), (75, "p75")]);
+        assert!(latency.is_spilled());
+        let keys: Vec<u32> = latency.keys().copied().collect();
+        assert_eq!(keys, [10, 50, 75, 90, 99]);
+        let tail: Vec<&str> = latency.range(75..=90).map(|(_, v)| *v).collect();
+        assert_eq!(tail, ["p75", "p-ninety"]);
+        assert_eq!(latency.range(51..75).count(), 0);
+        assert_eq!(latency.first_key_value(), Some((&10, &"p10")));
+        assert_eq!(latency.remove(&99), Some("p99"));
+        assert_eq!(latency.last_key_value().map(|(k, _)| *k), Some(90));
+        assert!(!latency.contains_key(&99) && latency.get(&50).is_some());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: tinyset<u8, 4> = [1, 2, 3, 4].into_iter().collect();
+        let b: tinyset<u8, 2> = [3, 4, 5].into_iter().collect();
+        let union: Vec<u8> = a.union(&b).copied().collect();
+        assert_eq!(union, [1, 2, 3, 4, 5]);
+        let both: Vec<u8> = a.intersection(&b).copied().collect();
+        assert_eq!(both, [3, 4]);
+        let only_b: Vec<u8> = b.difference(&a).copied().collect();
+        assert_eq!(only_b, [5]);
+        let either: Vec<u8> = a.symmetric_difference(&b).copied().collect();
+        assert_eq!(either, [1, 2, 5]);
+        let small: tinyset<u8, 2> = [4, 2].into_iter().collect();
+        assert!(small.is_subset(&a) && a.is_superset(&small) && !a.is_subset(&b));
+        assert!(
+            !a.is_disjoint(&b) && small.is_disjoint(&[5].into_iter().collect::<tinyset<u8, 1>>())
+        );
+    }
+
+    #[test]
+    fn deque_slices() {
+        let mut queue: tinydeque<String, 4> = tinydeque::new();
+        for word in ["a", "b", "c", "d"] {
+            queue.push_back(word.to_string());
+        }
+        queue.pop_front();
+        queue.pop_front();
+        queue.push_back("e".to_string());
+        let (front, back) = queue.as_slices();
+        assert!(front == ["c", "d"] && back == ["e"]);
+        queue.as_mut_slices().1[0].push('!');
+        assert_eq!(queue.make_contiguous(), ["c", "d", "e!"]);
+        assert!(queue.as_slices().1.is_empty() && !queue.is_spilled());
+        queue.push_front("z".to_string());
+        queue.push_front("y".to_string());
+        queue.make_contiguous().sort();
+        let words: Vec<&str> = queue.iter().map(String::as_str).collect();
+        assert_eq!(words, ["c", "d", "e!", "y", "z"]);
+    }
+
+    #[test]
+    fn lossy_push() {
+        let mut trace: lossyvec<String, 2> = lossyvec::new();
+        assert!(trace.push("open".to_string()));
+        assert!(trace.push("read".to_string()));
+        assert!(!trace.push("write".to_string()));
+        assert_eq!((trace.len(), trace.dropped()), (2, 1));
+        trace.pop();
+        trace.extend(["seek", "close"].map(String::from));
+        assert_eq!(trace.as_slice(), ["open", "seek"]);
+        assert_eq!((trace.take_dropped(), trace.dropped()), (2, 0));
+        let all: lossyvec<u8, 3> = (0..=255).collect();
+        assert_eq!(all.dropped(), 253);
+        assert_eq!(all.into_inner().as_slice(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn from_strings() {
+        let short: tinyvec<u8, 8> = "GET".into();
+        assert_eq!((short.as_slice(), short.is_spilled()), (&b"GET"[..], false));
+        let owned = String::from("a string too long for eight bytes");
+        let ptr = owned.as_ptr();
+        let long: tinyvec<u8, 8> = owned.into();
+        assert!(long.is_spilled() && long.as_slice().as_ptr() == ptr);
+        let name = String::from("a heap-allocated name");
+        let ptr = name.as_ptr();
+        let string: tinystring<4> = name.into();
+        assert_eq!(string.as_str().as_ptr(), ptr);
+        let small: tinystring<8> = String::from("ok").into();
+        assert!(!small.is_spilled() && small == "ok");
+        let moved: tinyvec<String, 2> = vec!["x".to_string()].into();
+        assert_eq!((moved.len(), moved.is_spilled()), (1, false));
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn select_by_index() {
+        let row: tinyvec<&str, 4> = tiny_vec!["id", "name", "email", "age"];
+        let projected: tinyvec<&str, 2> = row.select([1, 3]);
+        assert_eq!(projected, ["name", "age"]);
+        let repeated = row.select::<4, _>([0, 0, 0].iter().copied());
+        assert_eq!(repeated, ["id"; 3]);
+        let error = row.try_select::<2, _>([2, 7]).unwrap_err();
+        assert_eq!(error, index_error { index: 7, len: 4 });
+        assert!(std::panic::catch_unwind(|| row.select::<2, _>([4])).is_err());
+    }
+
+    #[cfg(feature = "speedy")]
+    #[test]
+    fn speedy_roundtrip() {
+        use speedy::{Readable, Writable};
+        let point: tinyvec<i32, 4> = tiny_vec![3, -1, 7];
+        let bytes = point.write_to_vec().unwrap();
+        assert_eq!(bytes, vec![3, -1, 7].write_to_vec().unwrap());
+        assert_eq!(tinyvec::<i32, 4>::read_from_buffer(&bytes).unwrap(), point);
+        let label: tinystring<8> = "metric".into();
+        let bytes = label.write_to_vec().unwrap();
+        assert_eq!(String::read_from_buffer(&bytes).unwrap(), "metric");
+        assert_eq!(tinystring::<2>::read_from_buffer(&bytes).unwrap(), label);
+        let invalid = vec![0xffu8].write_to_vec().unwrap();
+        assert!(tinystring::<2>::read_from_buffer(&invalid).is_err());
+        let truncated = [9, 0, 0, 0, 1];
+        assert!(tinyvec::<u8, 4>::read_from_buffer(&truncated).is_err());
+    }
+
+    #[test]
+    fn drain_into_vec() {
+        let mut batch: tinyvec<String, 2> = tinyvec::new();
+        let mut all = vec!["start".to_string()];
+        for round in 0..3 {
+            for i in 0..=round {
+                batch.push(format!("{round}.{i}"));
+            }
+            batch.drain_into(&mut all);
+            assert!(batch.is_empty());
+        }
+        assert_eq!(all, ["start", "0.0", "1.0", "1.1", "2.0", "2.1", "2.2"]);
+        assert!(batch.is_spilled() && batch.total_capacity() >= 3);
+    }
+
+    #[test]
+    fn deque_contiguous_views() {
+        let mut window: tinydeque<u32, 4> = tinydeque::new();
+        for sample in [5, 1, 4, 2] {
+            window.push_back(sample);
+        }
+        window.pop_front();
+        window.push_back(3);
+        assert!(!window.as_slices().1.is_empty());
+        assert_eq!(
+            window.with_contiguous(|run| run.iter().max().copied()),
+            Some(4)
+        );
+        assert!(!window.as_slices().1.is_empty());
+        window.with_contiguous_mut(|run| run.sort_unstable());
+        assert_eq!(window.with_contiguous(<[u32]>::to_vec), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn mpmc_threads() {
+        let queue: mpmc<String, 4> = mpmc::new();
+        for word in ["a", "b", "c", "d"] {
+            queue.try_send(word.to_string()).unwrap();
+        }
+        assert_eq!(queue.try_send("e".to_string()).unwrap_err().element(), "e");
+        assert_eq!(queue.try_recv().as_deref(), Some("a"));
+        assert_eq!(queue.len(), 3);
+
+        let received = std::sync::Mutex::new(Vec::new());
+        std::thread::scope(|s| {
+            for producer in 0..2 {
+                let queue = &queue;
+                s.spawn(move || {
+                    for i in 0..50 {
+                        let mut pending = format!("{producer}:{i}");
+                        while let Err(full) = queue.try_send(pending) {
+                            pending = full.element();
+                            std::thread::yield_now();
+                        }
+                    }
+                });
+            }
+            for _ in 0..2 {
+                let (queue, received) = (&queue, &received);
+                s.spawn(move || {
+                    let mut mine = Vec::new();
+                    while mine.len() < 51 {
+                        match queue.try_recv() {
+                            Some(word) => mine.push(word),
+                            None => std::thread::yield_now(),
+                        }
+                    }
+                    received.lock().unwrap().extend(mine);
+                });
+            }
+        });
+        let mut received = received.into_inner().unwrap();
+        received.extend(std::iter::from_fn(|| queue.try_recv()));
+        received.sort();
+        received.dedup();
+        assert_eq!(received.len(), 103);
+        assert!(queue.is_empty());
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn chunked_batches() {
+        let rows: tinyvec<String, 8> = (0..7).map(|i| format!("row{i}")).collect();
+        let batches: Vec<tinyvec<String, 3>> = rows.into_chunks().collect();
+        let sizes: Vec<usize> = batches.iter().map(|batch| batch.len()).collect();
+        assert_eq!(sizes, [3, 3, 1]);
+        assert!(batches.iter().all(|batch| !batch.is_spilled()));
+        assert_eq!(batches[2], ["row6"]);
+        let mut empty = into_chunks::<_, 2>::new(std::iter::empty::<u8>());
+        assert_eq!((empty.size_hint(), empty.next()), ((0, Some(0)), None));
+        assert_eq!(into_chunks::<_, 2>::new(0..5).size_hint(), (3, Some(3)));
+    }
+
+    #[cfg(feature = "abi_stable")]
+    #[test]
+    fn abi_stable_round_trip() {
+        use abi_stable::std_types::{RString, RVec};
+
+        let mut spilled: tinyvec<u32, 2> = (0..10).collect();
+        let ptr = spilled.as_slice().as_ptr();
+        assert_eq!(spilled.as_rslice_mut().len(), 10);
+        let rvec = RVec::from(spilled);
+        assert_eq!(rvec.as_ptr(), ptr);
+        let back: tinyvec<u32, 2> = tinyvec::from(rvec);
+        assert_eq!(back.as_slice(), (0..10).collect::<Vec<_>>());
+
+        let inline: tinyvec<u32, 4> = tiny_vec![1, 2];
+        assert_eq!(RVec::from(inline).as_slice(), [1, 2]);
+
+        let s: tinystring<8> = tinystring::from("héllo");
+        let r = RString::from(s);
+        assert_eq!(r.as_str(), "héllo");
+        assert_eq!(tinystring::<8>::from(r).as_str(), "héllo");
+    }
+
+    #[cfg(feature = "pyo3")]
+    #[test]
+    fn python_conversions() {
+        use pyo3::prelude::*;
+        use pyo3::types::{PyBytes, PyList};
+
+        pyo3::Python::initialize();
+        Python::attach(|py| {
+            let list = PyList::new(py, [1, 2, 3]).unwrap();
+            let vec: tinyvec<u32, 4> = list.extract().unwrap();
+            assert_eq!(vec.as_slice(), [1, 2, 3]);
+            assert!(vec.into_pyobject(py).unwrap().is_instance_of::<PyList>());
+
+            let bytes: tinyvec<u8, 4> = PyBytes::new(py, b"abc").extract().unwrap();
+            assert_eq!(bytes.as_slice(), b"abc");
+            let out = (&bytes).into_pyobject(py).unwrap();
+            assert_eq!(out.cast::<PyBytes>().unwrap().as_bytes(), b"abc");
+
+            let text = "abc".into_pyobject(py).unwrap();
+            assert!(text.extract::<tinyvec<u8, 4>>().is_err());
+        });
+    }
+
+    #[cfg(all(feature = "serde", feature = "encoding"))]
+    #[test]
+    fn encoded_serde() {
+        let digest: tinyvec<u8, 4> = tiny_vec![0xde, 0xad, 0x01];
+        let mut json = Vec::new();
+        crate::encoded::hex::serialize(&digest, &mut serde_json::Serializer::new(&mut json))
+            .unwrap();
+        assert_eq!(json, br#""dead01""#);
+        let mut de = serde_json::Deserializer::from_str(r#""DEAD01""#);
+        assert_eq!(
+            crate::encoded::hex::deserialize::<_, 4>(&mut de).unwrap(),
+            digest
+        );
+
+        let mut json = Vec::new();
+        crate::encoded::base64::serialize(&digest, &mut serde_json::Serializer::new(&mut json))
+            .unwrap();
+        assert_eq!(json, br#""3q0B""#);
+        let mut de = serde_json::Deserializer::from_str(r#""3q0B""#);
+        assert_eq!(
+            crate::encoded::base64::deserialize::<_, 4>(&mut de).unwrap(),
+            digest
+        );
+
+        let mut de = serde_json::Deserializer::from_str(r#""d*""#);
+        let error = crate::encoded::hex::deserialize::<_, 4>(&mut de).unwrap_err();
+        assert!(error.to_string().contains("invalid byte at offset 1"));
+    }
+
+    #[test]
+    fn tinyhashmap_matches_hashmap() {
+        use std::collections::HashMap;
+        use std::hash::{BuildHasherDefault, DefaultHasher};
+
+        // 12 keys always fit in 16 buckets, 32 don't.
+        for keys in [12, 32] {
+            let mut map: tinyhashmap<u32, u32, 16, BuildHasherDefault<DefaultHasher>> =
+                tinyhashmap::default();
+            let mut model = HashMap::new();
+            let mut seed = 7u32;
+            for step in 0..400 {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                let key = (seed >> 16) % keys;
+                if step < 300 && seed >> 8 & 1 == 0 {
+                    assert_eq!(map.remove(&key), model.remove(&key));
+                } else {
+                    assert_eq!(map.insert(key, step), model.insert(key, step));
+                }
+                assert_eq!(map.len(), model.len());
+                for key in 0..keys {
+                    assert_eq!(map.get(&key), model.get(&key));
+                }
+            }
+            assert_eq!(map.is_spilled(), keys == 32);
+            assert_eq!(map.iter().count(), model.len());
+        }
+
+        let mut small: tinyhashmap<String, usize, 4> = tinyhashmap::new();
+        for (i, word) in ["a", "b", "c"].iter().enumerate() {
+            small.insert(word.to_string(), i);
+        }
+        assert!(!small.is_spilled());
+        assert_eq!(small.get("b"), Some(&1));
+        small.insert("d".to_string(), 3);
+        assert!(small.is_spilled());
+        assert_eq!(small.get("d"), Some(&3));
+    }
+
+    #[test]
+    fn sharedvec_copies_on_write() {
+        let original: sharedvec<String, 2> = (0..5).map(|i| i.to_string()).collect();
+        assert!(original.is_spilled() && !original.is_shared());
+        let mut copy = original.clone();
+        assert_eq!(copy.as_ptr(), original.as_ptr());
+        copy.as_mut_slice()[0].push('!');
+        assert_ne!(copy.as_ptr(), original.as_ptr());
+        assert_eq!((&*original[0], &*copy[0]), ("0", "0!"));
+
+        let mut prefix = original.clone();
+        prefix.truncate(2);
+        assert_eq!(prefix.len(), 2);
+        assert_eq!(original.len(), 5);
+        prefix.insert(1, "x".to_string());
+        assert!(
+            prefix
+                == ["0", "x", "1"]
+                    .map(String::from)
+                    .into_iter()
+                    .collect::<sharedvec<_, 4>>()
+        );
+
+        let ptr = original.as_ptr();
+        let back = original.into_tinyvec();
+        assert_eq!(back.as_slice().as_ptr(), ptr);
+
+        let mut small: sharedvec<u8, 4> = sharedvec::new();
+        small.extend([1, 2]);
+        let mut twin = small.clone();
+        twin.push(3);
+        assert!(!small.is_spilled() && small.len() == 2);
+    }
+
+    #[test]
+    fn get_many_mut_checks_indices() {
+        let mut vec: tinyvec<String, 2> = ["a", "b", "c"].map(String::from).into_iter().collect();
+        let [c, a] = vec.get_many_mut([2, 0]).unwrap();
+        std::mem::swap(c, a);
+        assert!(vec == ["c", "b", "a"]);
+        assert_eq!(
+            vec.get_many_mut([0, 3]).unwrap_err(),
+            disjoint_error::out_of_bounds(index_error { index: 3, len: 3 })
+        );
+        assert_eq!(vec.get_many_mut::<0>([]).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn shrinking_policy_releases_heap() {
+        let mut vec: tinyvec<u64, 4, Global, growth::shrinking> = (0..1024).collect();
+        assert_eq!(vec.total_capacity(), 1024);
+        vec.truncate(300);
+        assert_eq!(vec.total_capacity(), 1024);
+        vec.drain(10..);
+        assert!(vec.total_capacity() <= 64);
+        assert!(vec.iter().copied().eq(0..10));
+        vec.retain(|x| *x < 2);
+        assert!(!vec.is_spilled());
+        assert_eq!(vec.capacity().heap, 0);
+        assert_eq!(vec.as_slice(), [0, 1]);
+
+        let mut vec: tinyvec<u32, 8, Global, growth::shrinking> = (0..100).collect();
+        vec.truncate(9);
+        assert!(vec.is_spilled() && vec.capacity().heap >= 9);
+        vec.clear();
+        assert!(!vec.is_spilled());
+
+        let mut plain: tinyvec<u64, 4> = (0..1024).collect();
+        plain.clear();
+        assert_eq!(plain.total_capacity(), 1024);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[test]
+    fn format_macros() {
+        let spilled: tinystring<4> = tiny_format!("{:>8}|{}", "ab", 'c');
+        assert_eq!(spilled, "      ab|c");
+        assert!(spilled.is_spilled());
+        let exact: Result<arraystring<3>, _> = array_format!("{}", 123);
+        assert_eq!(exact.unwrap(), "123");
+        assert!(
+            array_format!("{}", 1234)
+                .map(|s: arraystring<3>| s)
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn nom_parses_tinybytes() {
+        use nom::bytes::complete::{tag, take};
+        use nom::number::complete::be_u16;
+        use nom::{IResult, Parser};
+
+        fn frame(input: tinybytes<8>) -> IResult<tinybytes<8>, tinybytes<8>> {
+            let (input, _) = tag("FRM").parse(input)?;
+            let (input, len) = be_u16(input)?;
+            take(len).parse(input)
+        }
+
+        let mut wire = b"FRM\x00\x0ctwelve bytes".to_vec();
+        wire.extend_from_slice(b"FRM\x00\x02hi");
+        let input = tinybytes::from(wire);
+        let (rest, long) = frame(input).unwrap();
+        assert_eq!(&*long, b"twelve bytes");
+        assert!(!long.is_inline());
+        let (rest, short) = frame(rest).unwrap();
+        assert_eq!(
+            (&*short, short.is_inline(), rest.len()),
+            (&b"hi"[..], true, 0)
+        );
+        assert!(frame(tinybytes::from(&b"FRM\x00"[..])).is_err());
+    }
+
+    #[cfg(feature = "embedded-io")]
+    #[test]
+    fn embedded_io_read_write() {
+        use embedded_io::{BufRead, Read, Write};
+
+        let mut sink: stackvec<u8, 8> = stackvec::new();
+        assert_eq!(Write::write(&mut sink, b"0123456789"), Ok(8));
+        assert!(Write::write(&mut sink, b"x").is_err());
+        assert!(sink.write_all(b"").is_ok());
+
+        let mut log: tinyvec<u8, 4> = tinyvec::new();
+        let unit = "ab";
+        write!(log, "{}-{unit}", 12).unwrap();
+        assert_eq!(log.as_slice(), b"12-ab");
+
+        let mut buf = [0; 3];
+        assert_eq!(Read::read(&mut log, &mut buf), Ok(3));
+        assert_eq!((&buf, log.fill_buf().unwrap()), (b"12-", &b"ab"[..]));
+        log.consume(5);
+        assert_eq!(Read::read(&mut log, &mut buf), Ok(0));
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn ufmt_output() {
+        use ufmt::uwrite;
+
+        let readings: tinyvec<u16, 4> = tiny_vec![12, 7, 300];
+        let name: tinystring<8> = tinystring::from(r#"a"b\c"#);
+        let mut out: tinystring<64> = tinystring::new();
+        uwrite!(out, "{} {:?} {} {:?}", readings, readings, name, name).unwrap();
+        assert_eq!(out, r#"[ 12, 7, 300 ] [12, 7, 300] a"b\c "a\"b\\c""#);
+
+        out.clear();
+        uwrite!(out, "{}", tinyvec::<u8, 2>::new()).unwrap();
+        assert_eq!(out, "[ ]");
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_buf_traits() {
+        use bytes::{Buf, BufMut};
+
+        let mut frame: tinyvec<u8, 4> = tinyvec::new();
+        frame.put_u16(0xcafe);
+        frame.put_slice(b"hello");
+        frame.put_u32_le(7);
+        assert!(frame.is_spilled());
+        assert_eq!(Buf::get_u16(&mut frame), 0xcafe);
+        let mut word = [0; 5];
+        frame.copy_to_slice(&mut word);
+        assert_eq!((&word, Buf::get_u32_le(&mut frame)), (b"hello", 7));
+        assert!(!frame.has_remaining());
+
+        let mut fixed: stackvec<u8, 3> = stackvec::new();
+        fixed.put_u16(1);
+        assert_eq!(fixed.remaining_mut(), 1);
+        let overflow = std::panic::catch_unwind(move || fixed.put_u16(2));
+        assert!(overflow.is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn tokio_cursor() {
+        use crate::cursor::tinycursor;
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let mut cursor: tinycursor<4> = tinycursor::new(tiny_vec![b'a', b'b', b'c']);
+            cursor.set_position(1);
+            cursor.write_all(b"XYZ").await.unwrap();
+            cursor.set_position(8);
+            cursor.write_all(b"!").await.unwrap();
+            assert_eq!(cursor.get_ref().as_slice(), b"aXYZ\0\0\0\0!");
+
+            cursor.set_position(0);
+            let mut line = String::new();
+            cursor.get_mut().extend_from_slice(b"\nrest");
+            cursor.read_line(&mut line).await.unwrap();
+            assert_eq!(line.as_bytes(), b"aXYZ\0\0\0\0!\n");
+            let mut rest = Vec::new();
+            cursor.read_to_end(&mut rest).await.unwrap();
+            assert_eq!(rest, b"rest");
+            assert_eq!(cursor.read(&mut [0; 4]).await.unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn with_vec() {
+        let mut vec: tinyvec<u32, 2> = (0..6).collect();
+        let removed: Vec<u32> = vec.with_vec(|vec| vec.splice(1..5, [9]).collect());
+        assert_eq!(removed, [1, 2, 3, 4]);
+        assert_eq!(vec.as_slice(), [0, 9, 5]);
+
+        let mut vec: tinyvec<u32, 2> = (0..3).collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vec.with_vec(|vec| {
+                vec.pop();
+                panic!("in with_vec");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(vec.as_slice(), [0, 1]);
+
+        let mut vec: tinyvec<u32, 4> = (0..4).collect();
+        vec.push(4);
+        vec.push(5);
+        let heap = vec.capacity().heap;
+        vec.with_vec(|vec| vec.truncate(2));
+        assert!(vec.is_spilled());
+        assert_eq!(vec.capacity().heap, heap);
+        #[cfg(feature = "spill_count")]
+        assert_eq!(vec.spill_count(), 1);
+        #[cfg(feature = "high_water")]
+        assert_eq!(vec.max_len_seen(), 6);
+
+        let mut vec: tinyvec<u32, 4> = (0..2).collect();
+        vec.with_vec(|vec| vec.extend(2..6));
+        assert!(vec.is_spilled());
+        #[cfg(feature = "spill_count")]
+        assert_eq!(vec.spill_count(), 1);
+        vec.with_vec(|vec| vec.truncate(1));
+        assert_eq!(vec.as_slice(), [0]);
+    }
+
+    #[test]
+    fn zero_sized_deque() {
+        let mut deque: tinydeque<(), 4> = tinydeque::new();
+        for _ in 0..10 {
+            deque.push_front(());
+            deque.push_back(());
+        }
+        assert_eq!(deque.len(), 20);
+        assert_eq!(deque.make_contiguous().len(), 20);
+        assert_eq!(deque.pop_back(), Some(()));
+        assert_eq!(deque.pop_front(), Some(()));
+        assert_eq!(deque.get(17), Some(&()));
+        assert_eq!(deque.get(18), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "drain: range end overflows usize")]
+    fn drain_inclusive_max() {
+        let mut vec: tinyvec<u32, 4> = (0..3).collect();
+        vec.drain(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "drain: range start overflows usize")]
+    fn drain_excluded_max() {
+        use std::ops::Bound;
+        let mut vec: tinyvec<u32, 4> = (0..3).collect();
+        vec.drain((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic(expected = "slice: range end overflows usize")]
+    fn tinybytes_slice_inclusive_max() {
+        let bytes: tinybytes<4> = tinybytes::from(&b"abc"[..]);
+        bytes.slice(..=usize::MAX);
+    }
 }
@@ -0,0 +1,111 @@
+//! Best-effort collection that drops what doesn't fit, see
+//! [`lossyvec`].
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::{inline, stackvec, storagevec};
+
+/// [`stackvec`] whose `push()` never fails and never allocates: once
+/// the N slots are taken, further elements are dropped and counted.
+/// For sampling and diagnostics, where losing the excess is fine but
+/// allocating on that path isn't.
+///
+/// Only `push()` and `extend()` drop; everything else is
+/// [`storagevec`]'s API through `Deref`.
+///
+/// ```rust
+/// use vecstor::lossyvec;
+/// let mut samples: lossyvec<u32, 4> = lossyvec::new();
+/// samples.extend(0..10);
+/// assert_eq!(samples.as_slice(), [0, 1, 2, 3]);
+/// assert_eq!(samples.dropped(), 6);
+/// ```
+pub struct lossyvec<T, const N: usize> {
+    vec: stackvec<T, N>,
+    dropped: usize,
+}
+
+impl<T, const N: usize> lossyvec<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            vec: stackvec::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Keep `element` if there is a free slot, otherwise drop it
+    /// and count it. Returns whether it was kept.
+    pub fn push(&mut self, element: T) -> bool {
+        match self.vec.push(element) {
+            Ok(()) => true,
+            Err(_) => {
+                self.dropped = self.dropped.saturating_add(1);
+                false
+            }
+        }
+    }
+
+    /// How many elements were dropped since the last reset.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Return the drop count and start over from 0, e.g. once per
+    /// reporting interval.
+    pub fn take_dropped(&mut self) -> usize {
+        std::mem::take(&mut self.dropped)
+    }
+
+    /// The kept elements.
+    pub fn into_inner(self) -> stackvec<T, N> {
+        self.vec
+    }
+}
+
+impl<T, const N: usize> Deref for lossyvec<T, N> {
+    type Target = storagevec<T, inline<T, N>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T, const N: usize> DerefMut for lossyvec<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vec
+    }
+}
+
+impl<T, const N: usize> Default for lossyvec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pulls the whole iterator, counting what doesn't fit.
+impl<T, const N: usize> Extend<T> for lossyvec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for lossyvec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for lossyvec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("lossyvec")
+            .field("elements", &self.as_slice())
+            .field("dropped", &self.dropped)
+            .finish()
+    }
+}
@@ -0,0 +1,135 @@
+//! Bounded LRU cache, see [`tinylru`].
+
+use std::borrow::Borrow;
+use std::fmt;
+
+use crate::tinyvec;
+
+/// Cache of at most N entries that evicts the least recently used one
+/// to make room. Entries sit in a [`tinyvec`] ordered from least to
+/// most recently used, so it never leaves the stack, and lookups are
+/// a linear scan as in [`tinymap`](crate::tinymap).
+///
+/// ```rust
+/// use vecstor::tinylru;
+/// let mut routes: tinylru<&str, u16, 2> = tinylru::new();
+/// routes.insert("/", 1);
+/// routes.insert("/login", 2);
+/// routes.get("/");
+/// assert_eq!(routes.insert("/about", 3), Some(("/login", 2)));
+/// assert_eq!(routes.peek("/"), Some(&1));
+/// ```
+pub struct tinylru<K, V, const N: usize> {
+    /// Least recently used first.
+    entries: tinyvec<(K, V), N>,
+}
+
+impl<K: Eq, V, const N: usize> tinylru<K, V, N> {
+    pub fn new() -> Self {
+        Self {
+            entries: tinyvec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries kept before evicting, N.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn position<Q: ?Sized + Eq>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.entries.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Move entry `at` to the most recently used end.
+    fn touch(&mut self, at: usize) -> &mut (K, V) {
+        let entries = self.entries.as_mut_slice();
+        entries[at..].rotate_left(1);
+        entries.last_mut().unwrap()
+    }
+
+    /// Insert or replace `key` as the most recently used entry.
+    /// Returns the old entry for `key` if there was one, otherwise the
+    /// entry evicted to make room.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(at) = self.position(&key) {
+            let entry = self.touch(at);
+            return Some(std::mem::replace(entry, (key, value)));
+        }
+        if N == 0 {
+            return Some((key, value));
+        }
+        let evicted = if self.len() == N {
+            self.entries.remove(0)
+        } else {
+            None
+        };
+        self.entries.push((key, value));
+        evicted
+    }
+
+    /// Look up `key` and mark it most recently used.
+    pub fn get<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.position(key)?;
+        Some(&mut self.touch(at).1)
+    }
+
+    /// Look up `key` without changing its recency.
+    pub fn peek<Q: ?Sized + Eq>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.position(key)?;
+        Some(&self.entries.as_slice()[at].1)
+    }
+
+    pub fn contains_key<Q: ?Sized + Eq>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.position(key).is_some()
+    }
+
+    pub fn remove<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.position(key)?;
+        self.entries.remove(at).map(|(_, value)| value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Entries from least to most recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K: Eq, V, const N: usize> Default for tinylru<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<K: Eq + fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for tinylru<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
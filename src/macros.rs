@@ -0,0 +1,73 @@
+/// Build a [`tinyvec`](crate::tinyvec) from a list of elements, like
+/// `vec![]`. N comes from the type the result is bound to.
+///
+/// `tiny_vec![value; count]` makes `count` clones of `value`, and
+/// goes straight to the heap if they don't fit inline.
+///
+/// ```rust
+/// use vecstor::{tiny_vec, tinyvec};
+/// let small: tinyvec<u8, 4> = tiny_vec![1, 2, 3];
+/// assert_eq!(small.as_slice(), [1, 2, 3]);
+///
+/// let zeroes: tinyvec<u8, 4> = tiny_vec![0; 64];
+/// assert_eq!(zeroes.len(), 64);
+/// ```
+#[macro_export]
+macro_rules! tiny_vec {
+    () => {
+        $crate::tinyvec::new()
+    };
+    ($element:expr; $count:expr) => {{
+        let count = $count;
+        let mut vector = $crate::tinyvec::with_heap_capacity(count);
+        vector.resize(count, $element);
+        vector
+    }};
+    ($($element:expr),+ $(,)?) => {{
+        let mut vector = $crate::tinyvec::new();
+        ::core::iter::Extend::extend(&mut vector, [$($element),+]);
+        vector
+    }};
+}
+
+/// `format!()` into a [`tinystring`](crate::tinystring): the result is
+/// written straight into the inline bytes, no `String` in between,
+/// and only spills to the heap past N bytes. N comes from the type the
+/// result is bound to.
+///
+/// ```rust
+/// use vecstor::{tiny_format, tinystring};
+/// let (sensor, celsius) = (3, 21.5);
+/// let line: tinystring<32> = tiny_format!("sensor {sensor}: {celsius:.1} C");
+/// assert_eq!(line.as_str(), "sensor 3: 21.5 C");
+/// assert!(!line.is_spilled());
+/// ```
+#[macro_export]
+macro_rules! tiny_format {
+    ($($arg:tt)*) => {{
+        let mut out = $crate::tinystring::new();
+        ::core::fmt::Write::write_fmt(&mut out, ::core::format_args!($($arg)*))
+            .expect("a formatting trait implementation returned an error");
+        out
+    }};
+}
+
+/// `tiny_format!()` into an [`arraystring`](crate::arraystring), that
+/// never allocates: `Err(fmt::Error)` if the text doesn't fit in N
+/// bytes (or a formatting impl fails).
+///
+/// ```rust
+/// use vecstor::{array_format, arraystring};
+/// let code = 404;
+/// let short: Result<arraystring<16>, _> = array_format!("error {code}");
+/// assert_eq!(short.unwrap().as_str(), "error 404");
+/// let long: Result<arraystring<4>, _> = array_format!("error {code}");
+/// assert!(long.is_err());
+/// ```
+#[macro_export]
+macro_rules! array_format {
+    ($($arg:tt)*) => {{
+        let mut out = $crate::arraystring::new();
+        ::core::fmt::Write::write_fmt(&mut out, ::core::format_args!($($arg)*)).map(|()| out)
+    }};
+}
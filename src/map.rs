@@ -0,0 +1,709 @@
+//! Small association maps, see [`tinymap`], [`sortedmap`] and
+//! [`tinyhashmap`].
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash, RandomState};
+use std::ops::{Bound, RangeBounds};
+
+use crate::tinyvec;
+
+/// Map that keeps its entries in a [`tinyvec`], in insertion order,
+/// and finds keys by linear scan. For a handful of entries that is
+/// faster than hashing, and up to N entries nothing is allocated.
+///
+/// ```rust
+/// use vecstor::tinymap;
+/// let mut headers: tinymap<&str, &str, 4> = tinymap::new();
+/// headers.insert("host", "example.com");
+/// headers.insert("accept", "*/*");
+/// assert_eq!(headers.get("host"), Some(&"example.com"));
+/// assert_eq!(headers.insert("host", "other"), Some("example.com"));
+/// ```
+pub struct tinymap<K, V, const N: usize> {
+    entries: tinyvec<(K, V), N>,
+}
+
+impl<K: Eq, V, const N: usize> tinymap<K, V, N> {
+    pub fn new() -> Self {
+        Self {
+            entries: tinyvec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the entries moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.entries.is_spilled()
+    }
+
+    fn position<Q: ?Sized + Eq>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.entries.iter().position(|(k, _)| k.borrow() == key)
+    }
+
+    /// Set `key` to `value`, returning the old value if there was one.
+    /// A new key goes at the end.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.position(&key) {
+            Some(at) => {
+                let slot = &mut self.entries.as_mut_slice()[at].1;
+                Some(std::mem::replace(slot, value))
+            }
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get<Q: ?Sized + Eq>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.position(key)?;
+        Some(&self.entries.as_slice()[at].1)
+    }
+
+    pub fn get_mut<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.position(key)?;
+        Some(&mut self.entries.as_mut_slice()[at].1)
+    }
+
+    /// The entry for `key`, to read, insert or update it with a
+    /// single lookup.
+    ///
+    /// ```rust
+    /// use vecstor::tinymap;
+    /// let mut counts: tinymap<char, u32, 4> = tinymap::new();
+    /// for c in "abracadabra".chars() {
+    ///     counts.entry(c).and_modify(|n| *n += 1).or_insert(1);
+    /// }
+    /// assert_eq!(counts.get(&'a'), Some(&5));
+    /// ```
+    pub fn entry(&mut self, key: K) -> entry<'_, K, V, N> {
+        match self.position(&key) {
+            Some(at) => entry::occupied(occupied_entry {
+                entries: &mut self.entries,
+                at,
+            }),
+            None => entry::vacant(vacant_entry {
+                entries: &mut self.entries,
+                key,
+            }),
+        }
+    }
+
+    pub fn contains_key<Q: ?Sized + Eq>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Remove `key`, keeping the other entries in order.
+    pub fn remove<Q: ?Sized + Eq>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.position(key)?;
+        self.entries.remove(at).map(|(_, value)| value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+/// A key's place in a [`tinymap`], from `tinymap::entry()`.
+pub enum entry<'a, K, V, const N: usize> {
+    occupied(occupied_entry<'a, K, V, N>),
+    vacant(vacant_entry<'a, K, V, N>),
+}
+
+/// An entry whose key is in the map.
+pub struct occupied_entry<'a, K, V, const N: usize> {
+    entries: &'a mut tinyvec<(K, V), N>,
+    at: usize,
+}
+
+/// An entry whose key isn't in the map yet.
+pub struct vacant_entry<'a, K, V, const N: usize> {
+    entries: &'a mut tinyvec<(K, V), N>,
+    key: K,
+}
+
+impl<'a, K, V, const N: usize> entry<'a, K, V, N> {
+    pub fn key(&self) -> &K {
+        match self {
+            entry::occupied(entry) => entry.key(),
+            entry::vacant(entry) => &entry.key,
+        }
+    }
+
+    /// The value, inserting `default` if the key is vacant.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// The value, inserting `default()` if the key is vacant.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            entry::occupied(entry) => entry.into_mut(),
+            entry::vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Run `f` on the value if the key is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let entry::occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V, const N: usize> occupied_entry<'a, K, V, N> {
+    fn pair(&self) -> &(K, V) {
+        &self.entries.as_slice()[self.at]
+    }
+
+    pub fn key(&self) -> &K {
+        &self.pair().0
+    }
+
+    pub fn get(&self) -> &V {
+        &self.pair().1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.entries.as_mut_slice()[self.at].1
+    }
+
+    /// The value, borrowed for as long as the map was.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.entries.as_mut_slice()[self.at].1
+    }
+
+    /// Replace the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Take the entry out of the map, keeping the others in order.
+    pub fn remove(self) -> V {
+        match self.entries.remove(self.at) {
+            Some((_, value)) => value,
+            None => unreachable!("occupied entry out of bounds"),
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize> vacant_entry<'a, K, V, N> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Add the key with `value`, at the end like `tinymap::insert()`.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.entries.push((self.key, value));
+        let at = self.entries.len() - 1;
+        &mut self.entries.as_mut_slice()[at].1
+    }
+}
+
+impl<K: Eq, V, const N: usize> Default for tinymap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Later pairs win for duplicate keys, like `HashMap`.
+impl<K: Eq, V, const N: usize> FromIterator<(K, V)> for tinymap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Eq, V, const N: usize> Extend<(K, V)> for tinymap<K, V, N> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<K: Eq + fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for tinymap<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// [`tinymap`] flavour that keeps the entries sorted by key and
+/// finds them by binary search, so lookups stay cheap up to a few
+/// dozen entries, and key ranges come out in order.
+///
+/// ```rust
+/// use vecstor::sortedmap;
+/// let mut ports: sortedmap<u16, &str, 4> = sortedmap::new();
+/// ports.insert(443, "https");
+/// ports.insert(22, "ssh");
+/// ports.insert(80, "http");
+/// assert_eq!(ports.get(&80), Some(&"http"));
+/// let web: Vec<_> = ports.range(80..).map(|(_, name)| *name).collect();
+/// assert_eq!(web, ["http", "https"]);
+/// ```
+pub struct sortedmap<K, V, const N: usize> {
+    entries: tinyvec<(K, V), N>,
+}
+
+impl<K: Ord, V, const N: usize> sortedmap<K, V, N> {
+    pub fn new() -> Self {
+        Self {
+            entries: tinyvec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the entries moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.entries.is_spilled()
+    }
+
+    fn search<Q: ?Sized + Ord>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.entries
+            .as_slice()
+            .binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    /// Set `key` to `value`, returning the old value if there was one.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.search(&key) {
+            Ok(at) => {
+                let slot = &mut self.entries.as_mut_slice()[at].1;
+                Some(std::mem::replace(slot, value))
+            }
+            Err(at) => {
+                self.entries.insert(at, (key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.search(key).ok()?;
+        Some(&self.entries.as_slice()[at].1)
+    }
+
+    pub fn get_mut<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.search(key).ok()?;
+        Some(&mut self.entries.as_mut_slice()[at].1)
+    }
+
+    pub fn contains_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.search(key).is_ok()
+    }
+
+    pub fn remove<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let at = self.search(key).ok()?;
+        self.entries.remove(at).map(|(_, value)| value)
+    }
+
+    /// Entries with a key within `range`, in key order.
+    pub fn range<Q: ?Sized + Ord, R: RangeBounds<Q>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        let slice = self.entries.as_slice();
+        let start = match range.start_bound() {
+            Bound::Included(q) => slice.partition_point(|(k, _)| k.borrow() < q),
+            Bound::Excluded(q) => slice.partition_point(|(k, _)| k.borrow() <= q),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(q) => slice.partition_point(|(k, _)| k.borrow() <= q),
+            Bound::Excluded(q) => slice.partition_point(|(k, _)| k.borrow() < q),
+            Bound::Unbounded => slice.len(),
+        };
+        slice[start..end.max(start)].iter().map(|(k, v)| (k, v))
+    }
+
+    /// Entry with the smallest key.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.as_slice().first().map(|(k, v)| (k, v))
+    }
+
+    /// Entry with the largest key.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.as_slice().last().map(|(k, v)| (k, v))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Entries in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Ord, V, const N: usize> Default for sortedmap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Later pairs win for duplicate keys.
+impl<K: Ord, V, const N: usize> FromIterator<(K, V)> for sortedmap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Ord, V, const N: usize> Extend<(K, V)> for sortedmap<K, V, N> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<K: Ord + fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for sortedmap<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Hashed map for the sizes where [`tinymap`]'s linear scan stops
+/// paying off, a few dozen entries and up: N inline buckets with open
+/// addressing (linear probing), moving to a `HashMap` once they're
+/// three quarters full. Iteration order is unspecified, as for
+/// `HashMap`.
+///
+/// ```rust
+/// use vecstor::tinyhashmap;
+/// let mut counts: tinyhashmap<&str, u32, 16> = tinyhashmap::new();
+/// for word in "the cat saw the dog".split(' ') {
+///     match counts.get_mut(word) {
+///         Some(n) => *n += 1,
+///         None => drop(counts.insert(word, 1)),
+///     }
+/// }
+/// assert_eq!(counts.get("the"), Some(&2));
+/// assert_eq!(counts.len(), 4);
+/// assert!(!counts.is_spilled());
+/// ```
+pub struct tinyhashmap<K, V, const N: usize, S = RandomState> {
+    repr: hashed<K, V, N, S>,
+    hasher: S,
+}
+
+enum hashed<K, V, const N: usize, S> {
+    inline {
+        buckets: [Option<bucket<K, V>>; N],
+        len: usize,
+    },
+    heap(HashMap<K, V, S>),
+}
+
+struct bucket<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+impl<K: Eq + Hash, V, const N: usize> tinyhashmap<K, V, N> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize, S: BuildHasher + Clone> tinyhashmap<K, V, N, S> {
+    /// Most entries kept inline, leaving a quarter of the buckets
+    /// empty; one more spills.
+    const INLINE: usize = N - N.div_ceil(4);
+
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            repr: hashed::inline {
+                buckets: std::array::from_fn(|_| None),
+                len: 0,
+            },
+            hasher,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            hashed::inline { len, .. } => *len,
+            hashed::heap(map) => map.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the entries moved to a `HashMap`.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.repr, hashed::heap(_))
+    }
+
+    /// Bucket holding `key`, when inline.
+    fn find<Q: ?Sized + Eq + Hash>(
+        buckets: &[Option<bucket<K, V>>; N],
+        hash: u64,
+        key: &Q,
+    ) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        if N == 0 {
+            return None;
+        }
+        let mut at = hash as usize % N;
+        // The load limit leaves at least one bucket empty.
+        while let Some(bucket) = &buckets[at] {
+            if bucket.hash == hash && bucket.key.borrow() == key {
+                return Some(at);
+            }
+            at = (at + 1) % N;
+        }
+        None
+    }
+
+    /// Set `key` to `value`, returning the old value if there was one.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hasher.hash_one(&key);
+        if let hashed::inline { buckets, len } = &mut self.repr {
+            if let Some(at) = Self::find(buckets, hash, &key) {
+                let old = buckets[at].as_mut()?;
+                return Some(std::mem::replace(&mut old.value, value));
+            }
+            if *len < Self::INLINE {
+                let mut at = hash as usize % N;
+                while buckets[at].is_some() {
+                    at = (at + 1) % N;
+                }
+                buckets[at] = Some(bucket { hash, key, value });
+                *len += 1;
+                return None;
+            }
+            self.spill();
+        }
+        match &mut self.repr {
+            hashed::heap(map) => map.insert(key, value),
+            hashed::inline { .. } => unreachable!(),
+        }
+    }
+
+    fn spill(&mut self) {
+        let mut map = HashMap::with_capacity_and_hasher(N * 2, self.hasher.clone());
+        if let hashed::inline { buckets, .. } = &mut self.repr {
+            for bucket in buckets.iter_mut().filter_map(Option::take) {
+                map.insert(bucket.key, bucket.value);
+            }
+        }
+        self.repr = hashed::heap(map);
+    }
+
+    pub fn get<Q: ?Sized + Eq + Hash>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        match &self.repr {
+            hashed::inline { buckets, .. } => {
+                let at = Self::find(buckets, self.hasher.hash_one(key), key)?;
+                buckets[at].as_ref().map(|bucket| &bucket.value)
+            }
+            hashed::heap(map) => map.get(key),
+        }
+    }
+
+    pub fn get_mut<Q: ?Sized + Eq + Hash>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        match &mut self.repr {
+            hashed::inline { buckets, .. } => {
+                let at = Self::find(buckets, self.hasher.hash_one(key), key)?;
+                buckets[at].as_mut().map(|bucket| &mut bucket.value)
+            }
+            hashed::heap(map) => map.get_mut(key),
+        }
+    }
+
+    pub fn contains_key<Q: ?Sized + Eq + Hash>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Remove `key`. Inline, the entries after it in its probe run
+    /// shift back, so there are no tombstones to slow lookups down.
+    pub fn remove<Q: ?Sized + Eq + Hash>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let (buckets, len) = match &mut self.repr {
+            hashed::inline { buckets, len } => (buckets, len),
+            hashed::heap(map) => return map.remove(key),
+        };
+        let mut hole = Self::find(buckets, self.hasher.hash_one(key), key)?;
+        let removed = buckets[hole].take()?;
+        *len -= 1;
+        let mut at = (hole + 1) % N;
+        while let Some(bucket) = &buckets[at] {
+            let home = bucket.hash as usize % N;
+            // Move back unless its home lies after the hole.
+            if (at + N - home) % N >= (at + N - hole) % N {
+                buckets[hole] = buckets[at].take();
+                hole = at;
+            }
+            at = (at + 1) % N;
+        }
+        Some(removed.value)
+    }
+
+    /// Remove everything; a spilled map keeps its `HashMap`.
+    pub fn clear(&mut self) {
+        match &mut self.repr {
+            hashed::inline { buckets, len } => {
+                buckets.iter_mut().for_each(|bucket| *bucket = None);
+                *len = 0;
+            }
+            hashed::heap(map) => map.clear(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let (inline, heap) = match &self.repr {
+            hashed::inline { buckets, .. } => (Some(buckets.iter().flatten()), None),
+            hashed::heap(map) => (None, Some(map.iter())),
+        };
+        let inline = inline
+            .into_iter()
+            .flatten()
+            .map(|bucket| (&bucket.key, &bucket.value));
+        inline.chain(heap.into_iter().flatten())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize, S: BuildHasher + Clone + Default> Default
+    for tinyhashmap<K, V, N, S>
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize, S: BuildHasher + Clone + Default> FromIterator<(K, V)>
+    for tinyhashmap<K, V, N, S>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize, S: BuildHasher + Clone> Extend<(K, V)>
+    for tinyhashmap<K, V, N, S>
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<K: Eq + Hash + fmt::Debug, V: fmt::Debug, const N: usize, S: BuildHasher + Clone> fmt::Debug
+    for tinyhashmap<K, V, N, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
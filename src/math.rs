@@ -0,0 +1,107 @@
+//! Element-wise arithmetic for numeric vectors, e.g. small
+//! fixed-dimension float vectors, and descriptive statistics over
+//! float samples.
+
+use core::ops::{AddAssign, MulAssign, SubAssign};
+
+use crate::{storage, storagevec};
+
+macro_rules! elementwise {
+    ($($(#[$doc:meta])* $name:ident: $op:ident, $method:ident;)+) => {$(
+        $(#[$doc])*
+        pub fn $name<R: storage<T>>(&mut self, other: &storagevec<T, R>)
+        where
+            T: $op + Copy,
+        {
+            assert_eq!(
+                self.len(),
+                other.len(),
+                concat!(stringify!($name), ": lengths differ")
+            );
+            for (x, &y) in self.iter_mut().zip(other.iter()) {
+                x.$method(y);
+            }
+        }
+    )+};
+}
+
+impl<T, S: storage<T>> storagevec<T, S> {
+    elementwise! {
+        /// `self[i] += other[i]` for every `i`. Panics if the lengths
+        /// differ, as do the others.
+        add_assign_elementwise: AddAssign, add_assign;
+        sub_assign_elementwise: SubAssign, sub_assign;
+        mul_assign_elementwise: MulAssign, mul_assign;
+    }
+
+    /// Multiply every element by `factor`.
+    pub fn scale(&mut self, factor: T)
+    where
+        T: MulAssign + Copy,
+    {
+        for x in self.iter_mut() {
+            *x *= factor;
+        }
+    }
+}
+
+/// Statistics for sample windows. All of them return `None` for an
+/// empty vector, and work in place, with no copy of the samples.
+macro_rules! impl_statistics {
+    ($($float:ident),+) => {$(
+        impl<S: storage<$float>> storagevec<$float, S> {
+            pub fn mean(&self) -> Option<$float> {
+                if self.is_empty() {
+                    return None;
+                }
+                Some(self.iter().sum::<$float>() / self.len() as $float)
+            }
+
+            /// Population variance, the mean squared distance from
+            /// `mean()`.
+            pub fn variance(&self) -> Option<$float> {
+                let mean = self.mean()?;
+                let squares: $float = self.iter().map(|x| (x - mean) * (x - mean)).sum();
+                Some(squares / self.len() as $float)
+            }
+
+            /// Square root of `variance()`.
+            pub fn stddev(&self) -> Option<$float> {
+                self.variance().map($float::sqrt)
+            }
+
+            /// The `p`th percentile, `p` from 0 to 100, interpolating
+            /// linearly between the nearest ranks; `percentile(50.0)` is
+            /// the median. `None` if `p` is out of range.
+            ///
+            /// Ranks by `total_cmp`, so NaNs count as the extremes.
+            /// Doesn't reorder the samples: each rank is found by
+            /// counting, quadratic in the length, which is cheap at
+            /// the sizes this crate is for.
+            pub fn percentile(&self, p: $float) -> Option<$float> {
+                if self.is_empty() || !(0.0..=100.0).contains(&p) {
+                    return None;
+                }
+                let rank = p / 100.0 * (self.len() - 1) as $float;
+                let below = rank.floor();
+                let low = self.nth_smallest(below as usize)?;
+                if rank == below {
+                    // Exact rank, no interpolating an infinity into NaN.
+                    return Some(low);
+                }
+                let high = self.nth_smallest(rank.ceil() as usize)?;
+                Some(low + (high - low) * (rank - below))
+            }
+
+            fn nth_smallest(&self, n: usize) -> Option<$float> {
+                self.iter().copied().find(|x| {
+                    let less = self.iter().filter(|y| y.total_cmp(x).is_lt()).count();
+                    let equal = self.iter().filter(|y| y.total_cmp(x).is_eq()).count();
+                    less <= n && n < less + equal
+                })
+            }
+        }
+    )+};
+}
+
+impl_statistics!(f32, f64);
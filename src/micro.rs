@@ -0,0 +1,68 @@
+//! ufmt support, for printing over a serial port where `core::fmt`
+//! costs too much flash: `uDebug`/`uDisplay` for vectors over any
+//! backend and for tinystring, in the same layout as `Debug` and
+//! `Display`, and `uWrite` for tinystring so `uwrite!` can build one.
+
+use core::convert::Infallible;
+
+use ufmt::{Formatter, uDebug, uDisplay, uWrite};
+
+use crate::{storage, storagevec, tinystring};
+
+/// `[1, 2, 3]`, the elements only.
+impl<T: uDebug, S: storage<T>> uDebug for storagevec<T, S> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        <[T] as uDebug>::fmt(self.as_slice(), f)
+    }
+}
+
+/// `[ 1, 2, 3 ]`, `[ ]` when empty.
+impl<T: uDisplay, S: storage<T>> uDisplay for storagevec<T, S> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        if self.is_empty() {
+            return f.write_str("[ ]");
+        }
+        f.write_str("[ ")?;
+        for (i, element) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            element.fmt(f)?;
+        }
+        f.write_str(" ]")
+    }
+}
+
+impl<const N: usize> uDisplay for tinystring<N> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Quoted, escaping only `"` and `\`: ufmt has no `escape_debug()`,
+/// its panicking branch is what it avoids.
+impl<const N: usize> uDebug for tinystring<N> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_char('"')?;
+        for piece in self.as_str().split_inclusive(['"', '\\']) {
+            match piece.strip_suffix(['"', '\\']) {
+                Some(text) => {
+                    f.write_str(text)?;
+                    f.write_char('\\')?;
+                    f.write_str(&piece[text.len()..])?;
+                }
+                None => f.write_str(piece)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+impl<const N: usize> uWrite for tinystring<N> {
+    type Error = Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        self.push_str(s);
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! Sorting networks for the lengths a tinyvec usually has.
+//!
+//! A network is a fixed list of compare-exchanges, so there are no
+//! data-dependent branches beyond the comparison itself, which for
+//! small `Copy` keys compiles to conditional moves. Up to 8 elements
+//! that beats the general sort's insertion sort.
+
+/// Longest slice [`sort`] handles.
+pub(crate) const MAX: usize = 8;
+
+const N2: &[(u8, u8)] = &[(0, 1)];
+const N3: &[(u8, u8)] = &[(0, 2), (0, 1), (1, 2)];
+const N4: &[(u8, u8)] = &[(0, 2), (1, 3), (0, 1), (2, 3), (1, 2)];
+#[rustfmt::skip]
+const N5: &[(u8, u8)] = &[
+    (0, 3), (1, 4), (0, 2), (1, 3), (0, 1), (2, 4), (1, 2), (3, 4), (2, 3),
+];
+#[rustfmt::skip]
+const N6: &[(u8, u8)] = &[
+    (0, 5), (1, 3), (2, 4), (1, 2), (3, 4), (0, 3), (2, 5), (0, 1), (2, 3), (4, 5),
+    (1, 2), (3, 4),
+];
+#[rustfmt::skip]
+const N7: &[(u8, u8)] = &[
+    (0, 6), (2, 3), (4, 5), (0, 2), (1, 4), (3, 6), (0, 1), (2, 5), (3, 4), (1, 2),
+    (4, 6), (2, 3), (4, 5), (1, 2), (3, 4), (5, 6),
+];
+#[rustfmt::skip]
+const N8: &[(u8, u8)] = &[
+    (0, 2), (1, 3), (4, 6), (5, 7), (0, 4), (1, 5), (2, 6), (3, 7), (0, 1), (2, 3),
+    (4, 5), (6, 7), (2, 4), (3, 5), (1, 4), (3, 6), (1, 2), (3, 4), (5, 6),
+];
+
+/// Sort `v`, at most [`MAX`] long, by `is_less`. Not stable. A
+/// panicking `is_less` leaves `v` a permutation of what it was.
+pub(crate) fn sort<T, F: FnMut(&T, &T) -> bool>(v: &mut [T], is_less: &mut F) {
+    let network = match v.len() {
+        0 | 1 => return,
+        2 => N2,
+        3 => N3,
+        4 => N4,
+        5 => N5,
+        6 => N6,
+        7 => N7,
+        8 => N8,
+        len => unreachable!("no sorting network for {len} elements"),
+    };
+    let base = v.as_mut_ptr();
+    for &(a, b) in network {
+        // In bounds: every network only names slots below its length.
+        unsafe { swap_if_less(base.add(usize::from(a)), base.add(usize::from(b)), is_less) };
+    }
+}
+
+/// Put the lesser of `*a` and `*b` in `a`, picking the sources with
+/// selects instead of a branch, as the standard library's small sorts
+/// do. `is_less` runs before anything moves, so a panic in it is
+/// harmless.
+unsafe fn swap_if_less<T, F: FnMut(&T, &T) -> bool>(a: *mut T, b: *mut T, is_less: &mut F) {
+    unsafe {
+        let swap = is_less(&*b, &*a);
+        let (first, second) = if swap { (b, a) } else { (a, b) };
+        let second = std::mem::ManuallyDrop::new(std::ptr::read(second));
+        std::ptr::copy(first, a, 1);
+        std::ptr::copy_nonoverlapping(&*second, b, 1);
+    }
+}
@@ -0,0 +1,76 @@
+//! rayon support: parallel iteration, sorting and extending, so code
+//! written against `Vec`'s parallel API keeps working.
+
+use rayon::iter::{IntoParallelIterator, ParallelExtend, ParallelIterator};
+use rayon::slice::{Iter, IterMut, ParallelSliceMut};
+
+use crate::{storage, storagevec};
+
+/// Moves the elements into a `Vec` for rayon to split up.
+impl<T: Send, S: storage<T>> IntoParallelIterator for storagevec<T, S> {
+    type Item = T;
+    type Iter = rayon::vec::IntoIter<T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_iter().collect::<Vec<T>>().into_par_iter()
+    }
+}
+
+impl<'a, T: Sync, S: storage<T>> IntoParallelIterator for &'a storagevec<T, S> {
+    type Item = &'a T;
+    type Iter = Iter<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_slice().into_par_iter()
+    }
+}
+
+impl<'a, T: Send, S: storage<T>> IntoParallelIterator for &'a mut storagevec<T, S> {
+    type Item = &'a mut T;
+    type Iter = IterMut<'a, T>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_mut_slice().into_par_iter()
+    }
+}
+
+impl<T: Send, S: storage<T>> storagevec<T, S> {
+    /// Stable sort on the rayon pool, like `slice::par_sort`. Worth it
+    /// once a tinyvec has spilled well past N.
+    pub fn par_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().par_sort();
+    }
+
+    pub fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> std::cmp::Ordering + Sync,
+    {
+        self.as_mut_slice().par_sort_by(compare);
+    }
+
+    pub fn par_sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().par_sort_unstable();
+    }
+
+    pub fn par_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> std::cmp::Ordering + Sync,
+    {
+        self.as_mut_slice().par_sort_unstable_by(compare);
+    }
+}
+
+/// Items in the iterator's order. They are gathered on the pool
+/// first, then appended, so a tinyvec only grows once.
+impl<T: Send, S: storage<T>> ParallelExtend<T> for storagevec<T, S> {
+    fn par_extend<I: IntoParallelIterator<Item = T>>(&mut self, par_iter: I) {
+        let items: Vec<T> = par_iter.into_par_iter().collect();
+        self.extend(items);
+    }
+}
@@ -0,0 +1,117 @@
+//! nom support: [`tinybytes`] is a nom input, so parsers written for
+//! `&[u8]` run over it as they are and hand back `tinybytes` pieces
+//! that share the buffer (or are copied inline when short) instead of
+//! borrowing it.
+//!
+//! A byte tinyvec needs no impl of its own: `as_slice()` is already
+//! an input. It can't be one itself, nom inputs have to be cheap to
+//! clone. `Offset`, and so `recognize()`, isn't provided, since
+//! inline pieces are copies that share no address with their source.
+
+use nom::{AsBytes, Compare, CompareResult, FindSubstring, FindToken, Input, Needed};
+
+use crate::tinybytes;
+
+/// The bytes of a [`tinybytes`], by value.
+pub struct iter<const N: usize> {
+    bytes: tinybytes<N>,
+    at: usize,
+}
+
+impl<const N: usize> Iterator for iter<N> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.at)?;
+        self.at += 1;
+        Some(byte)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.bytes.len() - self.at;
+        (left, Some(left))
+    }
+}
+
+impl<const N: usize> Input for tinybytes<N> {
+    type Item = u8;
+    type Iter = iter<N>;
+    type IterIndices = std::iter::Enumerate<iter<N>>;
+
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    fn take(&self, index: usize) -> Self {
+        self.slice(..index)
+    }
+
+    fn take_from(&self, index: usize) -> Self {
+        self.slice(index..)
+    }
+
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.slice(index..), self.slice(..index))
+    }
+
+    fn position<P: Fn(u8) -> bool>(&self, predicate: P) -> Option<usize> {
+        self.iter().position(|&b| predicate(b))
+    }
+
+    fn iter_elements(&self) -> iter<N> {
+        iter {
+            bytes: self.clone(),
+            at: 0,
+        }
+    }
+
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.iter_elements().enumerate()
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        match count.checked_sub(self.len()) {
+            Some(missing @ 1..) => Err(Needed::new(missing)),
+            _ => Ok(count),
+        }
+    }
+}
+
+/// Anything a byte slice compares against, e.g. `tag(&b"GET"[..])`
+/// or `tag("GET")`.
+impl<T, const N: usize> Compare<T> for tinybytes<N>
+where
+    for<'a> &'a [u8]: Compare<T>,
+{
+    fn compare(&self, t: T) -> CompareResult {
+        self.as_slice().compare(t)
+    }
+
+    fn compare_no_case(&self, t: T) -> CompareResult {
+        self.as_slice().compare_no_case(t)
+    }
+}
+
+impl<T, const N: usize> FindSubstring<T> for tinybytes<N>
+where
+    for<'a> &'a [u8]: FindSubstring<T>,
+{
+    fn find_substring(&self, substr: T) -> Option<usize> {
+        self.as_slice().find_substring(substr)
+    }
+}
+
+impl<T, const N: usize> FindToken<T> for tinybytes<N>
+where
+    for<'a> &'a [u8]: FindToken<T>,
+{
+    fn find_token(&self, token: T) -> bool {
+        self.as_slice().find_token(token)
+    }
+}
+
+impl<const N: usize> AsBytes for tinybytes<N> {
+    fn as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
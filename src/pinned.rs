@@ -0,0 +1,132 @@
+//! Inline vector whose elements never move, see [`pinnedvec`].
+
+use std::fmt;
+use std::marker::PhantomPinned;
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+use crate::error::capacity_error;
+
+/// Up to N elements inline, with addresses that stay put from `push`
+/// until they're dropped. It is `!Unpin` and every mutation goes
+/// through `Pin<&mut Self>`, so once pinned the vector itself can't
+/// move either; what would shift elements (insert, remove, sort,
+/// spilling) isn't offered, and pushing past N fails rather than
+/// spill. Elements are handed out pinned, so they may be `!Unpin`
+/// too, e.g. hold intrusive back-pointers into the buffer.
+///
+/// ```rust
+/// use std::pin::pin;
+/// use vecstor::pinnedvec;
+/// let mut nodes = pin!(pinnedvec::<u32, 4>::new());
+/// let first: *const u32 = &*nodes.as_mut().push(1).unwrap();
+/// nodes.as_mut().push(2).unwrap();
+/// nodes.as_mut().truncate(1);
+/// assert_eq!(nodes.as_ptr(), first);
+/// assert_eq!(nodes.as_slice(), [1]);
+/// ```
+pub struct pinnedvec<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    len: usize,
+    pinned: PhantomPinned,
+}
+
+impl<T, const N: usize> pinnedvec<T, N> {
+    /// Empty, to be pinned with `pin!` or `Box::pin` before use.
+    pub const fn new() -> Self {
+        Self {
+            buf: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+            pinned: PhantomPinned,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether another `push()` would fail.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Always N.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr() as *const T
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len) }
+    }
+
+    pub fn get(&self, at: usize) -> Option<&T> {
+        self.as_slice().get(at)
+    }
+
+    /// Push `element` and return it pinned in its slot, or hand it
+    /// back if all N slots are taken.
+    pub fn push(self: Pin<&mut Self>, element: T) -> Result<Pin<&mut T>, capacity_error<T>> {
+        // Nothing below moves an element or the buffer.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.len == N {
+            return Err(capacity_error::new(element));
+        }
+        let slot = this.buf[this.len].write(element);
+        this.len += 1;
+        Ok(unsafe { Pin::new_unchecked(slot) })
+    }
+
+    /// The element at `at`, pinned.
+    pub fn get_pin_mut(self: Pin<&mut Self>, at: usize) -> Option<Pin<&mut T>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        if at >= this.len {
+            return None;
+        }
+        Some(unsafe { Pin::new_unchecked(this.buf[at].assume_init_mut()) })
+    }
+
+    /// Drop the elements from `len` on in place, last first.
+    pub fn truncate(self: Pin<&mut Self>, len: usize) {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.drop_from(len);
+    }
+
+    pub fn clear(self: Pin<&mut Self>) {
+        self.truncate(0);
+    }
+
+    fn drop_from(&mut self, len: usize) {
+        while self.len > len {
+            // Shrink first, so a panicking drop doesn't leave it counted.
+            self.len -= 1;
+            unsafe { self.buf[self.len].assume_init_drop() };
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for pinnedvec<T, N> {
+    fn drop(&mut self) {
+        self.drop_from(0);
+    }
+}
+
+impl<T, const N: usize> Default for pinnedvec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for pinnedvec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
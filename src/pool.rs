@@ -0,0 +1,65 @@
+//! Free list of spilled tinyvecs, see [`vecpool`].
+
+use crate::tinyvec;
+
+/// Keeps up to `limit` emptied tinyvecs whose heap allocation is worth
+/// reusing. [`take`](vecpool::take) hands one out (or a fresh tinyvec
+/// when the pool is empty), [`give`](vecpool::give) clears it and puts
+/// it back. Vectors that never spilled carry nothing worth keeping and
+/// are just dropped.
+///
+/// The pool isn't `Sync`; for a per-thread pool put it in a
+/// `thread_local!`:
+///
+/// ```rust
+/// use std::cell::RefCell;
+/// use vecstor::pool::vecpool;
+///
+/// thread_local! {
+///     static BUFFERS: RefCell<vecpool<u8, 64>> = RefCell::new(vecpool::new(8));
+/// }
+///
+/// let mut body = BUFFERS.with_borrow_mut(|pool| pool.take());
+/// body.extend(&[0; 200]);
+/// BUFFERS.with_borrow_mut(|pool| pool.give(body));
+///
+/// let reused = BUFFERS.with_borrow_mut(|pool| pool.take());
+/// assert!(reused.is_empty() && reused.is_spilled());
+/// ```
+pub struct vecpool<T, const N: usize> {
+    free: Vec<tinyvec<T, N>>,
+    limit: usize,
+}
+
+impl<T, const N: usize> vecpool<T, N> {
+    /// Empty pool keeping at most `limit` vectors.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            free: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Vectors waiting to be reused.
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// An empty tinyvec, with a pooled heap allocation if there is one.
+    pub fn take(&mut self) -> tinyvec<T, N> {
+        self.free.pop().unwrap_or_default()
+    }
+
+    /// Clear `vec` and keep it for a later `take`, unless it never
+    /// spilled or the pool is full.
+    pub fn give(&mut self, mut vec: tinyvec<T, N>) {
+        if vec.is_spilled() && self.free.len() < self.limit {
+            vec.clear();
+            self.free.push(vec);
+        }
+    }
+}
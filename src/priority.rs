@@ -0,0 +1,135 @@
+//! Binary heap on inline storage, see [`tinyheap`].
+
+use std::fmt;
+
+use crate::tinyvec;
+
+/// Max-heap over a [`tinyvec`]: up to N elements it never allocates.
+/// Wrap elements in [`Reverse`](std::cmp::Reverse) for a min-heap.
+///
+/// ```rust
+/// use std::cmp::Reverse;
+/// use vecstor::tinyheap;
+/// let mut open: tinyheap<Reverse<u32>, 8> = tinyheap::new();
+/// open.push(Reverse(5));
+/// open.push(Reverse(1));
+/// open.push(Reverse(3));
+/// assert_eq!(open.pop(), Some(Reverse(1)));
+/// ```
+pub struct tinyheap<T, const N: usize> {
+    data: tinyvec<T, N>,
+}
+
+impl<T: Ord, const N: usize> tinyheap<T, N> {
+    pub fn new() -> Self {
+        Self {
+            data: tinyvec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Whether the elements moved to the heap allocation.
+    pub fn is_spilled(&self) -> bool {
+        self.data.is_spilled()
+    }
+
+    /// The largest element.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.get(0)
+    }
+
+    pub fn push(&mut self, element: T) {
+        self.data.push(element);
+        self.sift_up(self.len() - 1);
+    }
+
+    /// Remove and return the largest element.
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        self.data.as_mut_slice().swap(0, len - 1);
+        let top = self.data.pop();
+        self.sift_down(0);
+        top
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Elements in heap order, which is no particular order.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Elements sorted ascending.
+    pub fn into_sorted(self) -> tinyvec<T, N> {
+        let mut data = self.data;
+        data.as_mut_slice().sort();
+        data
+    }
+
+    fn sift_up(&mut self, mut at: usize) {
+        let slice = self.data.as_mut_slice();
+        while at > 0 {
+            let parent = (at - 1) / 2;
+            if slice[at] <= slice[parent] {
+                break;
+            }
+            slice.swap(at, parent);
+            at = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut at: usize) {
+        let slice = self.data.as_mut_slice();
+        loop {
+            let mut largest = at;
+            for child in [2 * at + 1, 2 * at + 2] {
+                if child < slice.len() && slice[child] > slice[largest] {
+                    largest = child;
+                }
+            }
+            if largest == at {
+                break;
+            }
+            slice.swap(at, largest);
+            at = largest;
+        }
+    }
+}
+
+impl<T: Ord, const N: usize> Default for tinyheap<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the heap bottom-up in O(n).
+impl<T: Ord, const N: usize> FromIterator<T> for tinyheap<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self {
+            data: iter.into_iter().collect(),
+        };
+        for at in (0..heap.len() / 2).rev() {
+            heap.sift_down(at);
+        }
+        heap
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: Ord + fmt::Debug, const N: usize> fmt::Debug for tinyheap<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
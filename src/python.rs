@@ -0,0 +1,60 @@
+//! pyo3 support: a tinyvec converts from any Python sequence except
+//! `str`, and to a `list`, or to `bytes` for `u8`, the same as `Vec`.
+
+use pyo3::conversion::FromPyObjectOwned;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PySequence, PyString};
+
+use crate::allocator::Global;
+use crate::{growth, index, tinyvec};
+
+/// Pushes the items one by one, so arguments of up to N elements are
+/// taken without a heap allocation, `bytes` included.
+impl<'py, T, const N: usize, G: growth::policy, L: index> FromPyObject<'_, 'py>
+    for tinyvec<T, N, Global, G, L>
+where
+    T: FromPyObjectOwned<'py>,
+{
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> PyResult<Self> {
+        if obj.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err("Can't extract `str` to `tinyvec`"));
+        }
+        let sequence = obj.cast::<PySequence>()?;
+        let mut vec = Self::new();
+        for item in sequence.try_iter()? {
+            vec.push(item?.extract::<T>().map_err(Into::into)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl<'py, T, const N: usize, G: growth::policy, L: index> IntoPyObject<'py>
+    for tinyvec<T, N, Global, G, L>
+where
+    T: IntoPyObject<'py>,
+{
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        self.into_vec().into_pyobject(py)
+    }
+}
+
+impl<'a, 'py, T, const N: usize, G: growth::policy, L: index> IntoPyObject<'py>
+    for &'a tinyvec<T, N, Global, G, L>
+where
+    &'a T: IntoPyObject<'py>,
+{
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        self.as_slice().into_pyobject(py)
+    }
+}
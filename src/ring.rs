@@ -0,0 +1,165 @@
+//! Bounded ring that overwrites its oldest element, see [`tinyring`].
+
+use std::fmt;
+use std::iter::Chain;
+use std::mem::MaybeUninit;
+use std::slice;
+
+/// The last N elements pushed, kept inline. Pushing into a full ring
+/// drops the oldest element to make room instead of growing or
+/// failing, so it never allocates; reads go oldest to newest.
+///
+/// ```rust
+/// use vecstor::tinyring;
+/// let mut samples: tinyring<u32, 3> = tinyring::new();
+/// for ms in [10, 12, 11, 40, 9] {
+///     samples.push(ms);
+/// }
+/// assert_eq!(samples.iter().copied().collect::<Vec<_>>(), [11, 40, 9]);
+/// assert_eq!(samples.newest(), Some(&9));
+/// ```
+pub struct tinyring<T, const N: usize> {
+    buf: [MaybeUninit<T>; N],
+    /// Slot of the oldest element.
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> tinyring<T, N> {
+    pub fn new() -> Self {
+        Self {
+            buf: [const { MaybeUninit::uninit() }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the next push overwrites.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Always N.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Slot of the element `at` positions after the oldest.
+    fn slot(&self, at: usize) -> usize {
+        let index = self.head + at;
+        if index >= N { index - N } else { index }
+    }
+
+    /// Append `element` as the newest. Hands back the oldest element
+    /// if it had to go, or `element` itself when N is 0.
+    pub fn push(&mut self, element: T) -> Option<T> {
+        if N == 0 {
+            return Some(element);
+        }
+        if self.len < N {
+            let slot = self.slot(self.len);
+            self.buf[slot].write(element);
+            self.len += 1;
+            return None;
+        }
+
+        let oldest = std::mem::replace(&mut self.buf[self.head], MaybeUninit::new(element));
+        self.head = self.slot(1);
+        Some(unsafe { oldest.assume_init() })
+    }
+
+    /// Remove the oldest element.
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let element = unsafe { self.buf[self.head].assume_init_read() };
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(element)
+    }
+
+    /// Element `at` positions after the oldest.
+    pub fn get(&self, at: usize) -> Option<&T> {
+        if at >= self.len {
+            return None;
+        }
+        Some(unsafe { self.buf[self.slot(at)].assume_init_ref() })
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    /// The elements oldest first, as the run from the oldest to the
+    /// end of the buffer and the run that wrapped around.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first = self.len.min(N - self.head);
+        unsafe {
+            let base = self.buf.as_ptr() as *const T;
+            (
+                slice::from_raw_parts(base.add(self.head), first),
+                slice::from_raw_parts(base, self.len - first),
+            )
+        }
+    }
+
+    /// Oldest to newest.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second)
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_oldest().is_some() {}
+        self.head = 0;
+    }
+}
+
+impl<T, const N: usize> Drop for tinyring<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for tinyring<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps only the last N items.
+impl<T, const N: usize> Extend<T> for tinyring<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for tinyring<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ring = Self::new();
+        ring.extend(iter);
+        ring
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for tinyring<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,65 @@
+//! schemars `JsonSchema` impls, matching the shapes the std types they
+//! stand in for produce: arrays for tinyvec and stackvec, a string for
+//! tinystring.
+
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+
+use crate::{stackvec, tinystring, tinyvec};
+
+/// No `maxItems`: N is only where the heap takes over.
+impl<T: JsonSchema, const N: usize> JsonSchema for tinyvec<T, N> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <Vec<T>>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        <Vec<T>>::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Vec<T>>::json_schema(generator)
+    }
+}
+
+/// `maxItems` is N, since a stackvec can't hold more.
+impl<T: JsonSchema, const N: usize> JsonSchema for stackvec<T, N> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        format!("Array_up_to_size_{N}_of_{}", T::schema_name()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "array",
+            "items": generator.subschema_for::<T>(),
+            "maxItems": N,
+        })
+    }
+}
+
+impl<const N: usize> JsonSchema for tinystring<N> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        String::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        String::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        String::json_schema(generator)
+    }
+}
@@ -0,0 +1,177 @@
+//! Small set, see [`tinyset`].
+
+use std::borrow::Borrow;
+use std::fmt;
+
+use crate::tinyvec;
+
+/// Set that keeps its elements in a [`tinyvec`], in insertion order,
+/// with linear-scan lookups. Sets of up to N elements never allocate.
+///
+/// ```rust
+/// use vecstor::tinyset;
+/// let mut seen: tinyset<u32, 8> = tinyset::new();
+/// assert!(seen.insert(7));
+/// assert!(!seen.insert(7));
+/// assert!(seen.contains(&7));
+/// ```
+pub struct tinyset<T, const N: usize> {
+    elements: tinyvec<T, N>,
+}
+
+impl<T: Eq, const N: usize> tinyset<T, N> {
+    pub fn new() -> Self {
+        Self {
+            elements: tinyvec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Whether the elements moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.elements.is_spilled()
+    }
+
+    fn position<Q: ?Sized + Eq>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+    {
+        self.elements.iter().position(|e| e.borrow() == value)
+    }
+
+    /// Add `value`. Returns false, dropping `value`, if it
+    /// was already there.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.contains(&value) {
+            return false;
+        }
+        self.elements.push(value);
+        true
+    }
+
+    pub fn contains<Q: ?Sized + Eq>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.position(value).is_some()
+    }
+
+    /// Remove `value`, keeping the others in order. Returns
+    /// whether it was there.
+    pub fn remove<Q: ?Sized + Eq>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        match self.position(value) {
+            Some(at) => self.elements.remove(at).is_some(),
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// Elements in insertion order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.elements.as_slice()
+    }
+}
+
+/// Lazy set algebra, like `HashSet`'s: each yields references in
+/// this set's order, then `other`'s for `union()`. Collect into a
+/// `tinyset` for a new set:
+///
+/// ```rust
+/// use vecstor::tinyset;
+/// let granted: tinyset<&str, 4> = ["read", "write", "admin"].into_iter().collect();
+/// let required: tinyset<&str, 2> = ["read", "write"].into_iter().collect();
+/// assert!(required.is_subset(&granted));
+/// let extra: tinyset<&str, 4> = granted.difference(&required).copied().collect();
+/// assert_eq!(extra.as_slice(), ["admin"]);
+/// ```
+impl<T: Eq, const N: usize> tinyset<T, N> {
+    /// Elements of either set.
+    pub fn union<'a, const M: usize>(
+        &'a self,
+        other: &'a tinyset<T, M>,
+    ) -> impl Iterator<Item = &'a T> {
+        self.iter().chain(other.difference(self))
+    }
+
+    /// Elements of both sets.
+    pub fn intersection<'a, const M: usize>(
+        &'a self,
+        other: &'a tinyset<T, M>,
+    ) -> impl Iterator<Item = &'a T> {
+        self.iter().filter(|value| other.contains(*value))
+    }
+
+    /// Elements of this set that `other` doesn't have.
+    pub fn difference<'a, const M: usize>(
+        &'a self,
+        other: &'a tinyset<T, M>,
+    ) -> impl Iterator<Item = &'a T> {
+        self.iter().filter(|value| !other.contains(*value))
+    }
+
+    /// Elements of exactly one of the sets.
+    pub fn symmetric_difference<'a, const M: usize>(
+        &'a self,
+        other: &'a tinyset<T, M>,
+    ) -> impl Iterator<Item = &'a T> {
+        self.difference(other).chain(other.difference(self))
+    }
+
+    pub fn is_subset<const M: usize>(&self, other: &tinyset<T, M>) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    pub fn is_superset<const M: usize>(&self, other: &tinyset<T, M>) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint<const M: usize>(&self, other: &tinyset<T, M>) -> bool {
+        self.intersection(other).next().is_none()
+    }
+}
+
+impl<T: Eq, const N: usize> Default for tinyset<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq, const N: usize> FromIterator<T> for tinyset<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Eq, const N: usize> Extend<T> for tinyset<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: Eq + fmt::Debug, const N: usize> fmt::Debug for tinyset<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,214 @@
+//! Copy-on-write vector whose heap buffer is shared, see
+//! [`sharedvec`].
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::tinyvec;
+
+/// [`tinyvec`] whose spilled elements live in an `Arc`, so cloning
+/// past N is a reference count bump instead of a copy. The buffer is
+/// only copied when a clone that shares it is mutated; up to N
+/// elements are inline and clone element by element, as usual.
+///
+/// For fanning one buffer out to many readers. Mutating methods take
+/// `T: Clone`, for the copy.
+///
+/// ```rust
+/// use vecstor::sharedvec;
+/// let frame: sharedvec<u8, 16> = (0..=255).collect();
+/// let mut copy = frame.clone();
+/// assert!(frame.is_shared());
+/// copy.push(0);
+/// assert!(!frame.is_shared());
+/// assert_eq!((frame.len(), copy.len()), (256, 257));
+/// ```
+pub struct sharedvec<T, const N: usize> {
+    repr: repr<T, N>,
+}
+
+enum repr<T, const N: usize> {
+    /// Never spilled.
+    inline(tinyvec<T, N>),
+    heap(Arc<Vec<T>>),
+}
+
+impl<T, const N: usize> sharedvec<T, N> {
+    pub fn new() -> Self {
+        Self {
+            repr: repr::inline(tinyvec::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the elements moved to the shared heap buffer.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.repr, repr::heap(_))
+    }
+
+    /// Whether another clone holds the same buffer, so that the next
+    /// mutation copies it.
+    pub fn is_shared(&self) -> bool {
+        match &self.repr {
+            repr::heap(heap) => Arc::strong_count(heap) > 1,
+            repr::inline(_) => false,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        match &self.repr {
+            repr::inline(vec) => vec.as_slice(),
+            repr::heap(heap) => heap,
+        }
+    }
+
+    /// Drop the elements, or this clone's share of them; nothing is
+    /// copied.
+    pub fn clear(&mut self) {
+        self.repr = repr::inline(tinyvec::new());
+    }
+}
+
+impl<T: Clone, const N: usize> sharedvec<T, N> {
+    /// The heap buffer, copied first if shared.
+    fn heap_mut(heap: &mut Arc<Vec<T>>) -> &mut Vec<T> {
+        Arc::make_mut(heap)
+    }
+
+    /// Mutable view, copying the buffer first if it is shared.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.repr {
+            repr::inline(vec) => vec.as_mut_slice(),
+            repr::heap(heap) => Self::heap_mut(heap),
+        }
+    }
+
+    pub fn push(&mut self, element: T) {
+        match &mut self.repr {
+            repr::inline(vec) if vec.len() < N => vec.push(element),
+            repr::inline(vec) => {
+                let mut heap = std::mem::take(vec).into_vec();
+                heap.reserve(N.max(1));
+                heap.push(element);
+                self.repr = repr::heap(Arc::new(heap));
+            }
+            repr::heap(heap) => Self::heap_mut(heap).push(element),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.repr {
+            repr::inline(vec) => vec.pop(),
+            repr::heap(heap) => Self::heap_mut(heap).pop(),
+        }
+    }
+
+    /// Panics if `index > len()`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        assert!(index <= self.len(), "insert: index out of bounds");
+        self.push(element);
+        self.as_mut_slice()[index..].rotate_right(1);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        match &mut self.repr {
+            repr::inline(vec) => vec.remove(index),
+            repr::heap(heap) if index < heap.len() => Some(Self::heap_mut(heap).remove(index)),
+            repr::heap(_) => None,
+        }
+    }
+
+    /// Keep the first `len` elements. A shared buffer isn't copied
+    /// whole; only those `len` are.
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.repr {
+            repr::inline(vec) => vec.truncate(len),
+            repr::heap(heap) if Arc::strong_count(heap) == 1 => Self::heap_mut(heap).truncate(len),
+            repr::heap(heap) if len < heap.len() => *heap = Arc::new(heap[..len].to_vec()),
+            repr::heap(_) => {}
+        }
+    }
+
+    /// The elements as a plain tinyvec, taking over the heap buffer
+    /// if this is its only owner.
+    pub fn into_tinyvec(self) -> tinyvec<T, N> {
+        match self.repr {
+            repr::inline(vec) => vec,
+            repr::heap(heap) => tinyvec::from(Arc::unwrap_or_clone(heap)),
+        }
+    }
+}
+
+/// O(1) once spilled.
+impl<T: Clone, const N: usize> Clone for sharedvec<T, N> {
+    fn clone(&self) -> Self {
+        let repr = match &self.repr {
+            repr::inline(vec) => repr::inline(vec.iter().cloned().collect()),
+            repr::heap(heap) => repr::heap(Arc::clone(heap)),
+        };
+        Self { repr }
+    }
+}
+
+impl<T, const N: usize> Deref for sharedvec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> Default for sharedvec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Takes over the heap buffer of a spilled tinyvec.
+impl<T, const N: usize> From<tinyvec<T, N>> for sharedvec<T, N> {
+    fn from(vec: tinyvec<T, N>) -> Self {
+        let repr = if vec.is_spilled() {
+            repr::heap(Arc::new(vec.into_vec()))
+        } else {
+            repr::inline(vec)
+        };
+        Self { repr }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for sharedvec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(tinyvec::from_iter(iter))
+    }
+}
+
+impl<T: Clone, const N: usize> Extend<T> for sharedvec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<sharedvec<U, M>>
+    for sharedvec<T, N>
+{
+    fn eq(&self, other: &sharedvec<U, M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for sharedvec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,178 @@
+//! Generational arena, see [`tinyslotmap`].
+
+use std::fmt;
+
+use crate::tinyvec;
+
+/// Handle returned by [`tinyslotmap::insert`]. Stays unique after its
+/// value is removed: the slot gets reused under a new generation, so a
+/// stale key just finds nothing.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct slotkey {
+    index: u32,
+    generation: u32,
+}
+
+enum entry<T> {
+    occupied(T),
+    /// Next free slot, `NONE` at the end of the list.
+    vacant(u32),
+}
+
+const NONE: u32 = u32::MAX;
+
+struct slot<T> {
+    /// Bumped every time the slot is vacated.
+    generation: u32,
+    entry: entry<T>,
+}
+
+/// Arena of values addressed by [`slotkey`]s, in a [`tinyvec`] of
+/// slots: up to N live values (counting freed slots not reused yet)
+/// stay on the stack. Insert and remove are O(1); removed slots go on
+/// a free list.
+///
+/// ```rust
+/// use vecstor::tinyslotmap;
+/// let mut entities: tinyslotmap<&str, 4> = tinyslotmap::new();
+/// let player = entities.insert("player");
+/// let enemy = entities.insert("enemy");
+/// assert_eq!(entities.remove(enemy), Some("enemy"));
+/// let pickup = entities.insert("pickup");
+/// assert_eq!(entities.get(enemy), None);
+/// assert_eq!(entities.get(pickup), Some(&"pickup"));
+/// assert_eq!(entities.get(player), Some(&"player"));
+/// ```
+pub struct tinyslotmap<T, const N: usize> {
+    slots: tinyvec<slot<T>, N>,
+    free: u32,
+    len: usize,
+}
+
+impl<T, const N: usize> tinyslotmap<T, N> {
+    pub fn new() -> Self {
+        Self {
+            slots: tinyvec::new(),
+            free: NONE,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the slots moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.slots.is_spilled()
+    }
+
+    /// Store `value`, reusing a freed slot if there is one.
+    /// Panics past `u32::MAX - 1` slots.
+    pub fn insert(&mut self, value: T) -> slotkey {
+        self.len += 1;
+        if self.free != NONE {
+            let index = self.free;
+            let slot = &mut self.slots.as_mut_slice()[index as usize];
+            let entry::vacant(next) = slot.entry else {
+                unreachable!("occupied slot on the free list");
+            };
+            self.free = next;
+            slot.entry = entry::occupied(value);
+            return slotkey {
+                index,
+                generation: slot.generation,
+            };
+        }
+        let index = u32::try_from(self.slots.len())
+            .ok()
+            .filter(|&i| i != NONE)
+            .expect("tinyslotmap: too many slots");
+        self.slots.push(slot {
+            generation: 0,
+            entry: entry::occupied(value),
+        });
+        slotkey {
+            index,
+            generation: 0,
+        }
+    }
+
+    fn slot(&self, key: slotkey) -> Option<&slot<T>> {
+        let slot = self.slots.get(key.index as usize)?;
+        (slot.generation == key.generation).then_some(slot)
+    }
+
+    fn slot_mut(&mut self, key: slotkey) -> Option<&mut slot<T>> {
+        let slot = self.slots.get_mut(key.index as usize)?;
+        (slot.generation == key.generation).then_some(slot)
+    }
+
+    pub fn get(&self, key: slotkey) -> Option<&T> {
+        match &self.slot(key)?.entry {
+            entry::occupied(value) => Some(value),
+            entry::vacant(_) => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: slotkey) -> Option<&mut T> {
+        match &mut self.slot_mut(key)?.entry {
+            entry::occupied(value) => Some(value),
+            entry::vacant(_) => None,
+        }
+    }
+
+    pub fn contains(&self, key: slotkey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Take the value out, invalidating `key` and every copy of it.
+    pub fn remove(&mut self, key: slotkey) -> Option<T> {
+        let free = self.free;
+        let slot = self.slot_mut(key)?;
+        if let entry::vacant(_) = slot.entry {
+            return None;
+        }
+        let entry::occupied(value) = std::mem::replace(&mut slot.entry, entry::vacant(free)) else {
+            unreachable!()
+        };
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free = key.index;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Live values with their keys, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (slotkey, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match &slot.entry {
+                entry::occupied(value) => Some((
+                    slotkey {
+                        index: index as u32,
+                        generation: slot.generation,
+                    },
+                    value,
+                )),
+                entry::vacant(_) => None,
+            })
+    }
+}
+
+impl<T, const N: usize> Default for tinyslotmap<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: fmt::Debug, const N: usize> fmt::Debug for tinyslotmap<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,227 @@
+//! Vector kept in sorted order, see [`sortedvec`].
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+use crate::tinyvec;
+
+/// [`tinyvec`] whose elements are always sorted ascending, with
+/// binary-search lookups. Equal elements are kept, in insertion
+/// order, so it works as a small multiset; check `contains()` first
+/// for set semantics.
+///
+/// ```rust
+/// use vecstor::sortedvec;
+/// let mut ids: sortedvec<u32, 8> = sortedvec::new();
+/// for id in [30, 10, 20] {
+///     ids.insert(id);
+/// }
+/// assert_eq!(ids.as_slice(), [10, 20, 30]);
+/// assert_eq!(ids.range(15..), [20, 30]);
+/// ```
+pub struct sortedvec<T, const N: usize> {
+    data: tinyvec<T, N>,
+}
+
+impl<T: Ord, const N: usize> sortedvec<T, N> {
+    pub fn new() -> Self {
+        Self {
+            data: tinyvec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Insert `value` after any equal elements, returning
+    /// where it went.
+    pub fn insert(&mut self, value: T) -> usize {
+        let at = self.as_slice().partition_point(|e| *e <= value);
+        self.data.insert(at, value);
+        at
+    }
+
+    /// Position of some element equal to `value`, or where it would go.
+    pub fn binary_search<Q: ?Sized + Ord>(&self, value: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+    {
+        self.as_slice().binary_search_by(|e| e.borrow().cmp(value))
+    }
+
+    pub fn contains<Q: ?Sized + Ord>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+    {
+        self.binary_search(value).is_ok()
+    }
+
+    /// Remove one element equal to `value`.
+    pub fn remove<Q: ?Sized + Ord>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+    {
+        let at = self.binary_search(value).ok()?;
+        self.data.remove(at)
+    }
+
+    /// The elements within `range`, as a slice.
+    pub fn range<Q: ?Sized + Ord, R: RangeBounds<Q>>(&self, range: R) -> &[T]
+    where
+        T: Borrow<Q>,
+    {
+        let slice = self.as_slice();
+        let start = match range.start_bound() {
+            Bound::Included(q) => slice.partition_point(|e| e.borrow() < q),
+            Bound::Excluded(q) => slice.partition_point(|e| e.borrow() <= q),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(q) => slice.partition_point(|e| e.borrow() <= q),
+            Bound::Excluded(q) => slice.partition_point(|e| e.borrow() < q),
+            Bound::Unbounded => slice.len(),
+        };
+        &slice[start..end.max(start)]
+    }
+
+    /// Smallest element.
+    pub fn first(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    /// Largest element.
+    pub fn last(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// The sorted elements.
+    pub fn into_inner(self) -> tinyvec<T, N> {
+        self.data
+    }
+}
+
+/// Set algebra by one merge-like pass over both sorted runs. An
+/// element present k times here and m times in `other` counts as
+/// present `min(k, m)` times in both, so on duplicate-free vectors
+/// these are the usual set operations.
+///
+/// ```rust
+/// use vecstor::sortedvec;
+/// let a: sortedvec<u32, 8> = [1, 3, 5, 7].into_iter().collect();
+/// let b: sortedvec<u32, 8> = [3, 4, 5].into_iter().collect();
+/// assert_eq!(a.intersection(&b).as_slice(), [3, 5]);
+/// assert_eq!(a.union(&b).as_slice(), [1, 3, 4, 5, 7]);
+/// assert_eq!(a.difference(&b).as_slice(), [1, 7]);
+/// ```
+impl<T: Ord + Clone, const N: usize> sortedvec<T, N> {
+    /// Walk both runs, calling `keep(element, in_self, in_other)` for
+    /// each element of the union in order.
+    fn walk<const M: usize>(&self, other: &sortedvec<T, M>, mut keep: impl FnMut(&T, bool, bool)) {
+        let (mut a, mut b) = (self.as_slice(), other.as_slice());
+        loop {
+            match (a.first(), b.first()) {
+                (Some(x), Some(y)) => match x.cmp(y) {
+                    std::cmp::Ordering::Less => {
+                        keep(x, true, false);
+                        a = &a[1..];
+                    }
+                    std::cmp::Ordering::Greater => {
+                        keep(y, false, true);
+                        b = &b[1..];
+                    }
+                    std::cmp::Ordering::Equal => {
+                        keep(x, true, true);
+                        a = &a[1..];
+                        b = &b[1..];
+                    }
+                },
+                (Some(x), None) => {
+                    keep(x, true, false);
+                    a = &a[1..];
+                }
+                (None, Some(y)) => {
+                    keep(y, false, true);
+                    b = &b[1..];
+                }
+                (None, None) => return,
+            }
+        }
+    }
+
+    fn filtered<const M: usize>(
+        &self,
+        other: &sortedvec<T, M>,
+        pick: fn(bool, bool) -> bool,
+    ) -> Self {
+        let mut data = tinyvec::new();
+        self.walk(other, |element, mine, theirs| {
+            if pick(mine, theirs) {
+                data.push(element.clone());
+            }
+        });
+        Self { data }
+    }
+
+    /// Elements in both.
+    pub fn intersection<const M: usize>(&self, other: &sortedvec<T, M>) -> Self {
+        self.filtered(other, |mine, theirs| mine && theirs)
+    }
+
+    /// Elements in either.
+    pub fn union<const M: usize>(&self, other: &sortedvec<T, M>) -> Self {
+        self.filtered(other, |_, _| true)
+    }
+
+    /// Elements here but not in `other`.
+    pub fn difference<const M: usize>(&self, other: &sortedvec<T, M>) -> Self {
+        self.filtered(other, |mine, theirs| mine && !theirs)
+    }
+
+    /// Elements in exactly one of the two.
+    pub fn symmetric_difference<const M: usize>(&self, other: &sortedvec<T, M>) -> Self {
+        self.filtered(other, |mine, theirs| mine != theirs)
+    }
+
+    /// Whether every element is also in `other`.
+    pub fn is_subset<const M: usize>(&self, other: &sortedvec<T, M>) -> bool {
+        let mut subset = true;
+        self.walk(other, |_, mine, theirs| subset &= !mine || theirs);
+        subset
+    }
+}
+
+impl<T: Ord, const N: usize> Default for sortedvec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects, then sorts once (stably).
+impl<T: Ord, const N: usize> FromIterator<T> for sortedvec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut data: tinyvec<T, N> = iter.into_iter().collect();
+        data.as_mut_slice().sort();
+        Self { data }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<T: Ord + fmt::Debug, const N: usize> fmt::Debug for sortedvec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
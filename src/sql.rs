@@ -0,0 +1,65 @@
+//! sqlx Postgres support: `tinyvec<u8, N>` as BYTEA, `tinystring` as
+//! TEXT. Small values decode straight into inline storage instead of
+//! a `Vec<u8>` or `String` per row.
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use sqlx::{Decode, Encode, Type};
+
+use crate::{tinystring, tinyvec};
+
+impl<const N: usize> Type<Postgres> for tinyvec<u8, N> {
+    fn type_info() -> PgTypeInfo {
+        <&[u8] as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&[u8] as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, Postgres> for tinyvec<u8, N> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&[u8] as Encode<Postgres>>::encode(self.as_slice(), buf)
+    }
+}
+
+/// Binary results are copied in directly; text results (simple
+/// queries) are hex and go through sqlx's own `Vec<u8>` decoding.
+impl<'r, const N: usize> Decode<'r, Postgres> for tinyvec<u8, N> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let mut vec = tinyvec::new();
+        match value.format() {
+            PgValueFormat::Binary => {
+                vec.extend_from_slice(<&[u8] as Decode<Postgres>>::decode(value)?)
+            }
+            PgValueFormat::Text => {
+                vec.extend_from_slice(&<Vec<u8> as Decode<Postgres>>::decode(value)?)
+            }
+        }
+        Ok(vec)
+    }
+}
+
+impl<const N: usize> Type<Postgres> for tinystring<N> {
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, Postgres> for tinystring<N> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode(self.as_str(), buf)
+    }
+}
+
+impl<'r, const N: usize> Decode<'r, Postgres> for tinystring<N> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(tinystring::from(<&str as Decode<Postgres>>::decode(value)?))
+    }
+}
@@ -0,0 +1,62 @@
+//! Process-wide allocation counters for tinyvecs, see [`stats()`].
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+static spills: AtomicU64 = AtomicU64::new(0);
+static reallocations: AtomicU64 = AtomicU64::new(0);
+static heap_bytes: AtomicUsize = AtomicUsize::new(0);
+static peak_heap_bytes: AtomicUsize = AtomicUsize::new(0);
+
+/// What [`stats()`] returns, summed over every tinyvec in the process
+/// since it started.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct counters {
+    /// Moves from the inline slots to the heap.
+    pub spills: u64,
+    /// Heap buffers grown after the spill.
+    pub reallocations: u64,
+    /// Heap bytes held by tinyvecs right now.
+    pub heap_bytes: usize,
+    /// The most `heap_bytes` ever was.
+    pub peak_heap_bytes: usize,
+}
+
+/// Whether the small-vector optimization pays off across the process:
+/// few spills against many vectors means N is well chosen.
+///
+/// ```rust
+/// use vecstor::{stats, tinyvec};
+/// let before = stats();
+/// let mut v: tinyvec<u64, 2> = tinyvec::new();
+/// v.extend_from_slice(&[1, 2, 3]);
+/// assert!(stats().spills > before.spills);
+/// assert!(stats().peak_heap_bytes >= 3 * 8);
+/// ```
+pub fn stats() -> counters {
+    counters {
+        spills: spills.load(Ordering::Relaxed),
+        reallocations: reallocations.load(Ordering::Relaxed),
+        heap_bytes: heap_bytes.load(Ordering::Relaxed),
+        peak_heap_bytes: peak_heap_bytes.load(Ordering::Relaxed),
+    }
+}
+
+pub(crate) fn spilled(bytes: usize) {
+    spills.fetch_add(1, Ordering::Relaxed);
+    allocated(bytes);
+}
+
+pub(crate) fn reallocated(old_bytes: usize, new_bytes: usize) {
+    reallocations.fetch_add(1, Ordering::Relaxed);
+    freed(old_bytes);
+    allocated(new_bytes);
+}
+
+pub(crate) fn allocated(bytes: usize) {
+    let now = heap_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    peak_heap_bytes.fetch_max(now, Ordering::Relaxed);
+}
+
+pub(crate) fn freed(bytes: usize) {
+    heap_bytes.fetch_sub(bytes, Ordering::Relaxed);
+}
@@ -0,0 +1,39 @@
+//! `futures` integration: the owning iterator as a `Stream`, and
+//! batching a stream into a tinyvec.
+
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::{owned_iter, storage, tinyvec};
+
+/// Always ready, yields the elements in order.
+impl<T: Unpin, S: storage<T> + Unpin> Stream for owned_iter<T, S> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<T>> {
+        Poll::Ready(self.get_mut().next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        Iterator::size_hint(self)
+    }
+}
+
+/// Up to `max` items from `stream`, fewer if it ends first. The
+/// stream can be polled again for the next batch.
+pub async fn collect_stream<St, const N: usize>(stream: &mut St, max: usize) -> tinyvec<St::Item, N>
+where
+    St: Stream + Unpin,
+{
+    let mut batch = tinyvec::new();
+    while batch.len() < max {
+        match poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await {
+            Some(item) => batch.push(item),
+            None => break,
+        }
+    }
+    batch
+}
@@ -0,0 +1,259 @@
+//! UTF-8 string with inline storage.
+
+use crate::tinyvec;
+
+/// String that keeps up to N bytes of UTF-8 inline and moves to the
+/// heap past that, the string version of [`tinyvec`]. Short names and
+/// keys never allocate.
+///
+/// ```rust
+/// use vecstor::tinystring;
+/// let mut key: tinystring<16> = tinystring::new();
+/// key.push_str("user:");
+/// key.push_str("42");
+/// assert_eq!(key.as_str(), "user:42");
+/// assert!(!key.is_spilled());
+/// ```
+pub struct tinystring<const N: usize> {
+    /// Always valid UTF-8.
+    bytes: tinyvec<u8, N>,
+}
+
+impl<const N: usize> tinystring<N> {
+    /// New, empty string. Nothing is allocated until it
+    /// outgrows N bytes.
+    pub fn new() -> Self {
+        Self {
+            bytes: tinyvec::new(),
+        }
+    }
+
+    /// Take over `bytes` if they are valid UTF-8, keeping their
+    /// storage, inline or heap, as it is.
+    ///
+    /// ```rust
+    /// use vecstor::{tiny_vec, tinystring, tinyvec};
+    /// let packet: tinyvec<u8, 8> = tiny_vec![b'o', b'k'];
+    /// assert_eq!(tinystring::from_utf8(packet).unwrap().as_str(), "ok");
+    /// assert!(tinystring::<8>::from_utf8(tiny_vec![0xff]).is_err());
+    /// ```
+    pub fn from_utf8(bytes: tinyvec<u8, N>) -> Result<Self, std::str::Utf8Error> {
+        std::str::from_utf8(bytes.as_slice())?;
+        Ok(Self { bytes })
+    }
+
+    /// `from_utf8()` without the check.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be valid UTF-8.
+    pub unsafe fn from_utf8_unchecked(bytes: tinyvec<u8, N>) -> Self {
+        Self { bytes }
+    }
+
+    /// The bytes, storage and all.
+    pub fn into_bytes(self) -> tinyvec<u8, N> {
+        self.bytes
+    }
+
+    /// Length in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Bytes that fit before the next allocation.
+    pub fn capacity(&self) -> usize {
+        self.bytes.total_capacity()
+    }
+
+    /// Whether the bytes moved to the heap.
+    pub fn is_spilled(&self) -> bool {
+        self.bytes.is_spilled()
+    }
+
+    /// Append `s`.
+    pub fn push_str(&mut self, s: &str) {
+        self.bytes.extend_from_slice(s.as_bytes());
+    }
+
+    /// Append one character.
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Remove and return the last character.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.truncate(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Shorten to `len` bytes. Does nothing if `len >= self.len()`.
+    ///
+    /// Panics if `len` is not on a char boundary.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            assert!(
+                self.as_str().is_char_boundary(len),
+                "truncate: {len} is not a char boundary"
+            );
+            self.bytes.truncate(len);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { std::str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+impl<const N: usize> std::ops::Deref for tinystring<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> std::ops::DerefMut for tinystring<N> {
+    fn deref_mut(&mut self) -> &mut str {
+        unsafe { std::str::from_utf8_unchecked_mut(self.bytes.as_mut_slice()) }
+    }
+}
+
+/// So `write!(s, ...)` works.
+impl<const N: usize> std::fmt::Write for tinystring<N> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> std::fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for tinystring<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Clone for tinystring<N> {
+    fn clone(&self) -> Self {
+        let mut copy = Self::new();
+        copy.push_str(self.as_str());
+        copy
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<const N: usize> std::fmt::Debug for tinystring<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<const N: usize> std::fmt::Display for tinystring<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> From<&str> for tinystring<N> {
+    fn from(s: &str) -> Self {
+        let mut string = Self::new();
+        string.push_str(s);
+        string
+    }
+}
+
+/// Reuses the allocation of `s` when it doesn't fit inline.
+impl<const N: usize> From<String> for tinystring<N> {
+    fn from(s: String) -> Self {
+        Self {
+            bytes: tinyvec::from(s.into_bytes()),
+        }
+    }
+}
+
+impl<const N: usize> std::str::FromStr for tinystring<N> {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<tinystring<M>> for tinystring<N> {
+    fn eq(&self, other: &tinystring<M>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for tinystring<N> {}
+
+impl<const N: usize> PartialEq<str> for tinystring<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for tinystring<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<tinystring<N>> for &str {
+    fn eq(&self, other: &tinystring<N>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<const N: usize> std::hash::Hash for tinystring<N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for tinystring<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for tinystring<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct visitor<const N: usize>;
+
+        impl<const N: usize> serde::de::Visitor<'_> for visitor<N> {
+            type Value = tinystring<N>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<tinystring<N>, E> {
+                Ok(tinystring::from(s))
+            }
+        }
+
+        deserializer.deserialize_str(visitor)
+    }
+}
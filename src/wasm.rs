@@ -0,0 +1,43 @@
+//! Conversions between numeric tinyvecs and `js_sys` typed arrays.
+//!
+//! Each direction is a single copy across the JS boundary; nothing
+//! goes through an intermediate `Vec`. Going to JS allocates the typed
+//! array, coming back fills the tinyvec in place, so a chunk that fits
+//! in N stays on the stack.
+
+use js_sys::{
+    Float32Array, Float64Array, Int8Array, Int16Array, Int32Array, Uint8Array, Uint16Array,
+    Uint32Array,
+};
+
+use crate::tinyvec;
+
+macro_rules! impl_typed_array {
+    ($($elem:ty => $array:ty),* $(,)?) => {$(
+        impl<const N: usize> From<&tinyvec<$elem, N>> for $array {
+            fn from(vec: &tinyvec<$elem, N>) -> Self {
+                <$array>::from(vec.as_slice())
+            }
+        }
+
+        impl<const N: usize> From<&$array> for tinyvec<$elem, N> {
+            fn from(array: &$array) -> Self {
+                let mut vec = tinyvec::new();
+                vec.resize(array.length() as usize, 0 as $elem);
+                array.copy_to(vec.as_mut_slice());
+                vec
+            }
+        }
+    )*};
+}
+
+impl_typed_array! {
+    u8 => Uint8Array,
+    i8 => Int8Array,
+    u16 => Uint16Array,
+    i16 => Int16Array,
+    u32 => Uint32Array,
+    i32 => Int32Array,
+    f32 => Float32Array,
+    f64 => Float64Array,
+}
@@ -0,0 +1,53 @@
+//! Growing by zeroed elements with a single `memset`, instead of
+//! writing a zero per element.
+
+use crate::{storage, storagevec};
+
+/// Types for which all-zero bytes are a valid value.
+///
+/// # Safety
+///
+/// The all-zero bit pattern must be a valid `Self`.
+#[cfg(not(feature = "bytemuck"))]
+pub unsafe trait zeroable {}
+
+#[cfg(not(feature = "bytemuck"))]
+macro_rules! impl_zeroable {
+    ($($ty:ty),+) => {$(
+        unsafe impl zeroable for $ty {}
+    )+};
+}
+
+#[cfg(not(feature = "bytemuck"))]
+impl_zeroable!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+/// With the `bytemuck` feature, any `Zeroable` type.
+#[cfg(feature = "bytemuck")]
+pub use bytemuck::Zeroable as zeroable;
+
+impl<T: zeroable, S: storage<T>> storagevec<T, S> {
+    /// Append `count` zeroed elements.
+    ///
+    /// Panics if the storage can't grow.
+    pub fn extend_zeroed(&mut self, count: usize) {
+        if !self.storage.reserve(count) {
+            panic!("extend_zeroed: storage is full");
+        }
+        let len = self.len();
+        unsafe {
+            self.storage.as_mut_ptr().add(len).write_bytes(0, count);
+            self.storage.set_len(len + count);
+        }
+        self.check();
+    }
+
+    /// `resize()` with zeroes: zero-extend or truncate to `new_len`.
+    pub fn resize_zeroed(&mut self, new_len: usize) {
+        match new_len.checked_sub(self.len()) {
+            Some(count) => self.extend_zeroed(count),
+            None => self.truncate(new_len),
+        }
+    }
+}